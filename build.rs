@@ -0,0 +1,4 @@
+// `embed_migrations!` only notices new migration directories when the crate is rebuilt
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
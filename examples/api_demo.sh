@@ -2,6 +2,7 @@
 
 # Blog Rust API Demo Script
 # Make sure the server is running with: cargo run
+# Write requests need an API token: export BLOG_TOKEN=$(cargo run -q -- token create demo | tail -n 1)
 
 echo "🚀 Blog Rust API Demo"
 echo "===================="
@@ -10,12 +11,19 @@ echo
 # Base URL
 BASE_URL="http://localhost:8080"
 
+if [ -z "$BLOG_TOKEN" ]; then
+  echo "❌ BLOG_TOKEN is not set; mint one with: cargo run -- token create demo"
+  exit 1
+fi
+AUTH="Authorization: Bearer $BLOG_TOKEN"
+
 echo "1. Getting all posts..."
 curl -s -X GET "$BASE_URL/posts" | jq '.' || curl -s -X GET "$BASE_URL/posts"
 echo -e "\n"
 
 echo "2. Creating a new post..."
 NEW_POST=$(curl -s -X POST "$BASE_URL/posts" \
+  -H "$AUTH" \
   -H "Content-Type: application/json" \
   -d '{
     "title": "Demo Post",
@@ -33,11 +41,13 @@ echo -e "\n"
 
 echo "4. Updating the post..."
 curl -s -X PUT "$BASE_URL/posts/$POST_ID" \
+  -H "$AUTH" \
   -H "Content-Type: application/json" \
   -d '{
     "title": "Updated Demo Post",
     "body": "This post has been updated via the API!"
   }' | jq '.' || curl -s -X PUT "$BASE_URL/posts/$POST_ID" \
+  -H "$AUTH" \
   -H "Content-Type: application/json" \
   -d '{
     "title": "Updated Demo Post",
@@ -50,7 +60,7 @@ curl -s -X GET "$BASE_URL/posts/$POST_ID" | jq '.' || curl -s -X GET "$BASE_URL/
 echo -e "\n"
 
 echo "6. Deleting the post..."
-curl -s -X DELETE "$BASE_URL/posts/$POST_ID" | jq '.' || curl -s -X DELETE "$BASE_URL/posts/$POST_ID"
+curl -s -X DELETE "$BASE_URL/posts/$POST_ID" -H "$AUTH" | jq '.' || curl -s -X DELETE "$BASE_URL/posts/$POST_ID" -H "$AUTH"
 echo -e "\n"
 
 echo "7. Trying to get the deleted post (should return 404)..."
@@ -0,0 +1 @@
+DROP TABLE slug_history
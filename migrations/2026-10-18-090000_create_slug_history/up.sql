@@ -0,0 +1,5 @@
+CREATE TABLE slug_history (
+  slug VARCHAR NOT NULL PRIMARY KEY,
+  post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  retired_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+)
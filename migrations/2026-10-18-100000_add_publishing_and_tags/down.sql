@@ -0,0 +1,5 @@
+DROP TABLE post_tags;
+DROP INDEX posts_published_at;
+ALTER TABLE posts DROP COLUMN published_at;
+ALTER TABLE posts DROP COLUMN updated_at;
+ALTER TABLE posts DROP COLUMN created_at;
@@ -0,0 +1,17 @@
+-- SQLite cannot add a column with a non-constant default, so backfill existing rows afterwards
+ALTER TABLE posts ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT '1970-01-01 00:00:00';
+ALTER TABLE posts ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT '1970-01-01 00:00:00';
+ALTER TABLE posts ADD COLUMN published_at TIMESTAMP;
+
+-- Posts created before drafts existed were public immediately
+UPDATE posts SET created_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP, published_at = CURRENT_TIMESTAMP;
+
+CREATE INDEX posts_published_at ON posts (published_at);
+
+CREATE TABLE post_tags (
+  post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  tag VARCHAR NOT NULL,
+  PRIMARY KEY (post_id, tag)
+);
+
+CREATE INDEX post_tags_tag ON post_tags (tag);
@@ -0,0 +1,3 @@
+DROP INDEX posts_author_id;
+ALTER TABLE posts DROP COLUMN author_id;
+ALTER TABLE users DROP COLUMN role
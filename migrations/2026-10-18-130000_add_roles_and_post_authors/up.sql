@@ -0,0 +1,9 @@
+ALTER TABLE users ADD COLUMN role VARCHAR NOT NULL DEFAULT 'contributor';
+
+-- Accounts created before roles existed could do everything
+UPDATE users SET role = 'admin';
+
+-- Posts created before authors existed, or through API tokens, have no author
+ALTER TABLE posts ADD COLUMN author_id INTEGER REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX posts_author_id ON posts (author_id);
@@ -0,0 +1,11 @@
+-- Refresh tokens renew short-lived JWT access tokens; like sessions, only a SHA-256 hash is stored
+CREATE TABLE refresh_tokens (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  token_hash VARCHAR NOT NULL UNIQUE,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  expires_at TIMESTAMP NOT NULL,
+  revoked_at TIMESTAMP
+);
+
+CREATE INDEX refresh_tokens_user_id ON refresh_tokens (user_id);
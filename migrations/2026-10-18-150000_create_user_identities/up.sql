@@ -0,0 +1,11 @@
+-- Links a local user to an account at an OpenID Connect provider; `subject` is the provider's stable user ID
+CREATE TABLE user_identities (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  issuer VARCHAR NOT NULL,
+  subject VARCHAR NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE (issuer, subject)
+);
+
+CREATE INDEX user_identities_user_id ON user_identities (user_id);
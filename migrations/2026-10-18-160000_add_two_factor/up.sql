@@ -0,0 +1,21 @@
+-- The TOTP secret is set when enrollment starts and counts once `totp_enabled_at` is set;
+-- `totp_last_step` is the time step of the last accepted code, so no code works twice
+ALTER TABLE users ADD COLUMN totp_secret VARCHAR;
+ALTER TABLE users ADD COLUMN totp_enabled_at TIMESTAMP;
+ALTER TABLE users ADD COLUMN totp_last_step BIGINT;
+
+-- Single-use codes for when the authenticator is lost; like sessions, only a SHA-256 hash is stored
+CREATE TABLE recovery_codes (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  code_hash VARCHAR NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  used_at TIMESTAMP
+);
+
+CREATE INDEX recovery_codes_user_id ON recovery_codes (user_id);
+
+-- Roles whose users must enroll in two-factor authentication
+CREATE TABLE two_factor_roles (
+  role VARCHAR NOT NULL PRIMARY KEY
+);
@@ -0,0 +1,37 @@
+-- Rename any duplicates left from before slugs were checked, keeping the slug on the oldest post
+-- A renamed post becomes `<slug>-<id>`, or `<slug>-<id>-<n>` with the lowest free n from 2 when that is taken;
+-- the numbered form also skips every `<slug>-<id>` of another duplicate, so no two renames can meet
+CREATE TEMPORARY TABLE slug_renames (
+  id INTEGER NOT NULL PRIMARY KEY,
+  old_slug VARCHAR NOT NULL,
+  new_slug VARCHAR NOT NULL
+);
+
+INSERT INTO slug_renames (id, old_slug, new_slug)
+WITH RECURSIVE
+  duplicates(id, slug) AS (
+    SELECT id, slug FROM posts
+    WHERE id NOT IN (SELECT MIN(id) FROM posts GROUP BY slug)
+  ),
+  candidates(id, slug, n, candidate) AS (
+    SELECT id, slug, 1, slug || '-' || id FROM duplicates
+    UNION ALL
+    SELECT id, slug, n + 1, slug || '-' || id || '-' || (n + 1) FROM candidates
+    WHERE EXISTS (SELECT 1 FROM posts WHERE posts.slug = candidates.candidate)
+      OR (n > 1 AND EXISTS (SELECT 1 FROM duplicates WHERE duplicates.slug || '-' || duplicates.id = candidates.candidate))
+  )
+SELECT id, slug, candidate FROM candidates AS c
+WHERE n = (SELECT MAX(n) FROM candidates WHERE candidates.id = c.id);
+
+-- The old slug keeps redirecting to the renamed post while the oldest post does not hold it
+DELETE FROM slug_history WHERE slug IN (SELECT new_slug FROM slug_renames);
+INSERT OR IGNORE INTO slug_history (slug, post_id)
+SELECT old_slug, id FROM slug_renames ORDER BY id;
+
+UPDATE posts SET slug = (SELECT new_slug FROM slug_renames WHERE slug_renames.id = posts.id)
+WHERE id IN (SELECT id FROM slug_renames);
+
+DROP TABLE slug_renames;
+
+-- Closes the race between checking a slug and writing it
+CREATE UNIQUE INDEX posts_slug_unique ON posts (slug);
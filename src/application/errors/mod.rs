@@ -0,0 +1,3 @@
+pub mod service_error;
+
+pub use service_error::ServiceError;
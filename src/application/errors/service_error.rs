@@ -0,0 +1,69 @@
+use crate::domain::{IdentityProviderError, ValidationErrors};
+use std::fmt;
+
+/// Errors returned by application services
+/// Lets adapters tell client mistakes apart from infrastructure failures
+#[derive(Debug)]
+pub enum ServiceError {
+    /// The entity failed domain validation
+    Validation(ValidationErrors),
+    /// The request was malformed (e.g. a non-positive ID)
+    InvalidInput(String),
+    /// The targeted entity does not exist
+    NotFound(String),
+    /// The credentials were wrong; deliberately vague about which part
+    Unauthenticated(String),
+    /// The actor is authenticated but a policy denies the action
+    Forbidden(String),
+    /// The password was right, but the account also needs a TOTP or recovery code
+    TwoFactorRequired(String),
+    /// The account is temporarily locked after repeated failed logins
+    Locked(String),
+    /// A concurrent write changed the state the request relied on
+    Conflict(String),
+    /// The underlying repository failed
+    Repository(String),
+    /// An external service, such as the identity provider, failed or could not be reached
+    Upstream(String),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Validation(errors) => write!(f, "Validation failed: {}", errors),
+            ServiceError::InvalidInput(message) => write!(f, "{}", message),
+            ServiceError::NotFound(message) => write!(f, "{}", message),
+            ServiceError::Unauthenticated(message) => write!(f, "{}", message),
+            ServiceError::Forbidden(message) => write!(f, "{}", message),
+            ServiceError::TwoFactorRequired(message) => write!(f, "{}", message),
+            ServiceError::Locked(message) => write!(f, "{}", message),
+            ServiceError::Conflict(message) => write!(f, "{}", message),
+            ServiceError::Repository(message) => write!(f, "{}", message),
+            ServiceError::Upstream(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+impl From<ValidationErrors> for ServiceError {
+    fn from(errors: ValidationErrors) -> Self {
+        ServiceError::Validation(errors)
+    }
+}
+
+/// Repository ports report failures as plain strings
+impl From<String> for ServiceError {
+    fn from(message: String) -> Self {
+        ServiceError::Repository(message)
+    }
+}
+
+impl From<IdentityProviderError> for ServiceError {
+    fn from(error: IdentityProviderError) -> Self {
+        match error {
+            IdentityProviderError::Rejected(message) => ServiceError::Unauthenticated(message),
+            IdentityProviderError::Unavailable(message) => ServiceError::Upstream(message),
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod errors;
+pub mod policies;
+pub(crate) mod secrets;
+pub(crate) mod totp;
 pub mod use_cases;
 
+pub use errors::*;
+pub use policies::*;
 pub use use_cases::*;
@@ -0,0 +1,44 @@
+use crate::domain::{Principal, Role, User};
+
+/// Who a use case runs for, as far as policies are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    /// `None` when no user account is involved
+    pub user_id: Option<i32>,
+    pub role: Role,
+}
+
+impl Actor {
+    pub fn user(user: &User) -> Self {
+        Self {
+            user_id: user.id,
+            role: user.role,
+        }
+    }
+
+    /// The command line and API tokens: every right, on behalf of no user
+    pub fn system() -> Self {
+        Self {
+            user_id: None,
+            role: Role::Admin,
+        }
+    }
+
+    /// Whether the actor is the user identified by `user_id`
+    pub fn is(&self, user_id: Option<i32>) -> bool {
+        self.user_id.is_some() && self.user_id == user_id
+    }
+}
+
+impl From<&Principal> for Actor {
+    fn from(principal: &Principal) -> Self {
+        match principal {
+            Principal::Token(_) => Actor::system(),
+            Principal::User { user, .. } => Actor::user(user),
+            Principal::Jwt(claims) => Self {
+                user_id: Some(claims.user_id),
+                role: claims.role,
+            },
+        }
+    }
+}
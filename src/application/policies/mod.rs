@@ -0,0 +1,6 @@
+pub mod actor;
+pub mod post_policy;
+pub mod token_policy;
+pub mod two_factor_policy;
+
+pub use actor::Actor;
@@ -0,0 +1,199 @@
+use crate::application::errors::ServiceError;
+use crate::application::policies::Actor;
+use crate::domain::{Post, PostPatch, Role};
+use chrono::{DateTime, Utc};
+
+/// Published posts are public; drafts and scheduled posts are visible to editors and their author
+/// `actor` is `None` for anonymous readers
+pub fn can_view(actor: Option<&Actor>, post: &Post, now: DateTime<Utc>) -> bool {
+    post.is_published_at(now)
+        || actor.is_some_and(|actor| actor.role >= Role::Editor || actor.is(post.author_id))
+}
+
+/// Publishing, unpublishing and rescheduling are editorial decisions
+pub fn can_publish(actor: &Actor) -> Result<(), ServiceError> {
+    if actor.role >= Role::Editor {
+        return Ok(());
+    }
+    Err(forbidden("Only editors can publish, unpublish or reschedule posts"))
+}
+
+/// Anyone may write a post, but only editors may create it published
+pub fn can_create(actor: &Actor, post: &Post) -> Result<(), ServiceError> {
+    if post.published_at.is_some() {
+        can_publish(actor)?;
+    }
+    Ok(())
+}
+
+/// Editors edit any post, authors their own posts and contributors their own drafts
+/// `post` is the stored post before `changes` are applied
+pub fn can_edit(actor: &Actor, post: &Post, changes: &PostPatch) -> Result<(), ServiceError> {
+    if changes.published_at.is_some() {
+        can_publish(actor)?;
+    }
+
+    let owns = actor.is(post.author_id);
+    match actor.role {
+        Role::Admin | Role::Editor => Ok(()),
+        Role::Author if owns => Ok(()),
+        Role::Author => Err(forbidden("Authors can only edit their own posts")),
+        Role::Contributor if owns && post.published_at.is_none() => Ok(()),
+        Role::Contributor => Err(forbidden("Contributors can only edit their own drafts")),
+    }
+}
+
+/// Editors delete any post; everyone else only their own drafts
+pub fn can_delete(actor: &Actor, post: &Post) -> Result<(), ServiceError> {
+    if actor.role >= Role::Editor || (actor.is(post.author_id) && post.published_at.is_none()) {
+        return Ok(());
+    }
+    Err(forbidden("Only editors can delete published posts or posts of others"))
+}
+
+/// Imports create, overwrite and publish posts regardless of their author
+pub fn can_import(actor: &Actor) -> Result<(), ServiceError> {
+    if actor.role >= Role::Editor {
+        return Ok(());
+    }
+    Err(forbidden("Only editors can import posts"))
+}
+
+/// Exports contain every draft and scheduled post, so only editors may take one
+pub fn can_export(actor: &Actor) -> Result<(), ServiceError> {
+    if actor.role >= Role::Editor {
+        return Ok(());
+    }
+    Err(forbidden("Only editors can export posts"))
+}
+
+/// A released slug can be claimed by any post, so only editors may release one
+pub fn can_release_slug_redirect(actor: &Actor) -> Result<(), ServiceError> {
+    if actor.role >= Role::Editor {
+        return Ok(());
+    }
+    Err(forbidden("Only editors can release slug redirects"))
+}
+
+fn forbidden(reason: &str) -> ServiceError {
+    ServiceError::Forbidden(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: i32 = 1;
+    const OTHER: i32 = 2;
+    const ROLES: [Role; 4] = [Role::Contributor, Role::Author, Role::Editor, Role::Admin];
+
+    fn actor(role: Role) -> Actor {
+        Actor { user_id: Some(OWNER), role }
+    }
+
+    fn post(author: i32, published: bool) -> Post {
+        let mut post = Post::new("Title".to_string(), "title".to_string(), "Body".to_string());
+        post.author_id = Some(author);
+        post.published_at = published.then(|| Utc::now() - chrono::Duration::hours(1));
+        post
+    }
+
+    fn edit() -> PostPatch {
+        PostPatch { title: Some("New title".to_string()), ..PostPatch::default() }
+    }
+
+    fn publish() -> PostPatch {
+        PostPatch { published_at: Some(Some(Utc::now())), ..PostPatch::default() }
+    }
+
+    fn is_forbidden(result: Result<(), ServiceError>) -> bool {
+        matches!(result, Err(ServiceError::Forbidden(_)))
+    }
+
+    #[test]
+    fn only_editors_and_admins_publish() {
+        for role in ROLES {
+            let allowed = role >= Role::Editor;
+            assert_eq!(can_publish(&actor(role)).is_ok(), allowed, "{:?}", role);
+            assert_eq!(is_forbidden(can_publish(&actor(role))), !allowed, "{:?}", role);
+        }
+    }
+
+    #[test]
+    fn only_editors_and_admins_import() {
+        for role in ROLES {
+            let allowed = role >= Role::Editor;
+            assert_eq!(can_import(&actor(role)).is_ok(), allowed, "{:?}", role);
+            assert_eq!(is_forbidden(can_import(&actor(role))), !allowed, "{:?}", role);
+        }
+    }
+
+    #[test]
+    fn edit_rights_follow_role_ownership_and_publication() {
+        // (role, own draft, own published, foreign draft, foreign published)
+        let cases = [
+            (Role::Contributor, true, false, false, false),
+            (Role::Author, true, true, false, false),
+            (Role::Editor, true, true, true, true),
+            (Role::Admin, true, true, true, true),
+        ];
+        for (role, own_draft, own_published, foreign_draft, foreign_published) in cases {
+            let actor = actor(role);
+            assert_eq!(can_edit(&actor, &post(OWNER, false), &edit()).is_ok(), own_draft, "{:?} own draft", role);
+            assert_eq!(can_edit(&actor, &post(OWNER, true), &edit()).is_ok(), own_published, "{:?} own published", role);
+            assert_eq!(can_edit(&actor, &post(OTHER, false), &edit()).is_ok(), foreign_draft, "{:?} foreign draft", role);
+            assert_eq!(
+                can_edit(&actor, &post(OTHER, true), &edit()).is_ok(),
+                foreign_published,
+                "{:?} foreign published",
+                role
+            );
+        }
+    }
+
+    #[test]
+    fn changing_the_publication_date_needs_an_editor() {
+        for role in ROLES {
+            for target in [post(OWNER, false), post(OWNER, true), post(OTHER, false), post(OTHER, true)] {
+                let result = can_edit(&actor(role), &target, &publish());
+                assert_eq!(result.is_ok(), role >= Role::Editor, "{:?}", role);
+            }
+        }
+    }
+
+    #[test]
+    fn delete_rights_follow_role_ownership_and_publication() {
+        for role in ROLES {
+            let actor = actor(role);
+            let editor = role >= Role::Editor;
+            assert!(can_delete(&actor, &post(OWNER, false)).is_ok(), "{:?} own draft", role);
+            assert_eq!(can_delete(&actor, &post(OWNER, true)).is_ok(), editor, "{:?} own published", role);
+            assert_eq!(can_delete(&actor, &post(OTHER, false)).is_ok(), editor, "{:?} foreign draft", role);
+            assert_eq!(can_delete(&actor, &post(OTHER, true)).is_ok(), editor, "{:?} foreign published", role);
+            assert_eq!(is_forbidden(can_delete(&actor, &post(OTHER, true))), !editor, "{:?}", role);
+        }
+    }
+
+    #[test]
+    fn posts_without_an_author_belong_to_nobody() {
+        let contributor = Actor { user_id: None, role: Role::Contributor };
+        let mut orphan = post(OWNER, false);
+        orphan.author_id = None;
+
+        assert!(is_forbidden(can_edit(&contributor, &orphan, &edit())));
+        assert!(is_forbidden(can_delete(&contributor, &orphan)));
+    }
+
+    #[test]
+    fn drafts_are_visible_to_their_author_and_editors_only() {
+        let now = Utc::now();
+        for role in ROLES {
+            let actor = actor(role);
+            assert!(can_view(Some(&actor), &post(OWNER, false), now), "{:?} own draft", role);
+            assert_eq!(can_view(Some(&actor), &post(OTHER, false), now), role >= Role::Editor, "{:?} foreign draft", role);
+            assert!(can_view(Some(&actor), &post(OTHER, true), now), "{:?} foreign published", role);
+        }
+        assert!(!can_view(None, &post(OWNER, false), now));
+        assert!(can_view(None, &post(OWNER, true), now));
+    }
+}
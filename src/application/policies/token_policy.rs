@@ -0,0 +1,11 @@
+use crate::application::errors::ServiceError;
+use crate::application::policies::Actor;
+use crate::domain::Role;
+
+/// Tokens act with every right, so only admins may mint, list or revoke them
+pub fn can_manage_tokens(actor: &Actor) -> Result<(), ServiceError> {
+    if actor.role == Role::Admin {
+        return Ok(());
+    }
+    Err(ServiceError::Forbidden("Only admins can manage API tokens".to_string()))
+}
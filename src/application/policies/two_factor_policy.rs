@@ -0,0 +1,11 @@
+use crate::application::errors::ServiceError;
+use crate::application::policies::Actor;
+use crate::domain::Role;
+
+/// Only admins decide which roles must use two-factor authentication
+pub fn can_require_two_factor(actor: &Actor) -> Result<(), ServiceError> {
+    if actor.role == Role::Admin {
+        return Ok(());
+    }
+    Err(ServiceError::Forbidden("Only admins can change the two-factor requirements".to_string()))
+}
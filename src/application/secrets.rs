@@ -0,0 +1,29 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Random bytes in a secret; 256 bits cannot be guessed, so a fast hash is enough to store it
+const SECRET_BYTES: usize = 32;
+
+/// A new random secret, hex-encoded after `prefix`
+pub(crate) fn generate_secret(prefix: &str) -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    format!("{}{}", prefix, to_hex(&bytes))
+}
+
+/// A random code short enough to copy by hand: 64 bits as four groups of four hex digits
+pub(crate) fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; 8];
+    rand::rng().fill_bytes(&mut bytes);
+    let hex = to_hex(&bytes);
+    [&hex[0..4], &hex[4..8], &hex[8..12], &hex[12..16]].join("-")
+}
+
+/// Hex-encoded SHA-256 of a secret, the only form in which secrets are stored
+pub(crate) fn hash_secret(secret: &str) -> String {
+    to_hex(&Sha256::digest(secret.as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
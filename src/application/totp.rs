@@ -0,0 +1,189 @@
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+/// RFC 6238 defaults, the only parameters every authenticator app supports
+const DIGITS: u32 = 6;
+const STEP_SECONDS: i64 = 30;
+
+/// Steps accepted on either side of the current one, for clock drift and slow typing
+const ALLOWED_DRIFT: i64 = 1;
+
+/// 160 bits, the size RFC 4226 recommends for HMAC-SHA1
+const SECRET_BYTES: usize = 20;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A new random secret, Base32-encoded as authenticator apps expect it
+pub(crate) fn generate_totp_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// The time step a code is valid for, if it matches the secret at `unix_time` give or take the allowed drift
+pub(crate) fn verify_totp(secret: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let key = base32_decode(secret)?;
+    if code.len() != DIGITS as usize || !code.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let current = unix_time.div_euclid(STEP_SECONDS);
+    (current - ALLOWED_DRIFT..=current + ALLOWED_DRIFT).find(|step| hotp(&key, *step as u64) == code)
+}
+
+/// `otpauth://` URI that authenticator apps import, usually from a QR code
+pub(crate) fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        secret,
+        percent_encode(issuer),
+        DIGITS,
+        STEP_SECONDS
+    )
+}
+
+/// RFC 4226 HOTP value of a counter, zero-padded
+fn hotp(key: &[u8], counter: u64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // Dynamic truncation: four bytes at an offset taken from the last nibble
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([digest[offset], digest[offset + 1], digest[offset + 2], digest[offset + 3]])
+        & 0x7fff_ffff;
+    format!("{:0width$}", value % 10u32.pow(DIGITS), width = DIGITS as usize)
+}
+
+/// RFC 4648 Base32 without padding
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for symbol in encoded.trim_end_matches('=').bytes() {
+        let value = BASE32_ALPHABET.iter().position(|candidate| *candidate == symbol.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+/// Percent-encoding of everything but RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ASCII secret "12345678901234567890" of the RFC 4226 and RFC 6238 test vectors
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+    const RFC_SECRET_BASE32: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn hotp_matches_the_rfc_4226_test_vectors() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(hotp(RFC_SECRET, counter as u64), *code, "counter {}", counter);
+        }
+    }
+
+    #[test]
+    fn totp_matches_the_rfc_6238_sha1_test_vectors() {
+        // The RFC lists eight digits; six-digit codes are their last six
+        let expected = [
+            (59, "287082"),
+            (1_111_111_109, "081804"),
+            (1_111_111_111, "050471"),
+            (1_234_567_890, "005924"),
+            (2_000_000_000, "279037"),
+            (20_000_000_000, "353130"),
+        ];
+        for (unix_time, code) in expected {
+            let step = unix_time / STEP_SECONDS;
+            assert_eq!(hotp(RFC_SECRET, step as u64), code, "time {}", unix_time);
+            assert_eq!(verify_totp(RFC_SECRET_BASE32, code, unix_time), Some(step), "time {}", unix_time);
+        }
+    }
+
+    #[test]
+    fn codes_are_accepted_one_step_either_side() {
+        // "287082" is the code of step 1, from 30 to 59 seconds
+        assert_eq!(verify_totp(RFC_SECRET_BASE32, "287082", 0), Some(1));
+        assert_eq!(verify_totp(RFC_SECRET_BASE32, "287082", 89), Some(1));
+        assert_eq!(verify_totp(RFC_SECRET_BASE32, "287082", 90), None);
+    }
+
+    #[test]
+    fn malformed_codes_and_secrets_are_refused() {
+        assert_eq!(verify_totp(RFC_SECRET_BASE32, "28708", 59), None);
+        assert_eq!(verify_totp(RFC_SECRET_BASE32, "2870821", 59), None);
+        assert_eq!(verify_totp(RFC_SECRET_BASE32, "28708a", 59), None);
+        assert_eq!(verify_totp("not base32!", "287082", 59), None);
+    }
+
+    #[test]
+    fn base32_matches_the_rfc_4648_test_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "MY"),
+            ("fo", "MZXQ"),
+            ("foo", "MZXW6"),
+            ("foob", "MZXW6YQ"),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI"),
+        ];
+        for (plain, encoded) in vectors {
+            assert_eq!(base32_encode(plain.as_bytes()), encoded);
+            assert_eq!(base32_decode(encoded).as_deref(), Some(plain.as_bytes()));
+        }
+        assert_eq!(base32_encode(RFC_SECRET), RFC_SECRET_BASE32);
+    }
+
+    #[test]
+    fn base32_decoding_accepts_padding_and_lowercase() {
+        assert_eq!(base32_decode("MZXW6YQ=").as_deref(), Some(b"foob".as_slice()));
+        assert_eq!(base32_decode("mzxw6ytboi").as_deref(), Some(b"foobar".as_slice()));
+    }
+
+    #[test]
+    fn generated_secrets_hold_160_bits() {
+        let secret = generate_totp_secret();
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).map(|key| key.len()), Some(SECRET_BYTES));
+    }
+}
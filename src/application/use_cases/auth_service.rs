@@ -0,0 +1,255 @@
+use crate::application::errors::ServiceError;
+use crate::application::secrets::{generate_secret, hash_secret};
+use crate::application::use_cases::{PendingSecondFactor, TwoFactorService};
+use crate::domain::validation::{Violation, ViolationCode};
+use crate::domain::{
+    normalize_email, PasswordHasher, Role, Session, SessionRepository, User, UserRepository, ValidationErrors,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// `last_seen_at` is refreshed at most this often, so every request does not cost a write
+const LAST_SEEN_RESOLUTION: Duration = Duration::minutes(1);
+
+/// Longest user agent kept with a session
+const USER_AGENT_MAX_LENGTH: usize = 512;
+
+const INVALID_CREDENTIALS: &str = "Invalid email or password";
+
+/// Where a login came from, shown when listing sessions
+#[derive(Debug, Clone, Default)]
+pub struct LoginClient {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// A successful login; `secret` goes into the session cookie and is never stored
+pub struct LoggedIn {
+    pub user: User,
+    pub session: Session,
+    pub secret: String,
+}
+
+/// Application service for user accounts, password logins and sessions
+pub struct AuthService {
+    users: Arc<dyn UserRepository>,
+    sessions: Arc<dyn SessionRepository>,
+    hasher: Arc<dyn PasswordHasher>,
+    two_factor: Arc<TwoFactorService>,
+    session_ttl: Duration,
+    /// Verified against when the email is unknown or has no password, so every failure takes as long
+    dummy_hash: OnceCell<String>,
+}
+
+impl AuthService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        sessions: Arc<dyn SessionRepository>,
+        hasher: Arc<dyn PasswordHasher>,
+        two_factor: Arc<TwoFactorService>,
+        session_ttl: Duration,
+    ) -> Self {
+        Self {
+            users,
+            sessions,
+            hasher,
+            two_factor,
+            session_ttl,
+            dummy_hash: OnceCell::new(),
+        }
+    }
+
+    /// Create user use case
+    pub async fn create_user(
+        &self,
+        email: String,
+        display_name: String,
+        role: Role,
+        password: &str,
+    ) -> Result<User, ServiceError> {
+        let mut user = User::new(email, display_name, role, String::new());
+        let mut errors = match user.validate() {
+            Ok(()) => ValidationErrors::new(),
+            Err(errors) => errors,
+        };
+        if let Err(password_errors) = User::validate_password(password) {
+            for violation in password_errors.violations() {
+                errors.add(violation.clone());
+            }
+        }
+        if self.users.find_by_email(&user.email).await?.is_some() {
+            errors.add(Violation::new("email", ViolationCode::Taken, "is already registered"));
+        }
+        errors.into_result()?;
+
+        user.password_hash = self.hasher.hash(password).await?;
+        Ok(self.users.save(user).await?)
+    }
+
+    /// List users use case
+    pub async fn list_users(&self) -> Result<Vec<User>, ServiceError> {
+        Ok(self.users.find_all().await?)
+    }
+
+    /// Get user by email use case
+    pub async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, ServiceError> {
+        Ok(self.users.find_by_email(&normalize_email(email)).await?)
+    }
+
+    /// Check an email and password, and the second factor of accounts that have one, for a session or for access tokens
+    /// [`MAX_FAILED_LOGINS`](crate::domain::MAX_FAILED_LOGINS) failures in a row lock the account for a while
+    pub async fn verify_credentials(&self, email: &str, password: &str, code: Option<&str>) -> Result<User, ServiceError> {
+        let now = Utc::now();
+        // Unknown emails and single sign-on accounts take as long to refuse as a wrong password
+        let Some(mut user) = self.users.find_by_email(&normalize_email(email)).await?.filter(User::has_password) else {
+            let dummy_hash = self.dummy_hash.get_or_try_init(|| self.hasher.hash(INVALID_CREDENTIALS)).await?;
+            self.hasher.verify(password, dummy_hash).await?;
+            return Err(ServiceError::Unauthenticated(INVALID_CREDENTIALS.to_string()));
+        };
+        let id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+
+        if user.is_locked_at(now) {
+            return Err(locked(&user));
+        }
+
+        if !self.hasher.verify(password, &user.password_hash).await? {
+            user.record_failed_login(now);
+            self.users.update_login_state(id, user.failed_login_attempts, user.locked_until).await?;
+            if user.is_locked_at(now) {
+                return Err(locked(&user));
+            }
+            return Err(ServiceError::Unauthenticated(INVALID_CREDENTIALS.to_string()));
+        }
+
+        self.check_second_factor(user, code).await
+    }
+
+    /// Check the second factor of a single sign-on login that the identity provider has completed
+    pub async fn verify_second_factor(&self, pending: &PendingSecondFactor, code: &str) -> Result<User, ServiceError> {
+        if pending.expires_at <= Utc::now() {
+            return Err(ServiceError::Unauthenticated("The sign-in has expired; start it again".to_string()));
+        }
+        let user = self
+            .users
+            .find_by_id(pending.user_id)
+            .await?
+            .ok_or_else(|| ServiceError::Unauthenticated("The account no longer exists".to_string()))?;
+        self.check_second_factor(user, Some(code)).await
+    }
+
+    /// Ask accounts with two-factor authentication for their code, then clear the failed logins of the user
+    async fn check_second_factor(&self, mut user: User, code: Option<&str>) -> Result<User, ServiceError> {
+        let id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+        if user.has_two_factor() {
+            let Some(code) = code.filter(|code| !code.trim().is_empty()) else {
+                return Err(ServiceError::TwoFactorRequired(
+                    "Enter the code from your authenticator app or a recovery code".to_string(),
+                ));
+            };
+            self.two_factor.check_code(&mut user, code).await?;
+        }
+
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            user.record_successful_login();
+            self.users.update_login_state(id, 0, None).await?;
+        }
+        Ok(user)
+    }
+
+    /// Login use case: check the password and open a session
+    pub async fn login(
+        &self,
+        email: &str,
+        password: &str,
+        code: Option<&str>,
+        client: LoginClient,
+    ) -> Result<LoggedIn, ServiceError> {
+        let user = self.verify_credentials(email, password, code).await?;
+        self.open_session(user, client).await
+    }
+
+    /// Open a session for a user who has been authenticated, by password or single sign-on
+    pub async fn open_session(&self, user: User, client: LoginClient) -> Result<LoggedIn, ServiceError> {
+        let id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+
+        let secret = generate_secret("");
+        let mut session = Session::new(id, hash_secret(&secret), Utc::now() + self.session_ttl);
+        session.user_agent = client
+            .user_agent
+            .map(|user_agent| user_agent.chars().take(USER_AGENT_MAX_LENGTH).collect());
+        session.ip_address = client.ip_address;
+        let session = self.sessions.save(session).await?;
+
+        Ok(LoggedIn { user, session, secret })
+    }
+
+    /// Logout use case: revoke the session behind a cookie secret; unknown secrets are ignored
+    pub async fn logout(&self, secret: &str) -> Result<(), ServiceError> {
+        if let Some(session) = self.sessions.find_by_hash(&hash_secret(secret)).await?
+            && let Some(id) = session.id
+        {
+            self.sessions.revoke(session.user_id, id, Utc::now()).await?;
+        }
+        Ok(())
+    }
+
+    /// Authenticate use case: the user and active session matching a cookie secret, if any
+    pub async fn authenticate_session(&self, secret: &str) -> Result<Option<(User, Session)>, ServiceError> {
+        let now = Utc::now();
+        let Some(mut session) = self.sessions.find_by_hash(&hash_secret(secret)).await? else {
+            return Ok(None);
+        };
+        if !session.is_active_at(now) {
+            return Ok(None);
+        }
+        let Some(user) = self.users.find_by_id(session.user_id).await? else {
+            return Ok(None);
+        };
+
+        if let Some(id) = session.id
+            && now - session.last_seen_at >= LAST_SEEN_RESOLUTION
+        {
+            self.sessions.touch(id, now).await?;
+            session.last_seen_at = now;
+        }
+        Ok(Some((user, session)))
+    }
+
+    /// List sessions use case: the active sessions of a user
+    pub async fn list_sessions(&self, user_id: i32) -> Result<Vec<Session>, ServiceError> {
+        Ok(self.sessions.find_active_by_user(user_id, Utc::now()).await?)
+    }
+
+    /// Revoke session use case; only the owner's active sessions can be revoked
+    pub async fn revoke_session(&self, user_id: i32, session_id: i32) -> Result<(), ServiceError> {
+        if session_id <= 0 {
+            return Err(ServiceError::InvalidInput("Invalid session ID".to_string()));
+        }
+        if !self.sessions.revoke(user_id, session_id, Utc::now()).await? {
+            return Err(ServiceError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Revoke every active session of a user, e.g. after a password leak; returns how many were revoked
+    pub async fn revoke_all_sessions(&self, user_id: i32) -> Result<usize, ServiceError> {
+        Ok(self.sessions.revoke_all(user_id, Utc::now()).await?)
+    }
+
+    /// Change role use case; sessions see it on their next request, but access tokens carry the role
+    /// they were issued with until they expire
+    pub async fn set_role(&self, user_id: i32, role: Role) -> Result<(), ServiceError> {
+        Ok(self.users.update_role(user_id, role).await?)
+    }
+
+    /// Unlock user use case: clear the lockout and failed-login counter
+    pub async fn unlock_user(&self, user_id: i32) -> Result<(), ServiceError> {
+        Ok(self.users.update_login_state(user_id, 0, None).await?)
+    }
+}
+
+pub(crate) fn locked(user: &User) -> ServiceError {
+    let until = user.locked_until.map_or_else(String::new, |at| format!(" until {}", at.to_rfc3339()));
+    ServiceError::Locked(format!("Too many failed logins; the account is locked{}", until))
+}
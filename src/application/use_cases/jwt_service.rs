@@ -0,0 +1,137 @@
+use crate::application::errors::ServiceError;
+use crate::application::secrets::{generate_secret, hash_secret};
+use crate::application::use_cases::TwoFactorService;
+use crate::domain::{AccessClaims, AccessTokenCodec, RefreshToken, RefreshTokenRepository, User, UserRepository};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+
+/// Prefix of every refresh token, so they are not mistaken for API tokens
+pub const REFRESH_TOKEN_PREFIX: &str = "blog_refresh_";
+
+const INVALID_REFRESH_TOKEN: &str = "The refresh token is invalid, expired or revoked";
+
+/// An access token and the refresh token that renews it; the refresh secret is never stored
+pub struct IssuedTokens {
+    pub access_token: String,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+/// Application service for stateless JWT access tokens and the refresh tokens that renew them
+pub struct JwtService {
+    users: Arc<dyn UserRepository>,
+    refresh_tokens: Arc<dyn RefreshTokenRepository>,
+    codec: Arc<dyn AccessTokenCodec>,
+    two_factor: Arc<TwoFactorService>,
+    access_ttl: Duration,
+    refresh_ttl: Duration,
+}
+
+impl JwtService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        refresh_tokens: Arc<dyn RefreshTokenRepository>,
+        codec: Arc<dyn AccessTokenCodec>,
+        two_factor: Arc<TwoFactorService>,
+        access_ttl: Duration,
+        refresh_ttl: Duration,
+    ) -> Self {
+        Self {
+            users,
+            refresh_tokens,
+            codec,
+            two_factor,
+            access_ttl,
+            refresh_ttl,
+        }
+    }
+
+    /// Issue tokens use case, for a user whose credentials were checked
+    /// Users whose role requires two-factor authentication must enroll, from a session, first
+    pub async fn issue(&self, user: &User) -> Result<IssuedTokens, ServiceError> {
+        let user_id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+        if self.two_factor.enrollment_missing(user).await? {
+            return Err(enrollment_required(user));
+        }
+        let now = Utc::now();
+
+        let claims = AccessClaims {
+            user_id,
+            role: user.role,
+            issued_at: now,
+            expires_at: now + self.access_ttl,
+        };
+        let access_token = self.codec.issue(&claims)?;
+
+        let refresh_token = generate_secret(REFRESH_TOKEN_PREFIX);
+        let stored = self
+            .refresh_tokens
+            .save(RefreshToken::new(user_id, hash_secret(&refresh_token), now + self.refresh_ttl))
+            .await?;
+
+        Ok(IssuedTokens {
+            access_token,
+            access_expires_at: claims.expires_at,
+            refresh_token,
+            refresh_expires_at: stored.expires_at,
+        })
+    }
+
+    /// Refresh use case: exchange a refresh token for new tokens; the old refresh token stops working
+    /// A refresh token used twice was leaked, so every refresh token of its user is revoked
+    pub async fn refresh(&self, secret: &str) -> Result<IssuedTokens, ServiceError> {
+        let now = Utc::now();
+        let Some(token) = self.refresh_tokens.find_by_hash(&hash_secret(secret)).await? else {
+            return Err(invalid_refresh_token());
+        };
+        let id = token.id.ok_or_else(|| ServiceError::Repository("Stored refresh token has no ID".to_string()))?;
+
+        if token.expires_at <= now {
+            return Err(invalid_refresh_token());
+        }
+        // Losing the race against a concurrent use counts as reuse too
+        if token.revoked_at.is_some() || !self.refresh_tokens.revoke(id, now).await? {
+            self.refresh_tokens.revoke_all(token.user_id, now).await?;
+            return Err(invalid_refresh_token());
+        }
+
+        // Reloaded, so a role change applies from this refresh on
+        let Some(user) = self.users.find_by_id(token.user_id).await? else {
+            return Err(invalid_refresh_token());
+        };
+        self.issue(&user).await
+    }
+
+    /// Revoke use case: end a refresh token, e.g. when an app signs out; unknown secrets are ignored
+    /// Access tokens already issued stay valid until they expire
+    pub async fn revoke(&self, secret: &str) -> Result<(), ServiceError> {
+        if let Some(token) = self.refresh_tokens.find_by_hash(&hash_secret(secret)).await?
+            && let Some(id) = token.id
+        {
+            self.refresh_tokens.revoke(id, Utc::now()).await?;
+        }
+        Ok(())
+    }
+
+    /// Revoke every refresh token of a user; returns how many were revoked
+    pub async fn revoke_all(&self, user_id: i32) -> Result<usize, ServiceError> {
+        Ok(self.refresh_tokens.revoke_all(user_id, Utc::now()).await?)
+    }
+
+    /// Authenticate use case: the claims of a valid access token, if any; no database access
+    pub fn authenticate(&self, token: &str) -> Option<AccessClaims> {
+        self.codec.verify(token)
+    }
+}
+
+fn enrollment_required(user: &User) -> ServiceError {
+    ServiceError::Forbidden(format!(
+        "Two-factor authentication is required for {}s; enroll from a signed-in session first",
+        user.role
+    ))
+}
+
+fn invalid_refresh_token() -> ServiceError {
+    ServiceError::Unauthenticated(INVALID_REFRESH_TOKEN.to_string())
+}
@@ -1,3 +1,15 @@
+pub mod auth_service;
+pub mod jwt_service;
+pub mod oidc_service;
 pub mod post_service;
+pub mod token_service;
+pub mod two_factor_service;
 
-pub use post_service::PostService;
+pub use auth_service::{AuthService, LoggedIn, LoginClient};
+pub use jwt_service::{IssuedTokens, JwtService, REFRESH_TOKEN_PREFIX};
+pub use oidc_service::{OidcLogin, OidcOutcome, OidcService, PendingLogin, PendingSecondFactor, RoleMapping};
+pub use post_service::{
+    BatchReport, BatchResult, ImportOutcome, PostPage, PostService, SlugLookup, MAX_BATCH_OPERATIONS,
+};
+pub use token_service::{MintedToken, TokenService, TOKEN_PREFIX};
+pub use two_factor_service::{TwoFactorEnrollment, TwoFactorService, TwoFactorStatus, RECOVERY_CODE_COUNT};
@@ -0,0 +1,613 @@
+use crate::application::errors::ServiceError;
+use crate::application::secrets::generate_secret;
+use crate::domain::validation::rules::DISPLAY_NAME_MAX_LENGTH;
+use crate::domain::{
+    normalize_email, AuthorizationRequest, IdentityClaims, IdentityProvider, Role, User, UserIdentity,
+    UserIdentityRepository, UserRepository,
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Time allowed for entering the second factor once the provider has signed the user in
+const SECOND_FACTOR_TTL: Duration = Duration::minutes(5);
+
+/// How provider claims become local roles
+#[derive(Debug, Clone, Default)]
+pub struct RoleMapping {
+    /// Values of the roles claim and the role each grants; when empty, roles are managed locally
+    pub rules: Vec<(String, Role)>,
+    /// Role of users no rule matches; `None` refuses them
+    pub default_role: Option<Role>,
+}
+
+impl RoleMapping {
+    /// The highest role granted by the claim values, falling back to the default
+    pub fn role_for(&self, groups: &[String]) -> Option<Role> {
+        self.rules
+            .iter()
+            .filter(|(group, _)| groups.contains(group))
+            .map(|(_, role)| *role)
+            .max()
+            .or(self.default_role)
+    }
+}
+
+/// A login waiting for the provider's callback; kept by the browser and never by the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingLogin {
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    /// Local path to return to once signed in
+    pub return_to: String,
+}
+
+/// A login the provider completed for an account with two-factor authentication, waiting for its code
+/// Kept by the browser like [`PendingLogin`]; the expiry travels with it, so an old copy cannot be replayed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSecondFactor {
+    pub user_id: i32,
+    pub return_to: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Outcome of a completed provider login
+pub enum OidcOutcome {
+    /// The user may get a session
+    SignedIn(User),
+    /// The account has two-factor authentication, which the provider's login does not replace
+    SecondFactorRequired(PendingSecondFactor),
+}
+
+/// A started login: where to send the browser, and what to remember until it comes back
+pub struct OidcLogin {
+    pub authorization_url: String,
+    pub pending: PendingLogin,
+}
+
+/// Application service for single sign-on with an OpenID Connect provider
+pub struct OidcService {
+    provider: Arc<dyn IdentityProvider>,
+    users: Arc<dyn UserRepository>,
+    identities: Arc<dyn UserIdentityRepository>,
+    role_mapping: RoleMapping,
+}
+
+impl OidcService {
+    pub fn new(
+        provider: Arc<dyn IdentityProvider>,
+        users: Arc<dyn UserRepository>,
+        identities: Arc<dyn UserIdentityRepository>,
+        role_mapping: RoleMapping,
+    ) -> Self {
+        Self {
+            provider,
+            users,
+            identities,
+            role_mapping,
+        }
+    }
+
+    /// Begin login use case: a provider URL with fresh state, nonce and PKCE challenge
+    pub async fn begin(&self, return_to: Option<&str>) -> Result<OidcLogin, ServiceError> {
+        let return_to = match return_to {
+            Some(path) if is_local_path(path) => path.to_string(),
+            Some(_) => return Err(ServiceError::InvalidInput("return_to must be a path on this site".to_string())),
+            None => "/".to_string(),
+        };
+        let pending = PendingLogin {
+            state: generate_secret(""),
+            nonce: generate_secret(""),
+            // 64 hex characters, within the 43 to 128 unreserved characters PKCE allows
+            code_verifier: generate_secret(""),
+            return_to,
+        };
+
+        let authorization_url = self
+            .provider
+            .authorization_url(&AuthorizationRequest {
+                state: pending.state.clone(),
+                nonce: pending.nonce.clone(),
+                code_challenge: URL_SAFE_NO_PAD.encode(Sha256::digest(pending.code_verifier.as_bytes())),
+            })
+            .await?;
+
+        Ok(OidcLogin { authorization_url, pending })
+    }
+
+    /// Complete login use case: exchange the callback's code and find, link or create the local user
+    /// Users with two-factor authentication still have to enter a code, as with a password login
+    pub async fn complete(&self, pending: &PendingLogin, state: &str, code: &str) -> Result<OidcOutcome, ServiceError> {
+        let user = self.identify(pending, state, code).await?;
+        if !user.has_two_factor() {
+            return Ok(OidcOutcome::SignedIn(user));
+        }
+
+        let user_id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+        Ok(OidcOutcome::SecondFactorRequired(PendingSecondFactor {
+            user_id,
+            return_to: pending.return_to.clone(),
+            expires_at: Utc::now() + SECOND_FACTOR_TTL,
+        }))
+    }
+
+    /// The local user the provider's login stands for
+    async fn identify(&self, pending: &PendingLogin, state: &str, code: &str) -> Result<User, ServiceError> {
+        if state != pending.state {
+            return Err(ServiceError::Unauthenticated("The login state does not match; start the login again".to_string()));
+        }
+
+        let claims = self
+            .provider
+            .exchange_code(code, &pending.code_verifier, &pending.nonce)
+            .await?;
+        let mapped_role = self.role_mapping.role_for(&claims.groups);
+
+        if let Some(identity) = self.identities.find(&claims.issuer, &claims.subject).await? {
+            let user = self
+                .users
+                .find_by_id(identity.user_id)
+                .await?
+                .ok_or_else(|| ServiceError::Repository("Linked user no longer exists".to_string()))?;
+            return self.sync_role(user, mapped_role).await;
+        }
+
+        let Some(email) = claims.email.as_deref().map(normalize_email) else {
+            return Err(ServiceError::Unauthenticated("The identity provider did not share an email address".to_string()));
+        };
+
+        // An existing account is only taken over when the provider vouches for the email
+        let user = match self.users.find_by_email(&email).await? {
+            Some(_) if !claims.email_verified => {
+                return Err(ServiceError::Unauthenticated(
+                    "The identity provider has not verified this email address".to_string(),
+                ));
+            }
+            Some(user) => self.sync_role(user, mapped_role).await?,
+            None => self.create_user(&claims, email, mapped_role).await?,
+        };
+
+        let user_id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+        self.identities
+            .save(UserIdentity::new(user_id, claims.issuer, claims.subject))
+            .await?;
+        Ok(user)
+    }
+
+    async fn create_user(&self, claims: &IdentityClaims, email: String, role: Option<Role>) -> Result<User, ServiceError> {
+        let Some(role) = role else {
+            return Err(not_granted());
+        };
+        let display_name = claims
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| email.split('@').next().unwrap_or_default());
+        let display_name: String = display_name.chars().take(DISPLAY_NAME_MAX_LENGTH).collect();
+
+        // No password: the account signs in through the provider only
+        let user = User::new(email, display_name, role, String::new());
+        user.validate()?;
+        Ok(self.users.save(user).await?)
+    }
+
+    /// With mapping rules, the provider decides the role at every login; without, the local role stands
+    async fn sync_role(&self, mut user: User, mapped_role: Option<Role>) -> Result<User, ServiceError> {
+        if self.role_mapping.rules.is_empty() {
+            return Ok(user);
+        }
+        let Some(role) = mapped_role else {
+            return Err(not_granted());
+        };
+
+        if user.role != role {
+            let id = user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))?;
+            self.users.update_role(id, role).await?;
+            user.role = role;
+        }
+        Ok(user)
+    }
+
+    /// Issuer URL of the configured provider
+    pub fn issuer(&self) -> &str {
+        self.provider.issuer()
+    }
+}
+
+/// A path on this site, not a URL elsewhere; `//host` and `/\host` are read as hosts by browsers
+fn is_local_path(path: &str) -> bool {
+    path.starts_with('/') && !path.starts_with("//") && !path.starts_with("/\\") && !path.chars().any(char::is_control)
+}
+
+fn not_granted() -> ServiceError {
+    ServiceError::Forbidden("Your single sign-on account has not been granted access to this blog".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::IdentityProviderError;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use std::sync::Mutex;
+
+    const ISSUER: &str = "https://id.example.com";
+
+    /// Provider returning preset claims, remembering what it was asked
+    #[derive(Default)]
+    struct FakeProvider {
+        claims: Mutex<Option<IdentityClaims>>,
+        /// Refusal returned by the code exchange instead of the claims
+        rejection: Mutex<Option<String>>,
+        requests: Mutex<Vec<AuthorizationRequest>>,
+        /// Code, verifier and nonce of every exchange
+        exchanges: Mutex<Vec<(String, String, String)>>,
+    }
+
+    #[async_trait]
+    impl IdentityProvider for FakeProvider {
+        fn issuer(&self) -> &str {
+            ISSUER
+        }
+
+        async fn authorization_url(&self, request: &AuthorizationRequest) -> Result<String, IdentityProviderError> {
+            self.requests.lock().unwrap().push(AuthorizationRequest {
+                state: request.state.clone(),
+                nonce: request.nonce.clone(),
+                code_challenge: request.code_challenge.clone(),
+            });
+            Ok(format!("{}/authorize?state={}", ISSUER, request.state))
+        }
+
+        async fn exchange_code(
+            &self,
+            code: &str,
+            code_verifier: &str,
+            nonce: &str,
+        ) -> Result<IdentityClaims, IdentityProviderError> {
+            self.exchanges
+                .lock()
+                .unwrap()
+                .push((code.to_string(), code_verifier.to_string(), nonce.to_string()));
+            if let Some(reason) = self.rejection.lock().unwrap().clone() {
+                return Err(IdentityProviderError::Rejected(reason));
+            }
+            Ok(self.claims.lock().unwrap().clone().expect("claims are set"))
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryUsers(Mutex<Vec<User>>);
+
+    #[async_trait]
+    impl UserRepository for MemoryUsers {
+        async fn find_all(&self) -> Result<Vec<User>, String> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        async fn find_by_id(&self, id: i32) -> Result<Option<User>, String> {
+            Ok(self.0.lock().unwrap().iter().find(|user| user.id == Some(id)).cloned())
+        }
+
+        async fn find_by_email(&self, email: &str) -> Result<Option<User>, String> {
+            Ok(self.0.lock().unwrap().iter().find(|user| user.email == email).cloned())
+        }
+
+        async fn save(&self, mut user: User) -> Result<User, String> {
+            let mut users = self.0.lock().unwrap();
+            user.id = Some(users.len() as i32 + 1);
+            users.push(user.clone());
+            Ok(user)
+        }
+
+        async fn update_role(&self, id: i32, role: Role) -> Result<(), String> {
+            let mut users = self.0.lock().unwrap();
+            let user = users.iter_mut().find(|user| user.id == Some(id)).ok_or("no such user")?;
+            user.role = role;
+            Ok(())
+        }
+
+        async fn update_login_state(&self, _: i32, _: i32, _: Option<DateTime<Utc>>) -> Result<(), String> {
+            unreachable!("single sign-on does not count failed logins")
+        }
+
+        async fn update_two_factor(&self, _: i32, _: Option<String>, _: Option<DateTime<Utc>>) -> Result<(), String> {
+            unreachable!("single sign-on does not enroll two-factor authentication")
+        }
+
+        async fn advance_totp_step(&self, _: i32, _: i64) -> Result<bool, String> {
+            unreachable!("single sign-on does not check TOTP codes")
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryIdentities(Mutex<Vec<UserIdentity>>);
+
+    #[async_trait]
+    impl UserIdentityRepository for MemoryIdentities {
+        async fn find(&self, issuer: &str, subject: &str) -> Result<Option<UserIdentity>, String> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|identity| identity.issuer == issuer && identity.subject == subject)
+                .cloned())
+        }
+
+        async fn save(&self, identity: UserIdentity) -> Result<UserIdentity, String> {
+            self.0.lock().unwrap().push(identity.clone());
+            Ok(identity)
+        }
+    }
+
+    struct Fixture {
+        service: OidcService,
+        provider: Arc<FakeProvider>,
+        users: Arc<MemoryUsers>,
+        identities: Arc<MemoryIdentities>,
+    }
+
+    fn fixture(role_mapping: RoleMapping) -> Fixture {
+        let provider = Arc::new(FakeProvider::default());
+        let users = Arc::new(MemoryUsers::default());
+        let identities = Arc::new(MemoryIdentities::default());
+        let service = OidcService::new(
+            Arc::clone(&provider) as Arc<dyn IdentityProvider>,
+            Arc::clone(&users) as Arc<dyn UserRepository>,
+            Arc::clone(&identities) as Arc<dyn UserIdentityRepository>,
+            role_mapping,
+        );
+        Fixture { service, provider, users, identities }
+    }
+
+    fn local_roles() -> RoleMapping {
+        RoleMapping { rules: Vec::new(), default_role: Some(Role::Contributor) }
+    }
+
+    fn mapped_roles(default_role: Option<Role>) -> RoleMapping {
+        RoleMapping {
+            rules: vec![
+                ("writers".to_string(), Role::Author),
+                ("editors".to_string(), Role::Editor),
+            ],
+            default_role,
+        }
+    }
+
+    fn claims(email: &str, email_verified: bool, groups: &[&str]) -> IdentityClaims {
+        IdentityClaims {
+            issuer: ISSUER.to_string(),
+            subject: format!("sub-{}", email),
+            email: Some(email.to_string()),
+            email_verified,
+            name: Some("Ada Lovelace".to_string()),
+            groups: groups.iter().map(|group| group.to_string()).collect(),
+        }
+    }
+
+    impl Fixture {
+        fn returns(&self, claims: IdentityClaims) {
+            *self.provider.claims.lock().unwrap() = Some(claims);
+        }
+
+        async fn existing_user(&self, email: &str, role: Role) -> User {
+            let user = User::new(email.to_string(), "Existing".to_string(), role, "$argon2id$hash".to_string());
+            self.users.save(user).await.unwrap()
+        }
+
+        async fn login(&self) -> Result<User, ServiceError> {
+            match self.sign_in().await? {
+                OidcOutcome::SignedIn(user) => Ok(user),
+                OidcOutcome::SecondFactorRequired(_) => panic!("no second factor was expected"),
+            }
+        }
+
+        async fn sign_in(&self) -> Result<OidcOutcome, ServiceError> {
+            let login = self.service.begin(Some("/posts")).await.unwrap();
+            self.service.complete(&login.pending, &login.pending.state, "code").await
+        }
+    }
+
+    #[tokio::test]
+    async fn begin_binds_state_nonce_and_pkce_challenge_to_the_request() {
+        let f = fixture(local_roles());
+        let login = f.service.begin(Some("/posts")).await.unwrap();
+
+        let requests = f.provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].state, login.pending.state);
+        assert_eq!(requests[0].nonce, login.pending.nonce);
+        assert_ne!(login.pending.state, login.pending.nonce);
+        assert_eq!(
+            requests[0].code_challenge,
+            URL_SAFE_NO_PAD.encode(Sha256::digest(login.pending.code_verifier.as_bytes()))
+        );
+        assert_eq!(login.pending.return_to, "/posts");
+    }
+
+    #[tokio::test]
+    async fn begin_refuses_to_return_to_another_site() {
+        let f = fixture(local_roles());
+        for target in ["https://evil.example", "//evil.example", "/\\evil.example", "posts"] {
+            assert!(matches!(f.service.begin(Some(target)).await, Err(ServiceError::InvalidInput(_))), "{}", target);
+        }
+    }
+
+    #[tokio::test]
+    async fn a_state_mismatch_is_refused_before_the_code_is_exchanged() {
+        let f = fixture(local_roles());
+        f.returns(claims("ada@example.com", true, &[]));
+        let login = f.service.begin(None).await.unwrap();
+
+        let result = f.service.complete(&login.pending, "forged-state", "code").await;
+
+        assert!(matches!(result, Err(ServiceError::Unauthenticated(_))));
+        assert!(f.provider.exchanges.lock().unwrap().is_empty());
+        assert!(f.users.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn the_code_is_exchanged_with_the_pending_verifier_and_nonce() {
+        let f = fixture(local_roles());
+        f.returns(claims("ada@example.com", true, &[]));
+        let login = f.service.begin(None).await.unwrap();
+
+        f.service.complete(&login.pending, &login.pending.state, "the-code").await.unwrap();
+
+        let exchanges = f.provider.exchanges.lock().unwrap();
+        assert_eq!(
+            exchanges.as_slice(),
+            &[("the-code".to_string(), login.pending.code_verifier.clone(), login.pending.nonce.clone())]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_rejected_id_token_fails_the_login_without_creating_a_user() {
+        let f = fixture(local_roles());
+        *f.provider.rejection.lock().unwrap() = Some("The ID token was issued for another login".to_string());
+
+        assert!(matches!(f.login().await, Err(ServiceError::Unauthenticated(_))));
+        assert!(f.users.0.lock().unwrap().is_empty());
+        assert!(f.identities.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn an_unverified_email_does_not_take_over_an_existing_account() {
+        let f = fixture(local_roles());
+        f.existing_user("ada@example.com", Role::Admin).await;
+        f.returns(claims("ada@example.com", false, &[]));
+
+        assert!(matches!(f.login().await, Err(ServiceError::Unauthenticated(_))));
+        assert!(f.identities.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_verified_email_links_the_existing_account() {
+        let f = fixture(local_roles());
+        let existing = f.existing_user("ada@example.com", Role::Author).await;
+        f.returns(claims("Ada@Example.com", true, &[]));
+
+        let user = f.login().await.unwrap();
+
+        assert_eq!(user.id, existing.id);
+        let identities = f.identities.0.lock().unwrap();
+        assert_eq!(identities.len(), 1);
+        assert_eq!(Some(identities[0].user_id), existing.id);
+        assert_eq!(identities[0].subject, "sub-Ada@Example.com");
+    }
+
+    #[tokio::test]
+    async fn a_linked_account_is_found_by_subject_even_when_the_email_changes() {
+        let f = fixture(local_roles());
+        f.returns(claims("ada@example.com", true, &[]));
+        let first = f.login().await.unwrap();
+
+        let mut renamed = claims("ada@example.com", false, &[]);
+        renamed.email = Some("lovelace@example.com".to_string());
+        f.returns(renamed);
+        let second = f.login().await.unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(f.users.0.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn an_unverified_email_may_still_create_a_new_account() {
+        let f = fixture(local_roles());
+        f.returns(claims("new@example.com", false, &[]));
+
+        let user = f.login().await.unwrap();
+
+        assert_eq!(user.email, "new@example.com");
+        assert_eq!(user.display_name, "Ada Lovelace");
+        assert_eq!(user.role, Role::Contributor);
+        assert!(user.password_hash.is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_login_without_an_email_is_refused() {
+        let f = fixture(local_roles());
+        let mut anonymous = claims("ada@example.com", true, &[]);
+        anonymous.email = None;
+        f.returns(anonymous);
+
+        assert!(matches!(f.login().await, Err(ServiceError::Unauthenticated(_))));
+    }
+
+    #[tokio::test]
+    async fn accounts_with_two_factor_authentication_still_need_their_code() {
+        let f = fixture(local_roles());
+        let mut existing = User::new("ada@example.com".to_string(), "Ada".to_string(), Role::Admin, String::new());
+        existing.totp_secret = Some("JBSWY3DPEHPK3PXP".to_string());
+        existing.totp_enabled_at = Some(Utc::now());
+        let existing = f.users.save(existing).await.unwrap();
+        f.returns(claims("ada@example.com", true, &[]));
+
+        let started = Utc::now();
+        let OidcOutcome::SecondFactorRequired(pending) = f.sign_in().await.unwrap() else {
+            panic!("the login skipped the second factor");
+        };
+        assert_eq!(Some(pending.user_id), existing.id);
+        assert_eq!(pending.return_to, "/posts");
+        assert!(pending.expires_at > started && pending.expires_at <= Utc::now() + SECOND_FACTOR_TTL);
+        // Linked all the same, so the next login finds the account by subject
+        assert_eq!(f.identities.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn the_highest_matching_rule_wins() {
+        let mapping = mapped_roles(Some(Role::Contributor));
+        assert_eq!(mapping.role_for(&["editors".to_string(), "writers".to_string()]), Some(Role::Editor));
+        assert_eq!(mapping.role_for(&["writers".to_string(), "staff".to_string()]), Some(Role::Author));
+        assert_eq!(mapping.role_for(&["staff".to_string()]), Some(Role::Contributor));
+        assert_eq!(mapped_roles(None).role_for(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn new_users_get_the_mapped_role() {
+        let f = fixture(mapped_roles(Some(Role::Contributor)));
+        f.returns(claims("ed@example.com", true, &["writers", "editors"]));
+
+        assert_eq!(f.login().await.unwrap().role, Role::Editor);
+    }
+
+    #[tokio::test]
+    async fn users_no_rule_matches_are_refused_without_a_default_role() {
+        let f = fixture(mapped_roles(None));
+        f.returns(claims("guest@example.com", true, &["staff"]));
+
+        assert!(matches!(f.login().await, Err(ServiceError::Forbidden(_))));
+        assert!(f.users.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn mapped_roles_are_applied_at_every_login() {
+        let f = fixture(mapped_roles(None));
+        f.returns(claims("ed@example.com", true, &["editors"]));
+        let user = f.login().await.unwrap();
+        assert_eq!(user.role, Role::Editor);
+
+        f.returns(claims("ed@example.com", true, &["writers"]));
+        assert_eq!(f.login().await.unwrap().role, Role::Author);
+        let stored = f.users.find_by_id(user.id.unwrap()).await.unwrap().unwrap();
+        assert_eq!(stored.role, Role::Author);
+
+        f.returns(claims("ed@example.com", true, &[]));
+        assert!(matches!(f.login().await, Err(ServiceError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn without_rules_the_local_role_stands() {
+        let f = fixture(local_roles());
+        f.existing_user("admin@example.com", Role::Admin).await;
+        f.returns(claims("admin@example.com", true, &["writers"]));
+
+        assert_eq!(f.login().await.unwrap().role, Role::Admin);
+    }
+}
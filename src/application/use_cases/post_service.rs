@@ -1,47 +1,171 @@
-use crate::domain::{Post, PostRepository};
+use crate::application::errors::ServiceError;
+use crate::application::policies::{post_policy, Actor};
+use crate::domain::{
+    BatchGuard, OperationError, OperationOutcome, Post, PostActivity, PostEvent, PostImport, PostLink, PostOperation, PostPatch,
+    PostRepository, UpsertOutcome, ValidationErrors, Violation, ViolationCode,
+};
+use chrono::Utc;
 use std::sync::Arc;
+use tracing::instrument;
+
+/// Outcome of looking a post up by slug
+pub enum SlugLookup {
+    /// The slug is the post's current slug
+    Current(Post),
+    /// The slug was retired; the post now lives under its current slug
+    Moved(Post),
+}
+
+/// One page of a post listing
+pub struct PostPage {
+    pub posts: Vec<Post>,
+    pub page: i64,
+    pub has_next: bool,
+}
+
+/// Outcome of importing a post identified by its slug
+pub enum ImportOutcome {
+    Created(Post),
+    Updated(Post),
+    /// The stored post already matched the imported content
+    Unchanged(Post),
+}
+
+/// Largest number of operations accepted in one batch
+pub const MAX_BATCH_OPERATIONS: usize = 500;
+
+/// What happened to one operation of a batch
+pub enum BatchResult {
+    Applied(OperationOutcome),
+    Failed(ServiceError),
+    /// Applied, then undone because a later operation of the all-or-nothing batch failed
+    RolledBack(OperationOutcome),
+    /// Not attempted because an earlier operation of the all-or-nothing batch failed
+    Skipped,
+}
+
+/// Results of a batch, one per operation in request order
+pub struct BatchReport {
+    /// Whether the applied operations were kept; always true when continuing on error
+    pub committed: bool,
+    pub results: Vec<BatchResult>,
+}
 
 /// Application service that orchestrates business operations
 /// This layer contains the use cases and application-specific business rules
 pub struct PostService {
     repository: Arc<dyn PostRepository>,
+    activity: Arc<dyn PostActivity>,
 }
 
 impl PostService {
-    pub fn new(repository: Arc<dyn PostRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<dyn PostRepository>, activity: Arc<dyn PostActivity>) -> Self {
+        Self { repository, activity }
     }
 
     /// Get all posts use case
-    pub async fn get_all_posts(&self) -> Result<Vec<Post>, String> {
-        self.repository.find_all().await
+    #[instrument(skip_all, err(Display, level = "info"))]
+    pub async fn get_all_posts(&self) -> Result<Vec<Post>, ServiceError> {
+        Ok(self.repository.find_all().await?)
+    }
+
+    /// Get the posts `viewer` may see use case; `None` is an anonymous reader
+    #[instrument(skip_all, err(Display, level = "info"))]
+    pub async fn get_visible_posts(&self, viewer: Option<&Actor>) -> Result<Vec<Post>, ServiceError> {
+        let now = Utc::now();
+        let mut posts = self.repository.find_all().await?;
+        posts.retain(|post| post_policy::can_view(viewer, post, now));
+        Ok(posts)
+    }
+
+    /// Get the next batch of posts after `after_id`, drafts included, in ID order use case
+    #[instrument(skip_all, fields(after_id, limit), err(Display, level = "info"))]
+    pub async fn get_posts_after(&self, after_id: i32, limit: i64) -> Result<Vec<Post>, ServiceError> {
+        if limit <= 0 {
+            return Err(ServiceError::InvalidInput("Invalid batch size".to_string()));
+        }
+        Ok(self.repository.find_after_id(after_id, limit).await?)
     }
 
-    /// Get post by ID use case
-    pub async fn get_post_by_id(&self, id: i32) -> Result<Option<Post>, String> {
+    /// Get post by ID use case; posts `viewer` may not see are not found
+    #[instrument(skip_all, fields(post_id = id), err(Display, level = "info"))]
+    pub async fn get_post_by_id(&self, viewer: Option<&Actor>, id: i32) -> Result<Option<Post>, ServiceError> {
         if id <= 0 {
-            return Err("Invalid post ID".to_string());
+            return Err(ServiceError::InvalidInput("Invalid post ID".to_string()));
         }
-        self.repository.find_by_id(id).await
+        let now = Utc::now();
+        Ok(self.repository
+            .find_by_id(id)
+            .await?
+            .filter(|post| post_policy::can_view(viewer, post, now)))
     }
 
-    /// Create new post use case
-    pub async fn create_post(&self, title: String, slug: String, body: String) -> Result<Post, String> {
-        let post = Post::new(title, slug, body);
-        
-        // Validate the post
-        post.validate()?;
-        
-        // Check if slug already exists (business rule)
-        // Note: In a real application, you might want to add a find_by_slug method
-        
-        self.repository.save(post).await
+    /// Get post by slug use case, following retired slugs to their post; posts `viewer` may not see are not found
+    #[instrument(skip_all, fields(slug), err(Display, level = "info"))]
+    pub async fn get_post_by_slug(&self, viewer: Option<&Actor>, slug: &str) -> Result<Option<SlugLookup>, ServiceError> {
+        let now = Utc::now();
+        let lookup = match self.repository.find_by_slug(slug).await? {
+            Some(post) => Some(SlugLookup::Current(post)),
+            None => self.repository.find_by_retired_slug(slug).await?.map(SlugLookup::Moved),
+        };
+        Ok(lookup.filter(|lookup| match lookup {
+            SlugLookup::Current(post) | SlugLookup::Moved(post) => post_policy::can_view(viewer, post, now),
+        }))
+    }
+
+    /// Get the latest published posts use case, optionally restricted to a tag
+    #[instrument(skip_all, fields(limit, tag), err(Display, level = "info"))]
+    pub async fn get_published_posts(&self, limit: usize, tag: Option<&str>) -> Result<Vec<Post>, ServiceError> {
+        let limit = i64::try_from(limit)
+            .map_err(|_| ServiceError::InvalidInput("Invalid post limit".to_string()))?;
+        Ok(self.repository.find_latest_published(0, limit, tag).await?)
+    }
+
+    /// Get one page (1-based) of published posts use case, optionally restricted to a tag
+    #[instrument(skip_all, fields(page, per_page, tag), err(Display, level = "info"))]
+    pub async fn get_published_page(&self, page: i64, per_page: i64, tag: Option<&str>) -> Result<PostPage, ServiceError> {
+        if page < 1 || per_page < 1 {
+            return Err(ServiceError::InvalidInput("Invalid page".to_string()));
+        }
+
+        // Fetch one extra post to learn whether another page follows
+        let mut posts = self.repository
+            .find_latest_published((page - 1) * per_page, per_page + 1, tag)
+            .await?;
+        let has_next = posts.len() as i64 > per_page;
+        posts.truncate(per_page as usize);
+
+        Ok(PostPage { posts, page, has_next })
+    }
+
+    /// Count published posts use case
+    #[instrument(skip_all, err(Display, level = "info"))]
+    pub async fn count_published_posts(&self) -> Result<i64, ServiceError> {
+        Ok(self.repository.count_published().await?)
+    }
+
+    /// Get one page of links to published posts use case
+    #[instrument(skip_all, fields(offset, limit), err(Display, level = "info"))]
+    pub async fn get_published_post_links(&self, offset: i64, limit: i64) -> Result<Vec<PostLink>, ServiceError> {
+        if offset < 0 || limit <= 0 {
+            return Err(ServiceError::InvalidInput("Invalid page".to_string()));
+        }
+        Ok(self.repository.find_published_links(offset, limit).await?)
+    }
+
+    /// Create new post use case; the actor becomes the post's author
+    #[instrument(skip_all, fields(actor = ?actor.user_id, slug = %post.slug), err(Display, level = "info"))]
+    pub async fn create_post(&self, actor: &Actor, mut post: Post) -> Result<Post, ServiceError> {
+        post_policy::can_create(actor, &post)?;
+        post.author_id = actor.user_id;
+        self.insert_post(post).await
     }
 
     /// Update post use case
-    pub async fn update_post(&self, id: i32, title: String, body: String) -> Result<Option<Post>, String> {
+    #[instrument(skip_all, fields(actor = ?actor.user_id, post_id = id), err(Display, level = "info"))]
+    pub async fn update_post(&self, actor: &Actor, id: i32, title: String, body: String) -> Result<Option<Post>, ServiceError> {
         if id <= 0 {
-            return Err("Invalid post ID".to_string());
+            return Err(ServiceError::InvalidInput("Invalid post ID".to_string()));
         }
 
         // First check if post exists
@@ -49,19 +173,362 @@ impl PostService {
         
         match existing_post {
             Some(mut post) => {
+                let changes = PostPatch {
+                    title: Some(title.clone()),
+                    body: Some(body.clone()),
+                    ..PostPatch::default()
+                };
+                post_policy::can_edit(actor, &post, &changes)?;
+
                 post.update(title, body);
                 post.validate()?;
-                self.repository.update(id, post).await
+                Ok(self.repository.update(id, post).await?)
             }
             None => Ok(None)
         }
     }
 
+    /// Partially update post use case
+    /// The merged post is validated as a whole before only the changed fields are written
+    #[instrument(skip_all, fields(actor = ?actor.user_id, post_id = id), err(Display, level = "info"))]
+    pub async fn patch_post(&self, actor: &Actor, id: i32, patch: PostPatch) -> Result<Option<Post>, ServiceError> {
+        if id <= 0 {
+            return Err(ServiceError::InvalidInput("Invalid post ID".to_string()));
+        }
+
+        let Some(mut post) = self.repository.find_by_id(id).await? else {
+            return Ok(None);
+        };
+
+        let original = post.clone();
+        let changes = post.apply_patch(patch);
+        post_policy::can_edit(actor, &original, &changes)?;
+
+        let updated = self.write_changes(id, post, changes).await?;
+        if let Some(updated) = &updated {
+            self.record_publication(Some(&original), updated);
+        }
+        Ok(updated)
+    }
+
+    /// Import post use case: create the post, or update the post that currently owns its slug
+    /// Importing the same content again leaves the stored post untouched
+    #[instrument(skip_all, fields(actor = ?actor.user_id, slug = %post.slug), err(Display, level = "info"))]
+    pub async fn import_post(&self, actor: &Actor, mut post: Post) -> Result<ImportOutcome, ServiceError> {
+        post_policy::can_import(actor)?;
+
+        let Some(mut existing) = self.repository.find_by_slug(&post.slug).await? else {
+            post.author_id = post.author_id.or(actor.user_id);
+            return Ok(ImportOutcome::Created(self.insert_post(post).await?));
+        };
+        let Some(id) = existing.id else {
+            return Err(ServiceError::Repository("Stored post has no ID".to_string()));
+        };
+
+        let original = existing.clone();
+        let changes = existing.apply_patch(import_patch(post));
+        if changes.is_empty() {
+            return Ok(ImportOutcome::Unchanged(existing));
+        }
+
+        match self.write_changes(id, existing, changes).await? {
+            Some(updated) => {
+                self.record_publication(Some(&original), &updated);
+                Ok(ImportOutcome::Updated(updated))
+            }
+            None => Err(ServiceError::Repository("Post was deleted during import".to_string())),
+        }
+    }
+
+    /// Dry run of [`PostService::import_post`]: report what importing would do without writing anything
+    #[instrument(skip_all, fields(actor = ?actor.user_id, slug = %post.slug), err(Display, level = "info"))]
+    pub async fn preview_import(&self, actor: &Actor, post: Post) -> Result<ImportOutcome, ServiceError> {
+        post_policy::can_import(actor)?;
+
+        let Some(mut existing) = self.repository.find_by_slug(&post.slug).await? else {
+            post.validate()?;
+            self.ensure_slug_available(&post.slug, None).await?;
+            return Ok(ImportOutcome::Created(post));
+        };
+
+        let changes = existing.apply_patch(import_patch(post));
+        if changes.is_empty() {
+            return Ok(ImportOutcome::Unchanged(existing));
+        }
+        existing.validate()?;
+        Ok(ImportOutcome::Updated(existing))
+    }
+
+    /// Bulk import use case: validate every post, then create or update the valid ones by slug in one transaction
+    /// Returns one result per post, in input order
+    /// New posts without an author are attributed to the actor
+    #[instrument(skip_all, fields(actor = ?actor.user_id, posts = posts.len()), err(Display, level = "info"))]
+    pub async fn import_posts(&self, actor: &Actor, posts: Vec<PostImport>) -> Result<Vec<Result<UpsertOutcome, ServiceError>>, ServiceError> {
+        post_policy::can_import(actor)?;
+
+        let mut results: Vec<Option<Result<UpsertOutcome, ServiceError>>> = Vec::with_capacity(posts.len());
+        let mut valid = Vec::new();
+
+        for mut import in posts {
+            import.post.author_id = import.post.author_id.or(actor.user_id);
+            match import.post.validate() {
+                Ok(()) => {
+                    results.push(None);
+                    valid.push(import);
+                }
+                Err(errors) => results.push(Some(Err(ServiceError::Validation(errors)))),
+            }
+        }
+
+        let dated: Vec<bool> = valid.iter().map(|import| import.post.published_at.is_some()).collect();
+        let written = self.repository.upsert_batch(valid).await?;
+        for (result, dated) in written.iter().zip(dated) {
+            if let Ok(UpsertOutcome::Inserted(_)) = result {
+                self.activity.record(PostEvent::Created);
+                if dated {
+                    self.activity.record(PostEvent::Published);
+                }
+            }
+        }
+
+        let mut written = written.into_iter();
+        Ok(results
+            .into_iter()
+            .map(|result| match result {
+                Some(invalid) => invalid,
+                None => written
+                    .next()
+                    .unwrap_or_else(|| Err("Missing batch result".to_string()))
+                    .map_err(ServiceError::Repository),
+            })
+            .collect())
+    }
+
+    /// Batch use case: apply create, update and delete operations in order in one transaction
+    /// By default the first failure undoes the whole batch; with `continue_on_error` failing operations are
+    /// skipped and the others are kept
+    /// Every operation is checked against the post policies; a denied operation fails like an invalid one
+    /// Updates and deletes are checked inside the transaction, against the post as it is written
+    #[instrument(skip_all, fields(actor = ?actor.user_id, operations = operations.len(), continue_on_error), err(Display, level = "info"))]
+    pub async fn apply_batch(
+        &self,
+        actor: &Actor,
+        mut operations: Vec<PostOperation>,
+        continue_on_error: bool,
+    ) -> Result<BatchReport, ServiceError> {
+        if operations.is_empty() || operations.len() > MAX_BATCH_OPERATIONS {
+            return Err(ServiceError::InvalidInput(format!(
+                "A batch must hold between 1 and {} operations",
+                MAX_BATCH_OPERATIONS
+            )));
+        }
+
+        // Checks without reads run before the transaction, so an invalid or denied all-or-nothing batch never opens one
+        let mut rejected: Vec<Option<ServiceError>> = operations
+            .iter_mut()
+            .map(|operation| precheck(actor, operation).err())
+            .collect();
+        if !continue_on_error && let Some(failed) = rejected.iter().position(Option::is_some) {
+            let results = rejected
+                .iter_mut()
+                .enumerate()
+                .map(|(index, error)| match error.take() {
+                    Some(error) if index == failed => BatchResult::Failed(error),
+                    _ => BatchResult::Skipped,
+                })
+                .collect();
+            return Ok(BatchReport { committed: false, results });
+        }
+
+        let valid = operations
+            .into_iter()
+            .zip(&rejected)
+            .filter(|(_, error)| error.is_none())
+            .map(|(operation, _)| operation)
+            .collect();
+        let policy_actor = *actor;
+        let guard: BatchGuard = Box::new(move |stored, operation| {
+            authorize(&policy_actor, stored, operation).map_err(|error| OperationError::Denied(error.to_string()))
+        });
+        let applied = self.repository.apply_batch(valid, !continue_on_error, guard).await?;
+
+        if !continue_on_error {
+            let committed = applied.iter().all(Result::is_ok);
+            let mut results: Vec<BatchResult> = applied
+                .into_iter()
+                .map(|result| match result {
+                    Ok(outcome) if committed => BatchResult::Applied(outcome),
+                    Ok(outcome) => BatchResult::RolledBack(outcome),
+                    Err(error) => BatchResult::Failed(operation_error(error)),
+                })
+                .collect();
+            results.resize_with(rejected.len(), || BatchResult::Skipped);
+            self.record_batch(&results);
+            return Ok(BatchReport { committed, results });
+        }
+
+        let mut applied = applied.into_iter();
+        let results: Vec<BatchResult> = rejected
+            .into_iter()
+            .map(|error| match error {
+                Some(error) => BatchResult::Failed(error),
+                None => match applied.next() {
+                    Some(Ok(outcome)) => BatchResult::Applied(outcome),
+                    Some(Err(error)) => BatchResult::Failed(operation_error(error)),
+                    None => BatchResult::Failed(ServiceError::Repository("Missing batch result".to_string())),
+                },
+            })
+            .collect();
+        self.record_batch(&results);
+        Ok(BatchReport { committed: true, results })
+    }
+
     /// Delete post use case
-    pub async fn delete_post(&self, id: i32) -> Result<bool, String> {
+    #[instrument(skip_all, fields(actor = ?actor.user_id, post_id = id), err(Display, level = "info"))]
+    pub async fn delete_post(&self, actor: &Actor, id: i32) -> Result<bool, ServiceError> {
         if id <= 0 {
-            return Err("Invalid post ID".to_string());
+            return Err(ServiceError::InvalidInput("Invalid post ID".to_string()));
         }
-        self.repository.delete(id).await
+
+        let Some(post) = self.repository.find_by_id(id).await? else {
+            return Ok(false);
+        };
+        post_policy::can_delete(actor, &post)?;
+
+        let deleted = self.repository.delete(id).await?;
+        if deleted {
+            self.activity.record(PostEvent::Deleted);
+        }
+        Ok(deleted)
+    }
+
+    /// Release a retired slug so it stops redirecting and can be claimed again
+    #[instrument(skip_all, fields(actor = ?actor.user_id, slug), err(Display, level = "info"))]
+    pub async fn release_slug_redirect(&self, actor: &Actor, slug: &str) -> Result<bool, ServiceError> {
+        post_policy::can_release_slug_redirect(actor)?;
+        Ok(self.repository.release_retired_slug(slug).await?)
+    }
+
+    /// Validate and store a new post
+    async fn insert_post(&self, post: Post) -> Result<Post, ServiceError> {
+        // Validate the post
+        post.validate()?;
+
+        // Slugs must be unique and must not hijack a redirect of another post
+        self.ensure_slug_available(&post.slug, None).await?;
+
+        let saved = self.repository.save(post).await.map_err(operation_error)?;
+        self.activity.record(PostEvent::Created);
+        self.record_publication(None, &saved);
+        Ok(saved)
+    }
+
+    /// Record a post getting a publication date it did not have before `previous`
+    fn record_publication(&self, previous: Option<&Post>, post: &Post) {
+        if previous.is_none_or(|previous| previous.published_at.is_none()) && post.published_at.is_some() {
+            self.activity.record(PostEvent::Published);
+        }
+    }
+
+    /// Record the kept operations of a batch
+    fn record_batch(&self, results: &[BatchResult]) {
+        for result in results {
+            match result {
+                BatchResult::Applied(OperationOutcome::Created(post)) => {
+                    self.activity.record(PostEvent::Created);
+                    self.record_publication(None, post);
+                }
+                BatchResult::Applied(OperationOutcome::Updated { published: true, .. }) => {
+                    self.activity.record(PostEvent::Published);
+                }
+                BatchResult::Applied(OperationOutcome::Deleted(_)) => self.activity.record(PostEvent::Deleted),
+                _ => {}
+            }
+        }
+    }
+
+    /// Validate a post merged with `changes` and write only the changed fields
+    async fn write_changes(&self, id: i32, post: Post, changes: PostPatch) -> Result<Option<Post>, ServiceError> {
+        post.validate()?;
+
+        if let Some(slug) = &changes.slug {
+            self.ensure_slug_available(slug, Some(id)).await?;
+        }
+
+        if changes.is_empty() {
+            return Ok(Some(post));
+        }
+        self.repository.patch(id, changes).await.map_err(operation_error)
+    }
+
+    /// Reject a slug used by another post, either currently or as a redirect
+    async fn ensure_slug_available(&self, slug: &str, owner_id: Option<i32>) -> Result<(), ServiceError> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(post) = self.repository.find_by_slug(slug).await? {
+            if post.id != owner_id {
+                errors.add(Violation::new("slug", ViolationCode::Taken, "is already used by another post"));
+            }
+        } else if let Some(post) = self.repository.find_by_retired_slug(slug).await?
+            && post.id != owner_id
+        {
+            errors.add(Violation::new(
+                "slug",
+                ViolationCode::Redirected,
+                format!("still redirects to '{}'; release the redirect first", post.slug),
+            ));
+        }
+
+        Ok(errors.into_result()?)
+    }
+}
+
+/// Validate and authorize what a batch operation can be checked for without reading the database, attributing created posts to the actor
+fn precheck(actor: &Actor, operation: &mut PostOperation) -> Result<(), ServiceError> {
+    match operation {
+        PostOperation::Create(post) => {
+            post.validate()?;
+            post_policy::can_create(actor, post)?;
+            post.author_id = actor.user_id;
+            Ok(())
+        }
+        PostOperation::Update(id, _) | PostOperation::Delete(id) if *id <= 0 => {
+            Err(ServiceError::InvalidInput("Invalid post ID".to_string()))
+        }
+        PostOperation::Update(..) | PostOperation::Delete(_) => Ok(()),
+    }
+}
+
+/// Check a batch update or delete against the post policies; `stored` is the post it targets
+fn authorize(actor: &Actor, stored: &Post, operation: &PostOperation) -> Result<(), ServiceError> {
+    match operation {
+        PostOperation::Update(_, patch) => {
+            let mut post = stored.clone();
+            let changes = post.apply_patch(patch.clone());
+            post_policy::can_edit(actor, stored, &changes)
+        }
+        PostOperation::Delete(_) => post_policy::can_delete(actor, stored),
+        PostOperation::Create(_) => Ok(()),
+    }
+}
+
+fn operation_error(error: OperationError) -> ServiceError {
+    match error {
+        OperationError::NotFound => ServiceError::NotFound("Post not found".to_string()),
+        OperationError::Invalid(errors) => ServiceError::Validation(errors),
+        OperationError::Conflict(message) => ServiceError::Conflict(message),
+        OperationError::Denied(message) => ServiceError::Forbidden(message),
+        OperationError::Failed(message) => ServiceError::Repository(message),
+    }
+}
+
+/// Patch replacing every imported field of the post that owns the slug
+fn import_patch(post: Post) -> PostPatch {
+    PostPatch {
+        title: Some(post.title),
+        slug: None,
+        body: Some(post.body),
+        tags: Some(post.tags),
+        published_at: Some(post.published_at),
     }
 }
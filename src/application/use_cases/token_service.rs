@@ -0,0 +1,84 @@
+use crate::application::errors::ServiceError;
+use crate::application::policies::{token_policy, Actor};
+use crate::application::secrets::{generate_secret, hash_secret};
+use crate::domain::{ApiToken, ApiTokenRepository};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+
+/// Prefix of every minted token, so leaked tokens are easy to recognise and search for
+pub const TOKEN_PREFIX: &str = "blog_";
+
+/// `last_used_at` is refreshed at most this often, so authenticated reads do not each cost a write
+const LAST_USED_RESOLUTION: Duration = Duration::minutes(1);
+
+/// A freshly minted token; `secret` is never stored and cannot be shown again
+pub struct MintedToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+/// Application service for minting, listing, revoking and checking API tokens
+pub struct TokenService {
+    repository: Arc<dyn ApiTokenRepository>,
+}
+
+impl TokenService {
+    pub fn new(repository: Arc<dyn ApiTokenRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Mint token use case
+    pub async fn mint_token(
+        &self,
+        actor: &Actor,
+        name: String,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<MintedToken, ServiceError> {
+        token_policy::can_manage_tokens(actor)?;
+        let secret = generate_secret(TOKEN_PREFIX);
+
+        let token = ApiToken::new(name.trim().to_string(), hash_secret(&secret), expires_at);
+        token.validate()?;
+
+        let token = self.repository.save(token).await?;
+        Ok(MintedToken { token, secret })
+    }
+
+    /// List tokens use case
+    pub async fn list_tokens(&self, actor: &Actor) -> Result<Vec<ApiToken>, ServiceError> {
+        token_policy::can_manage_tokens(actor)?;
+        Ok(self.repository.find_all().await?)
+    }
+
+    /// Revoke token use case; revoking twice keeps the first revocation date
+    pub async fn revoke_token(&self, actor: &Actor, id: i32) -> Result<Option<ApiToken>, ServiceError> {
+        token_policy::can_manage_tokens(actor)?;
+        if id <= 0 {
+            return Err(ServiceError::InvalidInput("Invalid token ID".to_string()));
+        }
+        Ok(self.repository.revoke(id, Utc::now()).await?)
+    }
+
+    /// Authenticate use case: the active token matching `secret`, if any
+    pub async fn authenticate(&self, secret: &str) -> Result<Option<ApiToken>, ServiceError> {
+        if !secret.starts_with(TOKEN_PREFIX) {
+            return Ok(None);
+        }
+
+        let now = Utc::now();
+        let Some(mut token) = self.repository.find_by_hash(&hash_secret(secret)).await? else {
+            return Ok(None);
+        };
+        if !token.is_active_at(now) {
+            return Ok(None);
+        }
+
+        if let Some(id) = token.id
+            && token.last_used_at.is_none_or(|last_used_at| now - last_used_at >= LAST_USED_RESOLUTION)
+        {
+            self.repository.touch(id, now).await?;
+            token.last_used_at = Some(now);
+        }
+        Ok(Some(token))
+    }
+}
@@ -0,0 +1,212 @@
+use crate::application::errors::ServiceError;
+use crate::application::policies::{two_factor_policy, Actor};
+use crate::application::secrets::{generate_recovery_code, hash_secret};
+use crate::application::totp::{generate_totp_secret, otpauth_uri, verify_totp};
+use crate::application::use_cases::auth_service::locked;
+use crate::domain::{RecoveryCode, RecoveryCodeRepository, Role, TwoFactorPolicyRepository, User, UserRepository};
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Recovery codes issued at a time; each works once
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+const INVALID_CODE: &str = "Invalid two-factor code";
+
+/// A started enrollment; the secret is shown once, for apps that cannot scan the QR code
+pub struct TwoFactorEnrollment {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// Where a user stands with two-factor authentication
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    /// Whether the user's role must use it
+    pub required: bool,
+    pub recovery_codes_remaining: usize,
+}
+
+/// Application service for TOTP two-factor authentication and its recovery codes
+pub struct TwoFactorService {
+    users: Arc<dyn UserRepository>,
+    recovery_codes: Arc<dyn RecoveryCodeRepository>,
+    policy: Arc<dyn TwoFactorPolicyRepository>,
+    /// Names the account in authenticator apps
+    issuer: String,
+}
+
+impl TwoFactorService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        recovery_codes: Arc<dyn RecoveryCodeRepository>,
+        policy: Arc<dyn TwoFactorPolicyRepository>,
+        issuer: String,
+    ) -> Self {
+        Self {
+            users,
+            recovery_codes,
+            policy,
+            issuer,
+        }
+    }
+
+    /// Status use case
+    pub async fn status(&self, user: &User) -> Result<TwoFactorStatus, ServiceError> {
+        let id = user_id(user)?;
+        Ok(TwoFactorStatus {
+            enabled: user.has_two_factor(),
+            required: self.is_required(user).await?,
+            recovery_codes_remaining: self.recovery_codes.count_unused(id).await?,
+        })
+    }
+
+    /// Begin enrollment use case: a new secret, which counts once a code from it is confirmed
+    pub async fn begin_enrollment(&self, user: &User) -> Result<TwoFactorEnrollment, ServiceError> {
+        if user.has_two_factor() {
+            return Err(ServiceError::InvalidInput(
+                "Two-factor authentication is already enabled; disable it to enroll again".to_string(),
+            ));
+        }
+
+        let secret = generate_totp_secret();
+        self.users.update_two_factor(user_id(user)?, Some(secret.clone()), None).await?;
+        Ok(TwoFactorEnrollment {
+            otpauth_uri: otpauth_uri(&self.issuer, &user.email, &secret),
+            secret,
+        })
+    }
+
+    /// Confirm enrollment use case: a code from the new secret enables it; returns the first recovery codes
+    pub async fn confirm_enrollment(&self, user: &User, code: &str) -> Result<Vec<String>, ServiceError> {
+        let id = user_id(user)?;
+        let secret = match &user.totp_secret {
+            Some(secret) if !user.has_two_factor() => secret,
+            Some(_) => return Err(ServiceError::InvalidInput("Two-factor authentication is already enabled".to_string())),
+            None => return Err(ServiceError::InvalidInput("Start the enrollment first".to_string())),
+        };
+        let Some(step) = verify_totp(secret, code.trim(), Utc::now().timestamp()) else {
+            return Err(ServiceError::InvalidInput(
+                "The code does not match; check the authenticator app's clock".to_string(),
+            ));
+        };
+
+        self.users.update_two_factor(id, Some(secret.clone()), Some(Utc::now())).await?;
+        self.users.advance_totp_step(id, step).await?;
+        self.issue_recovery_codes(id).await
+    }
+
+    /// Regenerate recovery codes use case; the old codes stop working
+    pub async fn regenerate_recovery_codes(&self, user: &User, code: &str) -> Result<Vec<String>, ServiceError> {
+        let mut user = user.clone();
+        if !user.has_two_factor() {
+            return Err(ServiceError::InvalidInput("Two-factor authentication is not enabled".to_string()));
+        }
+        self.check_code(&mut user, code).await?;
+        self.issue_recovery_codes(user_id(&user)?).await
+    }
+
+    /// Disable use case; refused while the user's role requires two-factor authentication
+    pub async fn disable(&self, user: &User, code: &str) -> Result<(), ServiceError> {
+        let mut user = user.clone();
+        if !user.has_two_factor() {
+            return Err(ServiceError::InvalidInput("Two-factor authentication is not enabled".to_string()));
+        }
+        if self.is_required(&user).await? {
+            return Err(ServiceError::Forbidden(format!("Two-factor authentication is required for {}s", user.role)));
+        }
+        self.check_code(&mut user, code).await?;
+        self.reset(user_id(&user)?).await
+    }
+
+    /// Reset use case, for a lost authenticator: no code is asked for, so only the command line offers it
+    pub async fn reset(&self, user_id: i32) -> Result<(), ServiceError> {
+        self.users.update_two_factor(user_id, None, None).await?;
+        Ok(self.recovery_codes.replace_all(user_id, Vec::new()).await?)
+    }
+
+    /// Check a TOTP or recovery code as a second factor, counting failures towards the login lockout
+    pub async fn check_code(&self, user: &mut User, code: &str) -> Result<(), ServiceError> {
+        let now = Utc::now();
+        let id = user_id(user)?;
+        if user.is_locked_at(now) {
+            return Err(locked(user));
+        }
+        if self.verify(user, code).await? {
+            return Ok(());
+        }
+
+        user.record_failed_login(now);
+        self.users.update_login_state(id, user.failed_login_attempts, user.locked_until).await?;
+        if user.is_locked_at(now) {
+            return Err(locked(user));
+        }
+        Err(ServiceError::Unauthenticated(INVALID_CODE.to_string()))
+    }
+
+    /// Six digits are a TOTP code, accepted once; anything else is tried as a recovery code
+    async fn verify(&self, user: &User, code: &str) -> Result<bool, ServiceError> {
+        let id = user_id(user)?;
+        let Some(secret) = user.totp_secret.as_deref().filter(|_| user.has_two_factor()) else {
+            return Ok(false);
+        };
+
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+            return match verify_totp(secret, &code, Utc::now().timestamp()) {
+                Some(step) => Ok(self.users.advance_totp_step(id, step).await?),
+                None => Ok(false),
+            };
+        }
+
+        let code = normalize_recovery_code(&code);
+        Ok(self.recovery_codes.redeem(id, &hash_secret(&code), Utc::now()).await?)
+    }
+
+    /// Roles whose users must use two-factor authentication
+    pub async fn required_roles(&self) -> Result<Vec<Role>, ServiceError> {
+        let mut roles = self.policy.required_roles().await?;
+        roles.sort();
+        Ok(roles)
+    }
+
+    /// Require two-factor authentication for exactly these roles
+    pub async fn set_required_roles(&self, actor: &Actor, mut roles: Vec<Role>) -> Result<Vec<Role>, ServiceError> {
+        two_factor_policy::can_require_two_factor(actor)?;
+        roles.sort();
+        roles.dedup();
+        self.policy.set_required_roles(roles.clone()).await?;
+        Ok(roles)
+    }
+
+    /// Whether the user's role requires two-factor authentication but they have not enrolled yet
+    /// Accounts without a password sign in through the identity provider, whose own second factor applies
+    pub async fn enrollment_missing(&self, user: &User) -> Result<bool, ServiceError> {
+        Ok(!user.has_two_factor() && user.has_password() && self.is_required(user).await?)
+    }
+
+    async fn is_required(&self, user: &User) -> Result<bool, ServiceError> {
+        Ok(self.policy.required_roles().await?.contains(&user.role))
+    }
+
+    async fn issue_recovery_codes(&self, user_id: i32) -> Result<Vec<String>, ServiceError> {
+        let codes: Vec<String> = (0..RECOVERY_CODE_COUNT).map(|_| generate_recovery_code()).collect();
+        let stored = codes
+            .iter()
+            .map(|code| RecoveryCode::new(user_id, hash_secret(&normalize_recovery_code(code))))
+            .collect();
+        self.recovery_codes.replace_all(user_id, stored).await?;
+        Ok(codes)
+    }
+}
+
+/// Recovery codes are compared without their dashes and case, as people retype them loosely
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn user_id(user: &User) -> Result<i32, ServiceError> {
+    user.id.ok_or_else(|| ServiceError::Repository("Stored user has no ID".to_string()))
+}
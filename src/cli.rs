@@ -0,0 +1,314 @@
+use blog_rust::application::{Actor, AuthService, JwtService, TokenService, TwoFactorService};
+use blog_rust::domain::Role;
+use blog_rust::infrastructure::interchange::{parse_date, ImportStatus, WxrItemStatus};
+use blog_rust::infrastructure::{MarkdownExporter, MarkdownImporter, StaticSiteExporter, WxrImporter};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Blog Rust server and maintenance commands
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the HTTP server (default)
+    Serve,
+    /// Render the public site into a directory for static hosting
+    ExportSite {
+        /// Output directory; only files whose content changed are rewritten
+        #[arg(default_value = "dist")]
+        out_dir: PathBuf,
+    },
+    /// Create or update posts from Markdown files with YAML or TOML front matter
+    ImportMarkdown {
+        /// Markdown files, directories searched recursively, or zip archives
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Create or update posts from a WordPress eXtended RSS (WXR) export
+    ImportWxr {
+        path: PathBuf,
+        /// Report what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write every post as Markdown with front matter, plus referenced media, to a zip archive
+    ExportMarkdown {
+        #[arg(default_value = "blog-export.zip")]
+        output: PathBuf,
+    },
+    /// Mint, list or revoke API tokens for the write endpoints
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+    /// Create, list or unlock user accounts, change their roles and revoke their sessions
+    User {
+        #[command(subcommand)]
+        command: UserCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TokenCommand {
+    /// Mint a token; it is printed once and cannot be recovered
+    Create {
+        /// What the token is for, e.g. `editorial-bot`
+        name: String,
+        /// Expiry as `YYYY-MM-DD` or RFC 3339; the token never expires when omitted
+        #[arg(long, value_parser = parse_date)]
+        expires_at: Option<DateTime<Utc>>,
+    },
+    /// List every token, revoked and expired ones included
+    List,
+    /// Revoke a token by ID
+    Revoke { id: i32 },
+}
+
+#[derive(Subcommand)]
+pub enum UserCommand {
+    /// Create a user; the password is read from the first line of standard input
+    Create {
+        email: String,
+        /// Name shown for the user
+        #[arg(long)]
+        name: String,
+        /// `contributor`, `author`, `editor` or `admin`
+        #[arg(long, default_value_t = Role::Contributor)]
+        role: Role,
+    },
+    /// List every user
+    List,
+    /// Change what a user may do and revoke their refresh tokens; sessions see the new role on their
+    /// next request, access tokens once they expire
+    Role { email: String, role: Role },
+    /// Sign a user out everywhere by revoking all of their sessions and refresh tokens
+    RevokeSessions { email: String },
+    /// Clear the lockout left by repeated failed logins
+    Unlock { email: String },
+    /// Turn off two-factor authentication for a user who lost their authenticator and recovery codes
+    ResetTwoFactor { email: String },
+    /// Require two-factor authentication for exactly these roles; none lifts the requirement
+    RequireTwoFactor { roles: Vec<Role> },
+}
+
+pub async fn export_site(exporter: StaticSiteExporter, out_dir: &Path) -> io::Result<()> {
+    println!("📦 Exporting static site to {}", out_dir.display());
+
+    let summary = exporter.export(out_dir).await.map_err(io::Error::other)?;
+    println!(
+        "✅ {} written, {} unchanged, {} removed",
+        summary.written, summary.unchanged, summary.removed
+    );
+    Ok(())
+}
+
+pub async fn import_markdown(importer: MarkdownImporter, paths: &[PathBuf]) -> io::Result<()> {
+    let mut failed = 0;
+    for path in paths {
+        let report = importer.import_path(&Actor::system(), path).await.map_err(io::Error::other)?;
+        for file in &report.files {
+            let slug = file.slug.as_deref().unwrap_or("-");
+            match file.status {
+                ImportStatus::Created => println!("✅ created   {} ({})", file.file, slug),
+                ImportStatus::Updated => println!("✅ updated   {} ({})", file.file, slug),
+                ImportStatus::Unchanged => println!("➖ unchanged {} ({})", file.file, slug),
+                ImportStatus::Failed => {
+                    let error = file.error.as_deref().unwrap_or("import failed");
+                    match &file.violations {
+                        Some(violations) => println!("❌ failed    {}: {}: {}", file.file, error, violations),
+                        None => println!("❌ failed    {}: {}", file.file, error),
+                    }
+                }
+            }
+        }
+        println!(
+            "📄 {}: {} created, {} updated, {} unchanged, {} failed",
+            path.display(),
+            report.created,
+            report.updated,
+            report.unchanged,
+            report.failed
+        );
+        failed += report.failed;
+    }
+
+    if failed > 0 {
+        return Err(io::Error::other(format!("{} file(s) failed to import", failed)));
+    }
+    Ok(())
+}
+
+pub async fn import_wxr(importer: WxrImporter, path: &Path, dry_run: bool) -> io::Result<()> {
+    if dry_run {
+        println!("🔍 Dry run: nothing will be written");
+    }
+
+    let report = importer.import(&Actor::system(), path, dry_run).await.map_err(io::Error::other)?;
+    for item in &report.items {
+        let slug = item.slug.as_deref().unwrap_or("-");
+        let label = match item.status {
+            WxrItemStatus::Created => "✅ created  ",
+            WxrItemStatus::Updated => "✅ updated  ",
+            WxrItemStatus::Unchanged => "➖ unchanged",
+            WxrItemStatus::Skipped => "⏭️ skipped  ",
+            WxrItemStatus::Failed => "❌ failed   ",
+        };
+        print!("{} #{} {:?} ({})", label, item.item, item.title, slug);
+        if let Some(reason) = &item.reason {
+            print!(": {}", reason);
+        }
+        if let Some(violations) = &item.violations {
+            print!(": {}", violations);
+        }
+        println!();
+    }
+    println!(
+        "📄 {} created, {} updated, {} unchanged, {} skipped, {} failed; {} approved comments not imported",
+        report.created,
+        report.updated,
+        report.unchanged,
+        report.skipped,
+        report.failed,
+        report.comments_skipped
+    );
+
+    if report.failed > 0 {
+        return Err(io::Error::other(format!("{} item(s) failed to import", report.failed)));
+    }
+    Ok(())
+}
+
+pub async fn export_markdown(exporter: MarkdownExporter, output: &Path) -> io::Result<()> {
+    let archive = exporter.export_zip().await.map_err(io::Error::other)?;
+    std::fs::write(output, &archive)?;
+    println!("✅ Exported {} bytes to {}", archive.len(), output.display());
+    Ok(())
+}
+
+pub async fn token(service: &TokenService, command: TokenCommand) -> io::Result<()> {
+    let actor = Actor::system();
+    match command {
+        TokenCommand::Create { name, expires_at } => {
+            let minted = service.mint_token(&actor, name, expires_at).await.map_err(io::Error::other)?;
+            println!("🔑 Minted token #{} ({}); store it now, it cannot be shown again:", minted.token.id.unwrap_or(0), minted.token.name);
+            println!("{}", minted.secret);
+        }
+        TokenCommand::List => {
+            let now = Utc::now();
+            for token in service.list_tokens(&actor).await.map_err(io::Error::other)? {
+                let status = match (token.revoked_at, token.expires_at) {
+                    (Some(at), _) => format!("revoked {}", at.format("%Y-%m-%d")),
+                    (None, Some(at)) if at <= now => format!("expired {}", at.format("%Y-%m-%d")),
+                    (None, Some(at)) => format!("expires {}", at.format("%Y-%m-%d")),
+                    (None, None) => "never expires".to_string(),
+                };
+                let last_used = token
+                    .last_used_at
+                    .map_or("never".to_string(), |at| at.format("%Y-%m-%d %H:%M").to_string());
+                println!("#{:<4} {:<30} {:<20} last used {}", token.id.unwrap_or(0), token.name, status, last_used);
+            }
+        }
+        TokenCommand::Revoke { id } => match service.revoke_token(&actor, id).await.map_err(io::Error::other)? {
+            Some(token) => println!("✅ Revoked token #{} ({})", id, token.name),
+            None => return Err(io::Error::other(format!("token {} not found", id))),
+        },
+    }
+    Ok(())
+}
+
+pub async fn user(
+    service: &AuthService,
+    jwt_service: &JwtService,
+    two_factor_service: &TwoFactorService,
+    command: UserCommand,
+) -> io::Result<()> {
+    match command {
+        UserCommand::Create { email, name, role } => {
+            eprintln!("Password (read from standard input):");
+            let mut password = String::new();
+            io::stdin().read_line(&mut password)?;
+            let password = password.trim_end_matches(['\r', '\n']);
+
+            let user = service.create_user(email, name, role, password).await.map_err(io::Error::other)?;
+            println!("✅ Created {} #{} ({})", user.role, user.id.unwrap_or(0), user.email);
+        }
+        UserCommand::List => {
+            let now = Utc::now();
+            for user in service.list_users().await.map_err(io::Error::other)? {
+                let status = match user.locked_until {
+                    Some(at) if at > now => format!("locked until {}", at.format("%Y-%m-%d %H:%M")),
+                    _ => "active".to_string(),
+                };
+                let two_factor = if user.has_two_factor() { "2FA" } else { "" };
+                println!(
+                    "#{:<4} {:<40} {:<30} {:<12} {:<4} {}",
+                    user.id.unwrap_or(0),
+                    user.email,
+                    user.display_name,
+                    user.role.to_string(),
+                    two_factor,
+                    status
+                );
+            }
+        }
+        UserCommand::Role { email, role } => {
+            let user = find_user(service, &email).await?;
+            let user_id = user.id.unwrap_or(0);
+            service.set_role(user_id, role).await.map_err(io::Error::other)?;
+            // JWT clients then have to sign in again instead of renewing tokens across the change
+            let refresh_tokens = jwt_service.revoke_all(user_id).await.map_err(io::Error::other)?;
+            println!(
+                "✅ {} is now {}; revoked {} refresh token(s), access tokens keep the old role until they expire",
+                user.email, role, refresh_tokens
+            );
+        }
+        UserCommand::RevokeSessions { email } => {
+            let user = find_user(service, &email).await?;
+            let user_id = user.id.unwrap_or(0);
+            let sessions = service.revoke_all_sessions(user_id).await.map_err(io::Error::other)?;
+            let refresh_tokens = jwt_service.revoke_all(user_id).await.map_err(io::Error::other)?;
+            println!(
+                "✅ Revoked {} session(s) and {} refresh token(s) of {}",
+                sessions, refresh_tokens, user.email
+            );
+        }
+        UserCommand::Unlock { email } => {
+            let user = find_user(service, &email).await?;
+            service.unlock_user(user.id.unwrap_or(0)).await.map_err(io::Error::other)?;
+            println!("✅ Unlocked {}", user.email);
+        }
+        UserCommand::ResetTwoFactor { email } => {
+            let user = find_user(service, &email).await?;
+            two_factor_service.reset(user.id.unwrap_or(0)).await.map_err(io::Error::other)?;
+            println!("✅ Turned off two-factor authentication for {}", user.email);
+        }
+        UserCommand::RequireTwoFactor { roles } => {
+            let roles = two_factor_service
+                .set_required_roles(&Actor::system(), roles)
+                .await
+                .map_err(io::Error::other)?;
+            if roles.is_empty() {
+                println!("✅ No role requires two-factor authentication");
+            } else {
+                let roles: Vec<String> = roles.iter().map(Role::to_string).collect();
+                println!("✅ Two-factor authentication is required for: {}", roles.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn find_user(service: &AuthService, email: &str) -> io::Result<blog_rust::domain::User> {
+    service
+        .get_user_by_email(email)
+        .await
+        .map_err(io::Error::other)?
+        .ok_or_else(|| io::Error::other(format!("user {} not found", email)))
+}
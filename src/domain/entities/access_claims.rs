@@ -0,0 +1,12 @@
+use crate::domain::entities::Role;
+use chrono::{DateTime, Utc};
+
+/// What a signed access token asserts; it is trusted until it expires, without a database lookup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessClaims {
+    pub user_id: i32,
+    /// The user's role when the token was issued; role changes apply from the next refresh
+    pub role: Role,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,53 @@
+use crate::domain::validation::{rules, ValidationErrors, Violation, ViolationCode};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Credential for the write API
+/// Only a hash of the secret is kept, so a leaked database does not leak usable tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: Option<i32>,
+    /// What the token is for, e.g. `editorial-bot`
+    pub name: String,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    /// `None` for tokens that never expire
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    /// Create a new token without an ID (for creation)
+    pub fn new(name: String, token_hash: String, expires_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            id: None,
+            name,
+            token_hash,
+            created_at: Utc::now(),
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    /// Whether the token authenticates requests at the given instant
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    /// Validate the token data, collecting every violation instead of stopping at the first
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if rules::required(&mut errors, "name", &self.name) {
+            rules::max_length(&mut errors, "name", &self.name, rules::TOKEN_NAME_MAX_LENGTH);
+        }
+
+        if self.expires_at.is_some_and(|expires_at| expires_at <= self.created_at) {
+            errors.add(Violation::new("expires_at", ViolationCode::InvalidFormat, "must be in the future"));
+        }
+
+        errors.into_result()
+    }
+}
@@ -1,3 +1,21 @@
+pub mod access_claims;
+pub mod api_token;
 pub mod post;
+pub mod principal;
+pub mod recovery_code;
+pub mod refresh_token;
+pub mod role;
+pub mod session;
+pub mod user;
+pub mod user_identity;
 
-pub use post::Post;
+pub use access_claims::AccessClaims;
+pub use api_token::ApiToken;
+pub use post::{Post, PostLink, PostPatch};
+pub use principal::Principal;
+pub use recovery_code::RecoveryCode;
+pub use refresh_token::RefreshToken;
+pub use role::Role;
+pub use session::Session;
+pub use user::{normalize_email, User, LOCKOUT_DURATION, MAX_FAILED_LOGINS};
+pub use user_identity::UserIdentity;
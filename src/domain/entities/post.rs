@@ -1,3 +1,5 @@
+use crate::domain::validation::{rules, ValidationErrors};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Domain entity representing a blog post
@@ -8,16 +10,29 @@ pub struct Post {
     pub title: String,
     pub slug: String,
     pub body: String,
+    pub tags: Vec<String>,
+    /// `None` while the post is a draft; a future date schedules the post
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// User who created the post; `None` for posts created by API tokens, the CLI or before accounts existed
+    pub author_id: Option<i32>,
 }
 
 impl Post {
-    /// Create a new post without an ID (for creation)
+    /// Create a new draft post without an ID (for creation)
     pub fn new(title: String, slug: String, body: String) -> Self {
+        let now = Utc::now();
         Self {
             id: None,
             title,
             slug,
             body,
+            tags: Vec::new(),
+            published_at: None,
+            created_at: now,
+            updated_at: now,
+            author_id: None,
         }
     }
 
@@ -25,27 +40,54 @@ impl Post {
     pub fn with_id(id: i32, title: String, slug: String, body: String) -> Self {
         Self {
             id: Some(id),
-            title,
-            slug,
-            body,
+            ..Self::new(title, slug, body)
+        }
+    }
+
+    /// Replace the tags, keeping them sorted and free of duplicates
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = normalize_tags(tags);
+    }
+
+    /// Whether the post is visible to readers at the given instant
+    pub fn is_published_at(&self, now: DateTime<Utc>) -> bool {
+        self.published_at.is_some_and(|published_at| published_at <= now)
+    }
+
+    /// Publish the post immediately unless it already has a publication date
+    pub fn publish(&mut self) {
+        if self.published_at.is_none() {
+            self.published_at = Some(Utc::now());
         }
     }
 
-    /// Validate the post data
-    pub fn validate(&self) -> Result<(), String> {
-        if self.title.trim().is_empty() {
-            return Err("Title cannot be empty".to_string());
+    /// Validate the post data, collecting every violation instead of stopping at the first
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if rules::required(&mut errors, "title", &self.title) {
+            rules::max_length(&mut errors, "title", &self.title, rules::TITLE_MAX_LENGTH);
         }
-        
-        if self.slug.trim().is_empty() {
-            return Err("Slug cannot be empty".to_string());
+
+        if rules::required(&mut errors, "slug", &self.slug) {
+            rules::max_length(&mut errors, "slug", &self.slug, rules::SLUG_MAX_LENGTH);
+            rules::slug_format(&mut errors, "slug", &self.slug);
+            rules::not_reserved(&mut errors, "slug", &self.slug);
         }
-        
-        if self.body.trim().is_empty() {
-            return Err("Body cannot be empty".to_string());
+
+        if rules::required(&mut errors, "body", &self.body) {
+            rules::max_length(&mut errors, "body", &self.body, rules::BODY_MAX_LENGTH);
         }
-        
-        Ok(())
+
+        for (index, tag) in self.tags.iter().enumerate() {
+            let field = format!("tags[{}]", index);
+            if rules::required(&mut errors, &field, tag) {
+                rules::max_length(&mut errors, &field, tag, rules::TAG_MAX_LENGTH);
+                rules::slug_format(&mut errors, &field, tag);
+            }
+        }
+
+        errors.into_result()
     }
 
     /// Update the post with new data
@@ -53,4 +95,67 @@ impl Post {
         self.title = title;
         self.body = body;
     }
+
+    /// Merge a partial update into the post, returning only the fields whose value changed
+    pub fn apply_patch(&mut self, patch: PostPatch) -> PostPatch {
+        let mut changes = PostPatch::default();
+
+        if let Some(title) = patch.title.filter(|t| *t != self.title) {
+            self.title = title.clone();
+            changes.title = Some(title);
+        }
+        if let Some(slug) = patch.slug.filter(|s| *s != self.slug) {
+            self.slug = slug.clone();
+            changes.slug = Some(slug);
+        }
+        if let Some(body) = patch.body.filter(|b| *b != self.body) {
+            self.body = body.clone();
+            changes.body = Some(body);
+        }
+        if let Some(tags) = patch.tags.map(normalize_tags).filter(|t| *t != self.tags) {
+            self.tags = tags.clone();
+            changes.tags = Some(tags);
+        }
+        if let Some(published_at) = patch.published_at.filter(|p| *p != self.published_at) {
+            self.published_at = published_at;
+            changes.published_at = Some(published_at);
+        }
+
+        changes
+    }
+}
+
+/// Lightweight reference to a published post, enough to link to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostLink {
+    pub slug: String,
+    /// Latest of the modification and publication dates
+    pub last_modified: DateTime<Utc>,
+}
+
+fn normalize_tags(mut tags: Vec<String>) -> Vec<String> {
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Partial update of a post's mutable fields; `None` leaves the field untouched
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostPatch {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub body: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// `Some(None)` unpublishes the post
+    pub published_at: Option<Option<DateTime<Utc>>>,
+}
+
+impl PostPatch {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.slug.is_none()
+            && self.body.is_none()
+            && self.tags.is_none()
+            && self.published_at.is_none()
+    }
 }
@@ -0,0 +1,12 @@
+use crate::domain::entities::{AccessClaims, ApiToken, Session, User};
+
+/// Who an authenticated request acts for
+#[derive(Debug, Clone)]
+pub enum Principal {
+    /// A script or integration holding an API token
+    Token(ApiToken),
+    /// A person signed in through a session cookie
+    User { user: Box<User>, session: Session },
+    /// A mobile app or publisher holding a signed access token
+    Jwt(AccessClaims),
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Single-use code that stands in for a TOTP code when the authenticator is lost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCode {
+    pub id: Option<i32>,
+    pub user_id: i32,
+    pub code_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl RecoveryCode {
+    /// Create a new recovery code without an ID (for creation)
+    pub fn new(user_id: i32, code_hash: String) -> Self {
+        Self {
+            id: None,
+            user_id,
+            code_hash,
+            created_at: Utc::now(),
+            used_at: None,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A long-lived secret exchanged for new access tokens; each one can be used once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshToken {
+    pub id: Option<i32>,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Set when the token is used, revoked or replaced
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshToken {
+    /// Create a new refresh token without an ID (for creation)
+    pub fn new(user_id: i32, token_hash: String, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            id: None,
+            user_id,
+            token_hash,
+            created_at: Utc::now(),
+            expires_at,
+            revoked_at: None,
+        }
+    }
+
+    /// Whether the token can still be exchanged at the given instant
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
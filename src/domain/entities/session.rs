@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A signed-in browser; the cookie carries a secret whose hash identifies the session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: Option<i32>,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl Session {
+    /// Create a new session without an ID (for creation)
+    pub fn new(user_id: i32, token_hash: String, expires_at: DateTime<Utc>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            user_id,
+            token_hash,
+            created_at: now,
+            expires_at,
+            last_seen_at: now,
+            revoked_at: None,
+            user_agent: None,
+            ip_address: None,
+        }
+    }
+
+    /// Whether the session authenticates requests at the given instant
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
@@ -0,0 +1,118 @@
+use crate::domain::entities::Role;
+use crate::domain::validation::{rules, ValidationErrors};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Consecutive failed logins that lock an account
+pub const MAX_FAILED_LOGINS: i32 = 5;
+
+/// How long a locked account rejects logins, even with the right password
+pub const LOCKOUT_DURATION: Duration = Duration::minutes(15);
+
+/// Person who signs in to manage the blog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: Option<i32>,
+    /// Login identifier, stored lowercase
+    pub email: String,
+    pub display_name: String,
+    pub role: Role,
+    /// Argon2 PHC string; never the password itself, and empty for users who sign in with single sign-on only
+    pub password_hash: String,
+    /// Failed logins since the last success or lockout
+    pub failed_login_attempts: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    /// Base32 TOTP secret, set from the start of two-factor enrollment
+    pub totp_secret: Option<String>,
+    /// When enrollment was confirmed; until then the secret is not asked for
+    pub totp_enabled_at: Option<DateTime<Utc>>,
+    /// Time step of the last accepted TOTP code, so no code is accepted twice
+    pub totp_last_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl User {
+    /// Create a new user without an ID (for creation)
+    pub fn new(email: String, display_name: String, role: Role, password_hash: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            email: normalize_email(&email),
+            display_name: display_name.trim().to_string(),
+            role,
+            password_hash,
+            failed_login_attempts: 0,
+            locked_until: None,
+            totp_secret: None,
+            totp_enabled_at: None,
+            totp_last_step: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Users created by single sign-on have no password until one is set
+    pub fn has_password(&self) -> bool {
+        !self.password_hash.is_empty()
+    }
+
+    /// Whether logins ask for a TOTP or recovery code after the password
+    pub fn has_two_factor(&self) -> bool {
+        self.totp_secret.is_some() && self.totp_enabled_at.is_some()
+    }
+
+    /// Whether logins are refused at the given instant
+    pub fn is_locked_at(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|locked_until| locked_until > now)
+    }
+
+    /// Count a failed login, locking the account once [`MAX_FAILED_LOGINS`] is reached
+    pub fn record_failed_login(&mut self, now: DateTime<Utc>) {
+        // Failures from before an expired lockout do not count towards the next one
+        if self.locked_until.is_some_and(|locked_until| locked_until <= now) {
+            self.locked_until = None;
+            self.failed_login_attempts = 0;
+        }
+
+        self.failed_login_attempts += 1;
+        if self.failed_login_attempts >= MAX_FAILED_LOGINS {
+            self.failed_login_attempts = 0;
+            self.locked_until = Some(now + LOCKOUT_DURATION);
+        }
+    }
+
+    pub fn record_successful_login(&mut self) {
+        self.failed_login_attempts = 0;
+        self.locked_until = None;
+    }
+
+    /// Validate the user data, collecting every violation instead of stopping at the first
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if rules::required(&mut errors, "email", &self.email) {
+            rules::max_length(&mut errors, "email", &self.email, rules::EMAIL_MAX_LENGTH);
+            rules::email_format(&mut errors, "email", &self.email);
+        }
+
+        if rules::required(&mut errors, "display_name", &self.display_name) {
+            rules::max_length(&mut errors, "display_name", &self.display_name, rules::DISPLAY_NAME_MAX_LENGTH);
+        }
+
+        errors.into_result()
+    }
+
+    /// Validate a password before it is hashed
+    pub fn validate_password(password: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        rules::min_length(&mut errors, "password", password, rules::PASSWORD_MIN_LENGTH);
+        rules::max_length(&mut errors, "password", password, rules::PASSWORD_MAX_LENGTH);
+        errors.into_result()
+    }
+}
+
+/// Emails are compared case-insensitively
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Link between a local user and their account at an OpenID Connect provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserIdentity {
+    pub id: Option<i32>,
+    pub user_id: i32,
+    /// Issuer URL of the provider
+    pub issuer: String,
+    /// The provider's ID for the account; unlike the email, it never changes
+    pub subject: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserIdentity {
+    /// Create a new link without an ID (for creation)
+    pub fn new(user_id: i32, issuer: String, subject: String) -> Self {
+        Self {
+            id: None,
+            user_id,
+            issuer,
+            subject,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub mod entities;
 pub mod ports;
+pub mod validation;
 
 pub use entities::*;
 pub use ports::*;
+pub use validation::*;
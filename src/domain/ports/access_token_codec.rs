@@ -0,0 +1,8 @@
+use crate::domain::entities::AccessClaims;
+
+/// Port (interface) for signing and verifying stateless access tokens
+pub trait AccessTokenCodec: Send + Sync {
+    fn issue(&self, claims: &AccessClaims) -> Result<String, String>;
+    /// The claims of a token with a valid signature, issuer and audience that has not expired
+    fn verify(&self, token: &str) -> Option<AccessClaims>;
+}
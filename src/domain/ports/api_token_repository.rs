@@ -0,0 +1,15 @@
+use crate::domain::entities::ApiToken;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Port (interface) for API token storage
+#[async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    /// Every token, revoked and expired ones included, oldest first
+    async fn find_all(&self) -> Result<Vec<ApiToken>, String>;
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, String>;
+    async fn save(&self, token: ApiToken) -> Result<ApiToken, String>;
+    /// Mark a token revoked, keeping the first revocation date; `None` when it does not exist
+    async fn revoke(&self, id: i32, at: DateTime<Utc>) -> Result<Option<ApiToken>, String>;
+    async fn touch(&self, id: i32, at: DateTime<Utc>) -> Result<(), String>;
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+
+/// Values binding an authorization request to the browser that started it
+pub struct AuthorizationRequest {
+    /// Echoed back to the callback, tying it to this request
+    pub state: String,
+    /// Echoed back in the ID token, so a token issued for another login is refused
+    pub nonce: String,
+    /// PKCE S256 challenge of the verifier presented when the code is exchanged
+    pub code_challenge: String,
+}
+
+/// Claims of a validated ID token that accounts are matched and created from
+#[derive(Debug, Clone)]
+pub struct IdentityClaims {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    /// Whether the provider vouches that the user owns `email`
+    pub email_verified: bool,
+    pub name: Option<String>,
+    /// Values of the configured roles claim, e.g. group names
+    pub groups: Vec<String>,
+}
+
+/// Failures of an identity provider, told apart so a refused login is not reported as an outage
+#[derive(Debug)]
+pub enum IdentityProviderError {
+    /// The provider refused the code, or its ID token failed validation
+    Rejected(String),
+    /// The provider could not be reached or sent an unusable response
+    Unavailable(String),
+}
+
+/// Port (interface) for an OpenID Connect provider using the authorization code flow
+#[async_trait]
+pub trait IdentityProvider: Send + Sync {
+    /// Issuer URL, which identifies the provider's accounts
+    fn issuer(&self) -> &str;
+    /// URL of the provider's login page for the given request
+    async fn authorization_url(&self, request: &AuthorizationRequest) -> Result<String, IdentityProviderError>;
+    /// Exchange an authorization code and validate the ID token against `nonce`
+    async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+        nonce: &str,
+    ) -> Result<IdentityClaims, IdentityProviderError>;
+}
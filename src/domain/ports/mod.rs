@@ -1,3 +1,19 @@
+pub mod access_token_codec;
+pub mod api_token_repository;
+pub mod identity_provider;
+pub mod password_hasher;
+pub mod post_activity;
 pub mod post_repository;
+pub mod refresh_token_repository;
+pub mod two_factor_repository;
+pub mod user_repository;
 
-pub use post_repository::PostRepository;
+pub use access_token_codec::AccessTokenCodec;
+pub use api_token_repository::ApiTokenRepository;
+pub use identity_provider::{AuthorizationRequest, IdentityClaims, IdentityProvider, IdentityProviderError};
+pub use password_hasher::PasswordHasher;
+pub use post_activity::{PostActivity, PostEvent};
+pub use post_repository::{BatchGuard, OperationError, OperationOutcome, PostImport, PostOperation, PostRepository, UpsertOutcome};
+pub use refresh_token_repository::RefreshTokenRepository;
+pub use two_factor_repository::{RecoveryCodeRepository, TwoFactorPolicyRepository};
+pub use user_repository::{SessionRepository, UserIdentityRepository, UserRepository};
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+/// Port (interface) for slow, salted password hashing
+#[async_trait]
+pub trait PasswordHasher: Send + Sync {
+    async fn hash(&self, password: &str) -> Result<String, String>;
+    /// Whether `password` matches `hash`; malformed hashes never match
+    async fn verify(&self, password: &str, hash: &str) -> Result<bool, String>;
+}
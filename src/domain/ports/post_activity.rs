@@ -0,0 +1,14 @@
+/// Something that happened to a post
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostEvent {
+    Created,
+    /// A draft got a publication date, now or scheduled
+    Published,
+    Deleted,
+}
+
+/// Port for recording post activity, e.g. as metrics
+/// Recording happens after the write and cannot fail the use case
+pub trait PostActivity: Send + Sync {
+    fn record(&self, event: PostEvent);
+}
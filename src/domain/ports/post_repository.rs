@@ -1,13 +1,96 @@
-use crate::domain::entities::Post;
+use crate::domain::entities::{Post, PostLink, PostPatch};
+use crate::domain::validation::ValidationErrors;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Result of writing one post of a bulk import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    Inserted(i32),
+    Updated(i32),
+}
+
+/// One post of a bulk import, created or updated by slug
+/// Dates the source left out are `None`: a new post takes the defaults already in `post`, while an
+/// existing post keeps its stored values
+#[derive(Debug, Clone)]
+pub struct PostImport {
+    pub post: Post,
+    /// `Some(None)` makes the post a draft
+    pub published_at: Option<Option<DateTime<Utc>>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// One write of a batch
+#[derive(Debug, Clone)]
+pub enum PostOperation {
+    Create(Post),
+    Update(i32, PostPatch),
+    Delete(i32),
+}
+
+/// Result of a batch operation that was applied
+#[derive(Debug, Clone)]
+pub enum OperationOutcome {
+    Created(Post),
+    /// `published` is whether the update gave a draft its publication date
+    Updated { post: Post, published: bool },
+    Deleted(i32),
+}
+
+/// Why a write was not applied
+#[derive(Debug, Clone)]
+pub enum OperationError {
+    NotFound,
+    /// The post the operation would leave behind is invalid, or its slug is taken
+    Invalid(ValidationErrors),
+    /// A concurrent write took the slug after it was checked
+    Conflict(String),
+    /// The batch guard refused the operation
+    Denied(String),
+    Failed(String),
+}
+
+/// Check of an update or delete against the stored post it targets, run inside the batch transaction
+/// so the post cannot change between the check and the write
+pub type BatchGuard = Box<dyn Fn(&Post, &PostOperation) -> Result<(), OperationError> + Send>;
 
 /// Port (interface) for post repository
 /// This defines what operations the domain needs from the persistence layer
 #[async_trait]
 pub trait PostRepository: Send + Sync {
     async fn find_all(&self) -> Result<Vec<Post>, String>;
+    /// Page through every post by ascending ID, starting after `after_id` (keyset pagination)
+    async fn find_after_id(&self, after_id: i32, limit: i64) -> Result<Vec<Post>, String>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Post>, String>;
-    async fn save(&self, post: Post) -> Result<Post, String>;
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<Post>, String>;
+    /// Find the post a retired slug used to belong to
+    async fn find_by_retired_slug(&self, slug: &str) -> Result<Option<Post>, String>;
+    /// Drop a retired slug so it stops redirecting; returns whether it existed
+    async fn release_retired_slug(&self, slug: &str) -> Result<bool, String>;
+    /// Posts whose publication date has passed, newest first, optionally restricted to a tag
+    async fn find_latest_published(&self, offset: i64, limit: i64, tag: Option<&str>) -> Result<Vec<Post>, String>;
+    async fn count_published(&self) -> Result<i64, String>;
+    /// Page through published posts in a stable order, loading only what is needed to link to them
+    async fn find_published_links(&self, offset: i64, limit: i64) -> Result<Vec<PostLink>, String>;
+    async fn save(&self, post: Post) -> Result<Post, OperationError>;
     async fn update(&self, id: i32, post: Post) -> Result<Option<Post>, String>;
+    /// Write only the fields present in `changes`, retiring the previous slug when it changes
+    async fn patch(&self, id: i32, changes: PostPatch) -> Result<Option<Post>, OperationError>;
     async fn delete(&self, id: i32) -> Result<bool, String>;
+    /// Create or update posts by slug in one transaction; each post succeeds or fails on its own
+    /// New posts keep their `id` when it is still free, so restored feeds keep stable GUIDs
+    /// Updates write the title, body and tags, and only the dates the import contains
+    async fn upsert_batch(&self, posts: Vec<PostImport>) -> Result<Vec<Result<UpsertOutcome, String>>, String>;
+    /// Apply operations in order in one transaction, each seeing the writes of the previous ones
+    /// With `atomic`, the first failure rolls back the whole batch and ends it, so only the operations
+    /// up to the failing one have a result; otherwise each operation is kept or undone on its own
+    /// `guard` runs before every update and delete; a refusal fails the operation like any other error
+    async fn apply_batch(
+        &self,
+        operations: Vec<PostOperation>,
+        atomic: bool,
+        guard: BatchGuard,
+    ) -> Result<Vec<Result<OperationOutcome, OperationError>>, String>;
 }
@@ -0,0 +1,14 @@
+use crate::domain::entities::RefreshToken;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Port (interface) for refresh token storage
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, String>;
+    async fn save(&self, token: RefreshToken) -> Result<RefreshToken, String>;
+    /// Revoke one token; returns whether it was still active, so two concurrent uses cannot both succeed
+    async fn revoke(&self, id: i32, at: DateTime<Utc>) -> Result<bool, String>;
+    /// Revoke every active token of a user, returning how many there were
+    async fn revoke_all(&self, user_id: i32, at: DateTime<Utc>) -> Result<usize, String>;
+}
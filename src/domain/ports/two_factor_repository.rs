@@ -0,0 +1,20 @@
+use crate::domain::entities::{RecoveryCode, Role};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Port (interface) for two-factor recovery codes
+#[async_trait]
+pub trait RecoveryCodeRepository: Send + Sync {
+    /// Replace every code of a user, used or not, with the given ones
+    async fn replace_all(&self, user_id: i32, codes: Vec<RecoveryCode>) -> Result<(), String>;
+    /// Mark an unused code of a user as used; returns whether there was one
+    async fn redeem(&self, user_id: i32, code_hash: &str, at: DateTime<Utc>) -> Result<bool, String>;
+    async fn count_unused(&self, user_id: i32) -> Result<usize, String>;
+}
+
+/// Port (interface) for the roles that must use two-factor authentication
+#[async_trait]
+pub trait TwoFactorPolicyRepository: Send + Sync {
+    async fn required_roles(&self) -> Result<Vec<Role>, String>;
+    async fn set_required_roles(&self, roles: Vec<Role>) -> Result<(), String>;
+}
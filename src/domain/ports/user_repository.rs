@@ -0,0 +1,40 @@
+use crate::domain::entities::{Role, Session, User, UserIdentity};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Port (interface) for user accounts
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<User>, String>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<User>, String>;
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, String>;
+    async fn save(&self, user: User) -> Result<User, String>;
+    async fn update_role(&self, id: i32, role: Role) -> Result<(), String>;
+    /// Persist the failed-login counter and lockout of a user
+    async fn update_login_state(&self, id: i32, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), String>;
+    /// Set or clear the TOTP secret and when it was enabled; the last accepted step is forgotten
+    async fn update_two_factor(&self, id: i32, totp_secret: Option<String>, totp_enabled_at: Option<DateTime<Utc>>) -> Result<(), String>;
+    /// Record a TOTP step as used unless it or a later one already was; returns whether it was recorded
+    async fn advance_totp_step(&self, id: i32, step: i64) -> Result<bool, String>;
+}
+
+/// Port (interface) for links between users and OpenID Connect accounts
+#[async_trait]
+pub trait UserIdentityRepository: Send + Sync {
+    async fn find(&self, issuer: &str, subject: &str) -> Result<Option<UserIdentity>, String>;
+    async fn save(&self, identity: UserIdentity) -> Result<UserIdentity, String>;
+}
+
+/// Port (interface) for login sessions
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<Session>, String>;
+    /// Sessions of a user that are neither revoked nor expired, most recently used first
+    async fn find_active_by_user(&self, user_id: i32, now: DateTime<Utc>) -> Result<Vec<Session>, String>;
+    async fn save(&self, session: Session) -> Result<Session, String>;
+    async fn touch(&self, id: i32, at: DateTime<Utc>) -> Result<(), String>;
+    /// Revoke one session of a user; returns whether an active session was revoked
+    async fn revoke(&self, user_id: i32, id: i32, at: DateTime<Utc>) -> Result<bool, String>;
+    /// Revoke every active session of a user, returning how many there were
+    async fn revoke_all(&self, user_id: i32, at: DateTime<Utc>) -> Result<usize, String>;
+}
@@ -0,0 +1,4 @@
+pub mod rules;
+pub mod validation_errors;
+
+pub use validation_errors::{ValidationErrors, Violation, ViolationCode};
@@ -0,0 +1,86 @@
+use crate::domain::validation::{ValidationErrors, Violation, ViolationCode};
+
+pub const TITLE_MAX_LENGTH: usize = 200;
+pub const SLUG_MAX_LENGTH: usize = 100;
+pub const BODY_MAX_LENGTH: usize = 100_000;
+pub const TAG_MAX_LENGTH: usize = 50;
+pub const TOKEN_NAME_MAX_LENGTH: usize = 100;
+pub const EMAIL_MAX_LENGTH: usize = 254;
+pub const DISPLAY_NAME_MAX_LENGTH: usize = 100;
+pub const PASSWORD_MIN_LENGTH: usize = 12;
+/// Argon2 accepts longer passwords, but hashing megabytes on every login attempt is a denial of service
+pub const PASSWORD_MAX_LENGTH: usize = 1024;
+
+/// Slugs that collide with routes or generated pages and can never be claimed by a post
+pub const RESERVED_SLUGS: &[&str] = &[
+    "admin", "api", "atom", "auth", "blog", "edit", "export", "feed", "health", "import",
+    "index", "login", "logout", "new", "page", "posts", "rss", "search", "sitemap", "tags",
+];
+
+/// Record a `required` violation when the value is blank; returns whether the value is present
+pub fn required(errors: &mut ValidationErrors, field: &str, value: &str) -> bool {
+    if value.trim().is_empty() {
+        errors.add(Violation::new(field, ViolationCode::Required, "must not be empty"));
+        return false;
+    }
+    true
+}
+
+/// Record a `too_short` violation when the value has fewer than `min` characters
+pub fn min_length(errors: &mut ValidationErrors, field: &str, value: &str, min: usize) {
+    if value.chars().count() < min {
+        errors.add(Violation::new(
+            field,
+            ViolationCode::TooShort,
+            format!("must be at least {} characters", min),
+        ));
+    }
+}
+
+/// Record a `too_long` violation when the value exceeds `max` characters
+pub fn max_length(errors: &mut ValidationErrors, field: &str, value: &str, max: usize) {
+    if value.chars().count() > max {
+        errors.add(Violation::new(
+            field,
+            ViolationCode::TooLong,
+            format!("must be at most {} characters", max),
+        ));
+    }
+}
+
+/// Record an `invalid_format` violation unless the value is lowercase words joined by single hyphens
+pub fn slug_format(errors: &mut ValidationErrors, field: &str, value: &str) {
+    if !is_valid_slug(value) {
+        errors.add(Violation::new(
+            field,
+            ViolationCode::InvalidFormat,
+            "must contain only lowercase letters, digits and single hyphens",
+        ));
+    }
+}
+
+/// Record an `invalid_format` violation unless the value looks like `local@domain`
+/// Deliverability is not checked; the address is only an identifier
+pub fn email_format(errors: &mut ValidationErrors, field: &str, value: &str) {
+    let valid = value.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty() && domain.contains('.') && !domain.starts_with('.') && !domain.ends_with('.')
+    }) && !value.chars().any(char::is_whitespace);
+
+    if !valid {
+        errors.add(Violation::new(field, ViolationCode::InvalidFormat, "must be an email address"));
+    }
+}
+
+/// Record a `reserved` violation when the slug is in [`RESERVED_SLUGS`]
+pub fn not_reserved(errors: &mut ValidationErrors, field: &str, value: &str) {
+    if RESERVED_SLUGS.contains(&value) {
+        errors.add(Violation::new(field, ViolationCode::Reserved, "is reserved"));
+    }
+}
+
+pub fn is_valid_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value.split('-').all(|part| {
+            !part.is_empty() && part.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
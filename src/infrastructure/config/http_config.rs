@@ -0,0 +1,91 @@
+use crate::infrastructure::config::site_config::{env_list, env_or, env_var, SiteConfig};
+
+/// Content Security Policy of the HTML pages; post bodies may embed images from anywhere
+const HTML_CONTENT_SECURITY_POLICY: &str =
+    "default-src 'self'; img-src 'self' https: data:; object-src 'none'; base-uri 'self'; form-action 'self'; frame-ancestors 'none'";
+
+/// Content Security Policy of everything else; JSON, feeds and SVG need no subresources
+const API_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; frame-ancestors 'none'";
+
+/// One year, the minimum for HSTS preload lists
+const DEFAULT_HSTS_MAX_AGE_SECONDS: u64 = 31_536_000;
+
+/// Settings of cross-origin requests from browser apps on other origins
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins such as `https://app.example.com`, or `*` for any; empty turns CORS off
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Let browsers send cookies and read responses to credentialed requests; needs explicit origins
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response
+    pub max_age_seconds: usize,
+}
+
+impl CorsConfig {
+    pub fn from_env() -> Self {
+        let allowed_origins = env_list("CORS_ALLOWED_ORIGINS");
+        let allow_credentials = env_var("CORS_ALLOW_CREDENTIALS").is_some_and(|value| value == "true");
+        assert!(
+            !(allow_credentials && allowed_origins.iter().any(|origin| origin == "*")),
+            "CORS_ALLOW_CREDENTIALS needs explicit CORS_ALLOWED_ORIGINS, not *"
+        );
+
+        Self {
+            allowed_origins,
+            allowed_methods: list_or("CORS_ALLOWED_METHODS", &["GET", "POST", "PUT", "PATCH", "DELETE"]),
+            allowed_headers: list_or("CORS_ALLOWED_HEADERS", &["authorization", "content-type", "if-match", "if-none-match"]),
+            allow_credentials,
+            max_age_seconds: env_or("CORS_MAX_AGE_SECONDS", "3600")
+                .parse()
+                .expect("CORS_MAX_AGE_SECONDS must be a non-negative integer"),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        !self.allowed_origins.is_empty()
+    }
+}
+
+/// Security headers sent with every response; HTML pages and the API get different defaults
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersConfig {
+    pub html_content_security_policy: String,
+    pub api_content_security_policy: String,
+    /// `Strict-Transport-Security` value; `None` unless the site is served over HTTPS
+    pub strict_transport_security: Option<String>,
+    /// `X-Frame-Options`: `DENY` or `SAMEORIGIN`
+    pub frame_options: String,
+    pub html_referrer_policy: String,
+    pub api_referrer_policy: String,
+}
+
+impl SecurityHeadersConfig {
+    pub fn from_env(site_config: &SiteConfig) -> Self {
+        let hsts_max_age: u64 = env_var("HSTS_MAX_AGE_SECONDS")
+            .map(|value| value.parse().expect("HSTS_MAX_AGE_SECONDS must be a non-negative integer"))
+            .unwrap_or(DEFAULT_HSTS_MAX_AGE_SECONDS);
+        let https = site_config.base_url.starts_with("https://");
+        let referrer_policy = env_var("REFERRER_POLICY");
+
+        Self {
+            html_content_security_policy: env_or("CSP_HTML", HTML_CONTENT_SECURITY_POLICY),
+            api_content_security_policy: env_or("CSP_API", API_CONTENT_SECURITY_POLICY),
+            strict_transport_security: (https && hsts_max_age > 0)
+                .then(|| format!("max-age={}; includeSubDomains", hsts_max_age)),
+            frame_options: env_or("FRAME_OPTIONS", "DENY"),
+            html_referrer_policy: referrer_policy.clone().unwrap_or_else(|| "strict-origin-when-cross-origin".to_string()),
+            api_referrer_policy: referrer_policy.unwrap_or_else(|| "no-referrer".to_string()),
+        }
+    }
+}
+
+fn list_or(key: &str, default: &[&str]) -> Vec<String> {
+    let values = env_list(key);
+    if values.is_empty() {
+        default.iter().map(|value| value.to_string()).collect()
+    } else {
+        values
+    }
+}
@@ -0,0 +1,83 @@
+use crate::infrastructure::config::site_config::{env_list, env_or, env_var, SiteConfig};
+use chrono::Duration;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Shortest HS256 secret accepted, the size of a SHA-256 output
+pub const JWT_SECRET_MIN_BYTES: usize = 32;
+
+/// How access tokens are signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    /// HMAC-SHA256 with shared secrets; only this server can verify the tokens
+    Hs256,
+    /// Ed25519 signatures; anyone can verify the tokens with the published keys
+    EdDsa,
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_uppercase().as_str() {
+            "HS256" => Ok(Self::Hs256),
+            "EDDSA" => Ok(Self::EdDsa),
+            _ => Err(format!("unknown JWT algorithm `{}`; expected HS256 or EdDSA", value)),
+        }
+    }
+}
+
+/// Settings of JWT access tokens and the refresh tokens that renew them
+/// Keys are listed newest first: the first one signs, the others are still accepted, so keys can be rotated
+#[derive(Debug, Clone)]
+pub struct JwtConfig {
+    pub algorithm: JwtAlgorithm,
+    /// HS256 secrets from `JWT_SECRETS`
+    pub secrets: Vec<String>,
+    /// Ed25519 PEM files from `JWT_KEY_FILES`; the first must hold a private key, the others may be public keys
+    pub key_files: Vec<PathBuf>,
+    pub issuer: String,
+    pub audience: String,
+    pub access_ttl: Duration,
+    pub refresh_ttl: Duration,
+}
+
+impl JwtConfig {
+    pub fn from_env(site_config: &SiteConfig) -> Self {
+        let algorithm = env_var("JWT_ALGORITHM")
+            .map(|value| value.parse().unwrap_or_else(|e: String| panic!("JWT_ALGORITHM: {}", e)))
+            .unwrap_or(JwtAlgorithm::EdDsa);
+        let secrets: Vec<String> = env_list("JWT_SECRETS");
+        assert!(
+            secrets.iter().all(|secret| secret.len() >= JWT_SECRET_MIN_BYTES),
+            "every JWT_SECRETS entry must be at least {} bytes",
+            JWT_SECRET_MIN_BYTES
+        );
+        let access_minutes: i64 = env_var("JWT_ACCESS_TTL_MINUTES")
+            .map(|value| value.parse().expect("JWT_ACCESS_TTL_MINUTES must be a positive integer"))
+            .filter(|minutes| *minutes > 0)
+            .unwrap_or(15);
+        let refresh_days: i64 = env_var("JWT_REFRESH_TTL_DAYS")
+            .map(|value| value.parse().expect("JWT_REFRESH_TTL_DAYS must be a positive integer"))
+            .filter(|days| *days > 0)
+            .unwrap_or(30);
+
+        Self {
+            algorithm,
+            secrets,
+            key_files: env_list("JWT_KEY_FILES").into_iter().map(PathBuf::from).collect(),
+            issuer: env_or("JWT_ISSUER", &site_config.base_url),
+            audience: env_or("JWT_AUDIENCE", "blog-rust"),
+            access_ttl: Duration::minutes(access_minutes),
+            refresh_ttl: Duration::days(refresh_days),
+        }
+    }
+
+    /// No key is configured for the algorithm, so one is generated at startup and tokens end with the process
+    pub fn ephemeral(&self) -> bool {
+        match self.algorithm {
+            JwtAlgorithm::Hs256 => self.secrets.is_empty(),
+            JwtAlgorithm::EdDsa => self.key_files.is_empty(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub mod http_config;
+pub mod jwt_config;
+pub mod oidc_config;
+pub mod rate_limit_config;
+pub mod session_config;
+pub mod site_config;
+
+pub use http_config::{CorsConfig, SecurityHeadersConfig};
+pub use jwt_config::{JwtAlgorithm, JwtConfig, JWT_SECRET_MIN_BYTES};
+pub use oidc_config::OidcConfig;
+pub use rate_limit_config::{RateLimitConfig, RouteClass};
+pub use session_config::{SessionConfig, SESSION_COOKIE};
+pub use site_config::SiteConfig;
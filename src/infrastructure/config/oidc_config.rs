@@ -0,0 +1,60 @@
+use crate::application::RoleMapping;
+use crate::domain::Role;
+use crate::infrastructure::config::site_config::{env_list, env_or, env_var, SiteConfig};
+
+/// Settings of single sign-on with an OpenID Connect provider
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    /// Issuer URL; its discovery document is at `/.well-known/openid-configuration` below it
+    pub issuer: String,
+    pub client_id: String,
+    /// Unset for a public client, which PKCE alone protects
+    pub client_secret: Option<String>,
+    /// Callback URL registered with the provider
+    pub redirect_url: String,
+    pub scopes: Vec<String>,
+    /// ID token claim holding group or role names; dots reach into nested objects
+    pub roles_claim: String,
+    pub role_mapping: RoleMapping,
+}
+
+impl OidcConfig {
+    /// Single sign-on is off unless `OIDC_ISSUER` is set
+    pub fn from_env(site_config: &SiteConfig) -> Option<Self> {
+        let issuer = env_var("OIDC_ISSUER")?;
+        let client_id = env_var("OIDC_CLIENT_ID").expect("OIDC_CLIENT_ID must be set when OIDC_ISSUER is");
+
+        let mut scopes = env_list("OIDC_SCOPES");
+        if scopes.is_empty() {
+            scopes = vec!["email".to_string(), "profile".to_string()];
+        }
+        if !scopes.iter().any(|scope| scope == "openid") {
+            scopes.insert(0, "openid".to_string());
+        }
+
+        let rules = env_list("OIDC_ROLE_MAP")
+            .into_iter()
+            .map(|rule| {
+                let (group, role) = rule
+                    .rsplit_once('=')
+                    .unwrap_or_else(|| panic!("OIDC_ROLE_MAP: `{}` is not of the form group=role", rule));
+                let role = role.trim().parse().unwrap_or_else(|e: String| panic!("OIDC_ROLE_MAP: {}", e));
+                (group.trim().to_string(), role)
+            })
+            .collect();
+        let default_role = match env_or("OIDC_DEFAULT_ROLE", Role::Contributor.as_str()).as_str() {
+            "none" => None,
+            role => Some(role.parse().unwrap_or_else(|e: String| panic!("OIDC_DEFAULT_ROLE: {}", e))),
+        };
+
+        Some(Self {
+            issuer,
+            client_id,
+            client_secret: env_var("OIDC_CLIENT_SECRET"),
+            redirect_url: env_var("OIDC_REDIRECT_URL").unwrap_or_else(|| site_config.url("/auth/oidc/callback")),
+            scopes,
+            roles_claim: env_or("OIDC_ROLES_CLAIM", "groups"),
+            role_mapping: RoleMapping { rules, default_role },
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use crate::infrastructure::config::site_config::env_var;
+use crate::infrastructure::rate_limit::RateLimit;
+use std::fmt;
+
+/// Routes sharing a rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    Read,
+    Write,
+    /// Writes under `/auth/`, which check passwords and codes
+    Auth,
+}
+
+impl fmt::Display for RouteClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Auth => "auth",
+        })
+    }
+}
+
+/// Settings of the per-client rate limits; `None` leaves a class unlimited
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub read: Option<RateLimit>,
+    pub write: Option<RateLimit>,
+    pub auth: Option<RateLimit>,
+    /// Key anonymous clients by `Forwarded` or `X-Forwarded-For`; only safe behind a proxy that sets them
+    pub trust_proxy: bool,
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        Self {
+            read: per_minute("RATE_LIMIT_READ_PER_MINUTE", 300),
+            write: per_minute("RATE_LIMIT_WRITE_PER_MINUTE", 60),
+            auth: per_minute("RATE_LIMIT_AUTH_PER_MINUTE", 10),
+            trust_proxy: env_var("RATE_LIMIT_TRUST_PROXY").is_some_and(|value| value == "true"),
+        }
+    }
+
+    pub fn limit(&self, class: RouteClass) -> Option<&RateLimit> {
+        match class {
+            RouteClass::Read => self.read.as_ref(),
+            RouteClass::Write => self.write.as_ref(),
+            RouteClass::Auth => self.auth.as_ref(),
+        }
+    }
+}
+
+/// `0` turns the limit off
+fn per_minute(key: &str, default: u32) -> Option<RateLimit> {
+    let capacity = env_var(key)
+        .map(|value| value.parse().unwrap_or_else(|_| panic!("{} must be a non-negative integer", key)))
+        .unwrap_or(default);
+    (capacity > 0).then(|| RateLimit::per_minute(capacity))
+}
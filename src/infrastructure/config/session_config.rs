@@ -0,0 +1,50 @@
+use crate::infrastructure::config::site_config::{env_var, SiteConfig};
+use actix_web::cookie::Key;
+use chrono::Duration;
+
+/// Name of the cookie carrying the session secret
+pub const SESSION_COOKIE: &str = "blog_session";
+
+/// Shortest `SESSION_SECRET` accepted; the signing key is derived from it
+pub const SESSION_SECRET_MIN_BYTES: usize = 32;
+
+/// Settings of the login session cookie
+#[derive(Clone)]
+pub struct SessionConfig {
+    /// Signs the session cookie
+    pub key: Key,
+    /// How long a session lasts after login
+    pub ttl: Duration,
+    /// Send the cookie over HTTPS only; on when the site is served over HTTPS
+    pub secure: bool,
+    /// The key was generated at startup because `SESSION_SECRET` is unset
+    pub ephemeral: bool,
+}
+
+impl SessionConfig {
+    pub fn from_env(site_config: &SiteConfig) -> Self {
+        let secret = env_var("SESSION_SECRET");
+        let key = match &secret {
+            Some(secret) => {
+                assert!(
+                    secret.len() >= SESSION_SECRET_MIN_BYTES,
+                    "SESSION_SECRET must be at least {} bytes",
+                    SESSION_SECRET_MIN_BYTES
+                );
+                Key::derive_from(secret.as_bytes())
+            }
+            None => Key::generate(),
+        };
+        let ttl_hours: i64 = env_var("SESSION_TTL_HOURS")
+            .map(|value| value.parse().expect("SESSION_TTL_HOURS must be a positive integer"))
+            .filter(|hours| *hours > 0)
+            .unwrap_or(24 * 14);
+
+        Self {
+            key,
+            ttl: Duration::hours(ttl_hours),
+            secure: site_config.base_url.starts_with("https://"),
+            ephemeral: secret.is_none(),
+        }
+    }
+}
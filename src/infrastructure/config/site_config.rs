@@ -0,0 +1,85 @@
+use dotenvy::dotenv;
+use std::env;
+use std::path::PathBuf;
+
+/// Public-facing site settings used to build absolute URLs and feed metadata
+#[derive(Debug, Clone)]
+pub struct SiteConfig {
+    /// Absolute base URL without a trailing slash, e.g. `https://blog.example.com`
+    pub base_url: String,
+    pub title: String,
+    pub description: String,
+    pub language: String,
+    /// Author credited in feeds
+    pub author: String,
+    /// Number of posts included in feeds
+    pub feed_size: usize,
+    /// Number of posts listed per HTML page
+    pub page_size: i64,
+    /// Directory of images and other files published under `/media`
+    pub media_dir: PathBuf,
+}
+
+impl SiteConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+
+        let base_url = env_or("SITE_BASE_URL", "http://127.0.0.1:8080");
+        let feed_size = env_var("FEED_SIZE")
+            .map(|value| value.parse().expect("FEED_SIZE must be a positive integer"))
+            .unwrap_or(20);
+        let page_size = env_var("PAGE_SIZE")
+            .map(|value| value.parse().expect("PAGE_SIZE must be a positive integer"))
+            .filter(|size| *size > 0)
+            .unwrap_or(10);
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            title: env_or("SITE_TITLE", "Blog Rust"),
+            description: env_or("SITE_DESCRIPTION", "Latest posts"),
+            language: env_or("SITE_LANGUAGE", "en"),
+            author: env_var("SITE_AUTHOR").unwrap_or_else(|| env_or("SITE_TITLE", "Blog Rust")),
+            feed_size,
+            page_size,
+            media_dir: PathBuf::from(env_or("MEDIA_DIR", "media")),
+        }
+    }
+
+    /// Absolute URL for a site-relative path starting with `/`
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Public permalink of a post
+    pub fn post_url(&self, slug: &str) -> String {
+        self.url(&format!("/blog/{}", slug))
+    }
+
+    /// Public page listing the posts of a tag
+    pub fn tag_url(&self, tag: &str) -> String {
+        self.url(&format!("/tags/{}", tag))
+    }
+}
+
+/// Read an environment variable, treating an empty value (as left by `.env.example`) as unset
+pub(crate) fn env_var(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.trim().is_empty())
+}
+
+pub(crate) fn env_or(key: &str, default: &str) -> String {
+    env_var(key).unwrap_or_else(|| default.to_string())
+}
+
+/// Comma-separated values of an environment variable
+pub(crate) fn env_list(key: &str) -> Vec<String> {
+    env_var(key)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
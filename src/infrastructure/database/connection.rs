@@ -1,20 +1,54 @@
+use crate::infrastructure::observability::{metrics, PoolMetrics};
 use diesel::prelude::*;
-use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::r2d2::{ConnectionManager, CustomizeConnection, Pool};
 use dotenvy::dotenv;
 use std::env;
+use std::time::Instant;
 
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
+/// Turns on foreign key enforcement, which SQLite leaves off on every new connection,
+/// so the `ON DELETE CASCADE` and `SET NULL` clauses of the schema take effect
+#[derive(Debug)]
+struct ForeignKeys;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ForeignKeys {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        diesel::sql_query("PRAGMA foreign_keys = ON")
+            .execute(conn)
+            .map(|_| ())
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
 pub fn establish_connection_pool() -> DbPool {
     dotenv().ok();
 
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let manager = ConnectionManager::<SqliteConnection>::new(database_url);
     Pool::builder()
+        .event_handler(Box::new(PoolMetrics))
+        .connection_customizer(Box::new(ForeignKeys))
         .build(manager)
         .unwrap_or_else(|_| panic!("Error creating connection pool"))
 }
 
+/// A single-connection pool over a migrated in-memory database
+#[cfg(test)]
+pub fn memory_pool() -> DbPool {
+    use diesel_migrations::MigrationHarness;
+
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+        .expect("in-memory pool");
+    pool.get()
+        .expect("in-memory connection")
+        .run_pending_migrations(super::health::MIGRATIONS)
+        .expect("migrations apply");
+    pool
+}
+
 pub fn run_with_connection<F, T>(pool: &Pool<ConnectionManager<SqliteConnection>>, f: F) -> T
 where
     F: FnOnce(&mut SqliteConnection) -> T,
@@ -22,3 +56,31 @@ where
     let mut conn = pool.get().expect("Failed to get connection from pool");
     f(&mut conn)
 }
+
+/// Run a database call on the blocking thread pool, inside a `db` span recording its duration and any error
+/// The duration is also recorded in the `db_operation_duration_seconds` metric
+pub async fn spawn_db_call<F, T>(operation: &'static str, call: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    let span = tracing::info_span!("db", operation, elapsed_ms = tracing::field::Empty);
+    let started = Instant::now();
+    let result = tokio::task::spawn_blocking({
+        let span = span.clone();
+        move || span.in_scope(call)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))
+    .and_then(|result| result);
+
+    let elapsed = started.elapsed();
+    metrics().record_db_operation(operation, result.is_ok(), elapsed);
+    let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+    span.record("elapsed_ms", elapsed_ms);
+    span.in_scope(|| match &result {
+        Ok(_) => tracing::debug!(elapsed_ms, "database call finished"),
+        Err(error) => tracing::error!(elapsed_ms, error = %error, "database call failed"),
+    });
+    result
+}
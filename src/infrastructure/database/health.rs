@@ -0,0 +1,90 @@
+use crate::infrastructure::database::DbPool;
+use diesel::migration::MigrationSource;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel::sqlite::Sqlite;
+use diesel::sql_query;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use std::time::{Duration, Instant};
+
+/// Migrations compiled into the binary, which the database must have applied
+pub(crate) const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Longest wait for a pooled connection, so a probe answers well within its own timeout
+const CHECKOUT_TIMEOUT: Duration = Duration::from_secs(2);
+
+const SKIPPED: &str = "Skipped without a connection";
+
+/// Outcome of one readiness check
+pub struct HealthCheck {
+    pub name: &'static str,
+    /// Why the check failed; `None` when it passed
+    pub error: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl HealthCheck {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(QueryableByName)]
+struct AppliedMigration {
+    #[diesel(sql_type = Text)]
+    version: String,
+}
+
+/// Check out a connection, run a trivial query and compare the applied migrations with the embedded ones
+/// Blocks the calling thread; without a connection the other checks fail without running
+pub fn check_database(pool: &DbPool) -> Vec<HealthCheck> {
+    let started = Instant::now();
+    let mut conn = match pool.get_timeout(CHECKOUT_TIMEOUT) {
+        Ok(conn) => conn,
+        Err(e) => {
+            let error = format!("No database connection: {}", e);
+            return vec![
+                HealthCheck { name: "connection", error: Some(error), elapsed: started.elapsed() },
+                HealthCheck { name: "query", error: Some(SKIPPED.to_string()), elapsed: Duration::ZERO },
+                HealthCheck { name: "migrations", error: Some(SKIPPED.to_string()), elapsed: Duration::ZERO },
+            ];
+        }
+    };
+    let connection = HealthCheck { name: "connection", error: None, elapsed: started.elapsed() };
+
+    let started = Instant::now();
+    // Reading the schema touches the database file, so a locked or corrupt database fails here
+    let error = sql_query("SELECT 1 FROM sqlite_master LIMIT 1")
+        .execute(&mut conn)
+        .err()
+        .map(|e| format!("Database error: {}", e));
+    let query = HealthCheck { name: "query", error, elapsed: started.elapsed() };
+
+    let started = Instant::now();
+    let error = pending_migrations(&mut conn).err();
+    let migrations = HealthCheck { name: "migrations", error, elapsed: started.elapsed() };
+
+    vec![connection, query, migrations]
+}
+
+/// Read-only comparison, unlike diesel's harness, which creates its bookkeeping table when missing
+fn pending_migrations(conn: &mut SqliteConnection) -> Result<(), String> {
+    let applied: Vec<String> = sql_query("SELECT version FROM __diesel_schema_migrations")
+        .load::<AppliedMigration>(conn)
+        .map_err(|e| format!("Applied migrations unreadable: {}", e))?
+        .into_iter()
+        .map(|migration| migration.version)
+        .collect();
+
+    let embedded = MigrationSource::<Sqlite>::migrations(&MIGRATIONS).map_err(|e| format!("Migration error: {}", e))?;
+    let pending: Vec<String> = embedded
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .filter(|version| !applied.contains(version))
+        .collect();
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} migration(s) pending: {}", pending.len(), pending.join(", ")))
+    }
+}
@@ -1,3 +1,7 @@
 pub mod connection;
+pub mod health;
 
-pub use connection::{DbPool, establish_connection_pool, run_with_connection};
+pub use connection::{DbPool, establish_connection_pool, run_with_connection, spawn_db_call};
+#[cfg(test)]
+pub use connection::memory_pool;
+pub use health::{check_database, HealthCheck};
@@ -0,0 +1,61 @@
+use crate::domain::Post;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::xml::escape;
+use crate::infrastructure::feeds::{last_updated, post_guid, FeedChannel, SUMMARY_LENGTH};
+use crate::infrastructure::rendering::{excerpt, render_markdown};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::{self, Write};
+
+/// Render an Atom 1.0 (RFC 4287) document
+pub fn render_atom(site: &SiteConfig, channel: &FeedChannel, posts: &[Post]) -> String {
+    let mut xml = String::new();
+    write_atom(&mut xml, site, channel, posts).expect("writing to a String cannot fail");
+    xml
+}
+
+fn write_atom(xml: &mut String, site: &SiteConfig, channel: &FeedChannel, posts: &[Post]) -> fmt::Result {
+    // `updated` is mandatory, so an empty feed falls back to the epoch rather than the current time
+    let updated = last_updated(posts).unwrap_or(DateTime::UNIX_EPOCH);
+
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(xml, r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{}">"#, escape(&site.language))?;
+    writeln!(xml, "<id>{}</id>", escape(&channel.link))?;
+    writeln!(xml, "<title>{}</title>", escape(&channel.title))?;
+    writeln!(xml, "<subtitle>{}</subtitle>", escape(&channel.description))?;
+    writeln!(xml, "<updated>{}</updated>", timestamp(updated))?;
+    writeln!(xml, "<author><name>{}</name></author>", escape(&site.author))?;
+    writeln!(xml, r#"<link rel="self" type="application/atom+xml" href="{}"/>"#, escape(&channel.self_url))?;
+    writeln!(xml, r#"<link rel="alternate" type="text/html" href="{}"/>"#, escape(&channel.link))?;
+
+    for post in posts {
+        writeln!(xml, "<entry>")?;
+        writeln!(xml, "<id>{}</id>", escape(&post_guid(site, post)))?;
+        writeln!(xml, "<title>{}</title>", escape(&post.title))?;
+        writeln!(xml, "<updated>{}</updated>", timestamp(post.updated_at))?;
+        if let Some(published_at) = post.published_at {
+            writeln!(xml, "<published>{}</published>", timestamp(published_at))?;
+        }
+        writeln!(
+            xml,
+            r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+            escape(&site.post_url(&post.slug))
+        )?;
+        for tag in &post.tags {
+            writeln!(
+                xml,
+                r#"<category term="{}" scheme="{}"/>"#,
+                escape(tag),
+                escape(&site.url("/tags/"))
+            )?;
+        }
+        writeln!(xml, "<summary>{}</summary>", escape(&excerpt(&post.body, SUMMARY_LENGTH)))?;
+        writeln!(xml, r#"<content type="html">{}</content>"#, escape(&render_markdown(&post.body)))?;
+        writeln!(xml, "</entry>")?;
+    }
+
+    writeln!(xml, "</feed>")
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,67 @@
+use crate::domain::Post;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::{post_guid, FeedChannel, SUMMARY_LENGTH};
+use crate::infrastructure::rendering::{excerpt, render_markdown};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+const VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    description: &'a str,
+    language: &'a str,
+    authors: Vec<JsonFeedAuthor<'a>>,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: String,
+    url: String,
+    title: &'a str,
+    content_html: String,
+    summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<DateTime<Utc>>,
+    date_modified: DateTime<Utc>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
+
+/// Render a JSON Feed 1.1 document
+pub fn render_json_feed(site: &SiteConfig, channel: &FeedChannel, posts: &[Post]) -> String {
+    let feed = JsonFeed {
+        version: VERSION,
+        title: &channel.title,
+        home_page_url: &channel.link,
+        feed_url: &channel.self_url,
+        description: &channel.description,
+        language: &site.language,
+        authors: vec![JsonFeedAuthor { name: &site.author }],
+        items: posts
+            .iter()
+            .map(|post| JsonFeedItem {
+                id: post_guid(site, post),
+                url: site.post_url(&post.slug),
+                title: &post.title,
+                content_html: render_markdown(&post.body),
+                summary: excerpt(&post.body, SUMMARY_LENGTH),
+                date_published: post.published_at,
+                date_modified: post.updated_at,
+                tags: &post.tags,
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&feed).expect("feed serialization cannot fail")
+}
@@ -0,0 +1,137 @@
+pub mod atom;
+pub mod json_feed;
+pub mod rss;
+pub mod xml;
+
+pub use atom::render_atom;
+pub use json_feed::render_json_feed;
+pub use rss::render_rss;
+
+use crate::domain::Post;
+use crate::infrastructure::config::SiteConfig;
+use chrono::{DateTime, Utc};
+
+/// Length of the plain-text summary attached to each feed entry
+pub const SUMMARY_LENGTH: usize = 280;
+
+/// Syndication formats served for every post listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    pub const ALL: [FeedFormat; 3] = [FeedFormat::Rss, FeedFormat::Atom, FeedFormat::Json];
+
+    /// File name the feed is served under, relative to its listing
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.rss",
+            FeedFormat::Atom => "feed.atom",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+
+    /// Media type used in autodiscovery links
+    pub fn media_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml",
+            FeedFormat::Atom => "application/atom+xml",
+            FeedFormat::Json => "application/feed+json",
+        }
+    }
+
+    /// Value of the `Content-Type` response header
+    pub fn content_type(self) -> String {
+        format!("{}; charset=utf-8", self.media_type())
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "RSS",
+            FeedFormat::Atom => "Atom",
+            FeedFormat::Json => "JSON Feed",
+        }
+    }
+
+    pub fn render(self, site: &SiteConfig, channel: &FeedChannel, posts: &[Post]) -> String {
+        match self {
+            FeedFormat::Rss => render_rss(site, channel, posts),
+            FeedFormat::Atom => render_atom(site, channel, posts),
+            FeedFormat::Json => render_json_feed(site, channel, posts),
+        }
+    }
+}
+
+/// Channel-level metadata shared by every feed format
+pub struct FeedChannel {
+    pub title: String,
+    /// HTML page the feed mirrors; doubles as the feed's stable identifier
+    pub link: String,
+    /// Absolute URL of the feed document itself
+    pub self_url: String,
+    pub description: String,
+}
+
+impl FeedChannel {
+    /// Feed of every published post
+    pub fn site(site: &SiteConfig, format: FeedFormat) -> Self {
+        Self {
+            title: site.title.clone(),
+            link: site.url("/"),
+            self_url: site.url(&feed_path(None, format)),
+            description: site.description.clone(),
+        }
+    }
+
+    /// Feed of the published posts carrying `tag`
+    pub fn tag(site: &SiteConfig, tag: &str, format: FeedFormat) -> Self {
+        Self {
+            title: format!("{} – #{}", site.title, tag),
+            link: site.tag_url(tag),
+            self_url: site.url(&feed_path(Some(tag), format)),
+            description: format!("Posts tagged #{} on {}", tag, site.title),
+        }
+    }
+}
+
+/// Site-relative path of the site feed or of a tag feed
+pub fn feed_path(tag: Option<&str>, format: FeedFormat) -> String {
+    match tag {
+        Some(tag) => format!("/tags/{}/{}", tag, format.file_name()),
+        None => format!("/{}", format.file_name()),
+    }
+}
+
+/// `Link` header value advertising every feed format for autodiscovery
+pub fn discovery_link_header(site: &SiteConfig, tag: Option<&str>) -> String {
+    FeedFormat::ALL
+        .iter()
+        .map(|format| {
+            format!(
+                r#"<{}>; rel="alternate"; type="{}"; title="{} {}""#,
+                site.url(&feed_path(tag, *format)),
+                format.media_type(),
+                site.title.replace('"', "'"),
+                format.label()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Most recent modification across the posts; derived from content so output stays reproducible
+pub fn last_updated(posts: &[Post]) -> Option<DateTime<Utc>> {
+    posts
+        .iter()
+        .flat_map(|post| [Some(post.updated_at), post.published_at])
+        .flatten()
+        .max()
+}
+
+/// Stable identifier of a post that survives slug changes
+pub fn post_guid(site: &SiteConfig, post: &Post) -> String {
+    site.url(&format!("/posts/{}", post.id.unwrap_or(0)))
+}
@@ -0,0 +1,55 @@
+use crate::domain::Post;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::xml::{cdata, escape};
+use crate::infrastructure::feeds::{last_updated, post_guid, FeedChannel, SUMMARY_LENGTH};
+use crate::infrastructure::rendering::{excerpt, render_markdown};
+use std::fmt::{self, Write};
+
+/// Render an RSS 2.0 document with full HTML content in `content:encoded`
+pub fn render_rss(site: &SiteConfig, channel: &FeedChannel, posts: &[Post]) -> String {
+    let mut xml = String::new();
+    write_rss(&mut xml, site, channel, posts).expect("writing to a String cannot fail");
+    xml
+}
+
+fn write_rss(xml: &mut String, site: &SiteConfig, channel: &FeedChannel, posts: &[Post]) -> fmt::Result {
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        xml,
+        r#"<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:atom="http://www.w3.org/2005/Atom">"#
+    )?;
+    writeln!(xml, "<channel>")?;
+    writeln!(xml, "<title>{}</title>", escape(&channel.title))?;
+    writeln!(xml, "<link>{}</link>", escape(&channel.link))?;
+    writeln!(xml, "<description>{}</description>", escape(&channel.description))?;
+    writeln!(xml, "<language>{}</language>", escape(&site.language))?;
+    writeln!(
+        xml,
+        r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+        escape(&channel.self_url)
+    )?;
+    if let Some(updated) = last_updated(posts) {
+        writeln!(xml, "<lastBuildDate>{}</lastBuildDate>", updated.to_rfc2822())?;
+    }
+
+    for post in posts {
+        let permalink = site.post_url(&post.slug);
+        writeln!(xml, "<item>")?;
+        writeln!(xml, "<title>{}</title>", escape(&post.title))?;
+        writeln!(xml, "<link>{}</link>", escape(&permalink))?;
+        writeln!(xml, r#"<guid isPermaLink="false">{}</guid>"#, escape(&post_guid(site, post)))?;
+        if let Some(published_at) = post.published_at {
+            writeln!(xml, "<pubDate>{}</pubDate>", published_at.to_rfc2822())?;
+        }
+        for tag in &post.tags {
+            writeln!(xml, "<category>{}</category>", escape(tag))?;
+        }
+        writeln!(xml, "<description>{}</description>", escape(&excerpt(&post.body, SUMMARY_LENGTH)))?;
+        writeln!(xml, "<content:encoded>{}</content:encoded>", cdata(&render_markdown(&post.body)))?;
+        writeln!(xml, "</item>")?;
+    }
+
+    writeln!(xml, "</channel>")?;
+    writeln!(xml, "</rss>")
+}
+
@@ -0,0 +1,159 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+const YAML_DELIMITER: &str = "---";
+const TOML_DELIMITER: &str = "+++";
+/// Front matter keys holding dates
+const DATE_KEYS: [&str; 3] = ["date", "created", "updated"];
+
+/// Post metadata declared at the top of a Markdown file
+/// Unknown keys (e.g. `author`, `description`) are ignored so other generators' files import as-is
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FrontMatter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    /// Publication date, either `YYYY-MM-DD` or a full RFC 3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Creation date; defaults to `date`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// Last modification date; only applied when the post is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+/// A Markdown file split into its front matter and body
+#[derive(Debug, Clone)]
+pub struct MarkdownDocument {
+    pub front_matter: FrontMatter,
+    pub body: String,
+}
+
+/// Split a document with YAML (`---`) or TOML (`+++`) front matter
+pub fn parse_document(text: &str) -> Result<MarkdownDocument, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut lines = text.split_inclusive('\n');
+
+    let delimiter = match lines.next().map(str::trim_end) {
+        Some(YAML_DELIMITER) => YAML_DELIMITER,
+        Some(TOML_DELIMITER) => TOML_DELIMITER,
+        _ => return Err("missing front matter: the file must start with `---` or `+++`".to_string()),
+    };
+
+    let mut header = String::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        let trimmed = line.trim_end();
+        if trimmed == delimiter || (delimiter == YAML_DELIMITER && trimmed == "...") {
+            closed = true;
+            break;
+        }
+        header.push_str(line);
+    }
+    if !closed {
+        return Err(format!("unterminated front matter: missing closing `{}`", delimiter));
+    }
+
+    let front_matter = if delimiter == YAML_DELIMITER {
+        parse_yaml(&header)?
+    } else {
+        parse_toml(&header)?
+    };
+
+    // A single blank line conventionally separates the front matter from the body
+    let rest: String = lines.collect();
+    let body = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+        .unwrap_or(&rest)
+        .to_string();
+
+    Ok(MarkdownDocument { front_matter, body })
+}
+
+/// Render a document with YAML front matter, the inverse of [`parse_document`]
+pub fn render_document(front_matter: &FrontMatter, body: &str) -> Result<String, String> {
+    let yaml = serde_yaml::to_string(front_matter).map_err(|e| format!("YAML error: {}", e))?;
+    Ok(format!("{0}\n{1}{0}\n\n{2}", YAML_DELIMITER, yaml, body))
+}
+
+/// Format a date for front matter, keeping sub-second precision so round trips are exact
+pub fn format_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Parse a front matter date; dates without a time are taken as midnight UTC
+pub fn parse_date(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(date.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+        .ok_or_else(|| format!("invalid date `{}`: expected YYYY-MM-DD or RFC 3339", value))
+}
+
+fn parse_yaml(header: &str) -> Result<FrontMatter, String> {
+    if header.trim().is_empty() {
+        return Ok(FrontMatter::default());
+    }
+    serde_yaml::from_str(header).map_err(|e| format!("invalid YAML front matter: {}", e))
+}
+
+fn parse_toml(header: &str) -> Result<FrontMatter, String> {
+    let mut table: toml::Table =
+        toml::from_str(header).map_err(|e| format!("invalid TOML front matter: {}", e))?;
+
+    // TOML has a native datetime type; read it back as text like the YAML dates
+    for key in DATE_KEYS {
+        if let Some(toml::Value::Datetime(date)) = table.get(key) {
+            let date = date.to_string();
+            table.insert(key.to_string(), toml::Value::String(date));
+        }
+    }
+
+    table
+        .try_into()
+        .map_err(|e| format!("invalid TOML front matter: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_front_matter_reads_native_datetimes() {
+        let text = "+++\ntitle = \"Hello\"\ndate = 2024-01-02\ncreated = 2024-01-01T00:00:00Z\nupdated = 2024-01-03T12:30:00+02:00\n+++\n\nBody\n";
+        let document = parse_document(text).unwrap();
+        let front_matter = document.front_matter;
+
+        assert_eq!(front_matter.title.as_deref(), Some("Hello"));
+        let date = |value: Option<String>| parse_date(&value.expect("date is set")).unwrap();
+        assert_eq!(date(front_matter.date), parse_date("2024-01-02T00:00:00Z").unwrap());
+        assert_eq!(date(front_matter.created), parse_date("2024-01-01T00:00:00Z").unwrap());
+        assert_eq!(date(front_matter.updated), parse_date("2024-01-03T10:30:00Z").unwrap());
+        assert_eq!(document.body, "Body\n");
+    }
+
+    #[test]
+    fn toml_front_matter_still_reads_quoted_dates() {
+        let text = "+++\ntitle = \"Hello\"\ncreated = \"2024-01-01\"\n+++\nBody\n";
+        let front_matter = parse_document(text).unwrap().front_matter;
+        assert_eq!(front_matter.created.as_deref(), Some("2024-01-01"));
+        assert_eq!(front_matter.date, None);
+    }
+}
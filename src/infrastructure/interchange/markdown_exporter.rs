@@ -0,0 +1,103 @@
+use crate::application::PostService;
+use crate::domain::Post;
+use crate::infrastructure::interchange::front_matter::{format_date, render_document, FrontMatter};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Directory of the archive holding one Markdown file per post
+pub const POSTS_DIR: &str = "posts";
+/// Directory of the archive holding the media referenced by posts, mirroring `/media` URLs
+pub const MEDIA_DIR: &str = "media";
+
+const MEDIA_URL_PREFIX: &str = "/media/";
+
+/// Exports every post, drafts included, as a zip of Markdown files with front matter
+/// The archive can be fed back to the Markdown importer to recreate the same posts
+pub struct MarkdownExporter {
+    post_service: Arc<PostService>,
+    media_dir: PathBuf,
+}
+
+impl MarkdownExporter {
+    pub fn new(post_service: Arc<PostService>, media_dir: PathBuf) -> Self {
+        Self { post_service, media_dir }
+    }
+
+    /// Build the archive in memory; entries are sorted and undated, so identical content gives identical bytes
+    pub async fn export_zip(&self) -> Result<Vec<u8>, String> {
+        let mut posts = self.post_service.get_all_posts().await.map_err(|e| e.to_string())?;
+        posts.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        let media_dir = self.media_dir.clone();
+        tokio::task::spawn_blocking(move || write_zip(&posts, &media_dir))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))?
+    }
+}
+
+fn write_zip(posts: &[Post], media_dir: &Path) -> Result<Vec<u8>, String> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let zip_error = |e: zip::result::ZipError| format!("Zip error: {}", e);
+    let io_error = |e: std::io::Error| format!("I/O error: {}", e);
+
+    let mut media = BTreeSet::new();
+    for post in posts {
+        let document = render_document(&front_matter(post), &post.body)?;
+        zip.start_file(format!("{}/{}.md", POSTS_DIR, post.slug), options)
+            .map_err(zip_error)?;
+        zip.write_all(document.as_bytes()).map_err(io_error)?;
+        media.extend(referenced_media(&post.body));
+    }
+
+    for path in media {
+        // Links to media that no longer exists are left dangling rather than failing the export
+        let Ok(contents) = fs::read(media_dir.join(&path)) else {
+            continue;
+        };
+        zip.start_file(format!("{}/{}", MEDIA_DIR, path), options)
+            .map_err(zip_error)?;
+        zip.write_all(&contents).map_err(io_error)?;
+    }
+
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+fn front_matter(post: &Post) -> FrontMatter {
+    FrontMatter {
+        title: Some(post.title.clone()),
+        slug: Some(post.slug.clone()),
+        date: post.published_at.map(format_date),
+        created: Some(format_date(post.created_at)),
+        updated: Some(format_date(post.updated_at)),
+        tags: post.tags.clone(),
+        draft: post.published_at.is_none(),
+    }
+}
+
+/// Paths below `/media/` referenced from Markdown links, images or inline HTML
+fn referenced_media(body: &str) -> Vec<String> {
+    body.match_indices(MEDIA_URL_PREFIX)
+        .filter_map(|(start, _)| {
+            let rest = &body[start + MEDIA_URL_PREFIX.len()..];
+            let end = rest
+                .find(|c: char| c.is_whitespace() || "\"'()<>[]?#".contains(c))
+                .unwrap_or(rest.len());
+            let path = &rest[..end];
+            is_safe_relative(path).then(|| path.to_string())
+        })
+        .collect()
+}
+
+/// Reject paths that could escape the media directory
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
@@ -0,0 +1,298 @@
+use crate::application::{Actor, ImportOutcome, PostService, ServiceError, SlugLookup};
+use crate::domain::{Post, ValidationErrors};
+use crate::infrastructure::interchange::front_matter::{parse_date, parse_document};
+use chrono::Utc;
+use serde::Serialize;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zip::ZipArchive;
+
+/// Largest Markdown file accepted, front matter included
+pub const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Most entries a zip archive may list, directories and skipped files included
+pub const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+
+/// Most bytes extracted from one zip archive, so a small archive cannot decompress into gigabytes
+pub const MAX_EXTRACTED_BYTES: u64 = 128 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Created,
+    Updated,
+    Unchanged,
+    Failed,
+}
+
+/// Result of importing a single file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub file: String,
+    pub status: ImportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violations: Option<ValidationErrors>,
+}
+
+impl FileReport {
+    fn failed(file: &str, slug: Option<String>, error: impl Into<String>) -> Self {
+        Self {
+            file: file.to_string(),
+            status: ImportStatus::Failed,
+            id: None,
+            slug,
+            error: Some(error.into()),
+            violations: None,
+        }
+    }
+}
+
+/// Per-file results of an import batch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub files: Vec<FileReport>,
+}
+
+impl ImportReport {
+    pub fn push(&mut self, file: FileReport) {
+        match file.status {
+            ImportStatus::Created => self.created += 1,
+            ImportStatus::Updated => self.updated += 1,
+            ImportStatus::Unchanged => self.unchanged += 1,
+            ImportStatus::Failed => self.failed += 1,
+        }
+        self.files.push(file);
+    }
+
+    pub fn extend(&mut self, other: ImportReport) {
+        for file in other.files {
+            self.push(file);
+        }
+    }
+}
+
+/// Imports Markdown files with front matter, creating or updating posts by slug
+/// A failing file is reported and skipped; the rest of the batch still runs
+pub struct MarkdownImporter {
+    post_service: Arc<PostService>,
+}
+
+impl MarkdownImporter {
+    pub fn new(post_service: Arc<PostService>) -> Self {
+        Self { post_service }
+    }
+
+    /// Import a single `.md` file, every Markdown file below a directory, or a `.zip` archive
+    pub async fn import_path(&self, actor: &Actor, path: &Path) -> Result<ImportReport, String> {
+        if path.is_dir() {
+            let mut files = Vec::new();
+            collect_markdown_files(path, &mut files).map_err(|e| format!("I/O error: {}", e))?;
+
+            let mut report = ImportReport::default();
+            for file in files {
+                let name = file.display().to_string();
+                report.push(match read_limited(&file) {
+                    Ok(contents) => self.import_file(actor, &name, &contents).await,
+                    Err(error) => FileReport::failed(&name, None, error),
+                });
+            }
+            return Ok(report);
+        }
+
+        let contents = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if has_extension(path, &["zip"]) {
+            return self.import_zip(actor, contents).await;
+        }
+
+        let mut report = ImportReport::default();
+        report.push(self.import_file(actor, &path.display().to_string(), &contents).await);
+        Ok(report)
+    }
+
+    /// Import every Markdown file of a zip archive, in name order
+    pub async fn import_zip(&self, actor: &Actor, archive: Vec<u8>) -> Result<ImportReport, String> {
+        let entries = tokio::task::spawn_blocking(move || read_zip_entries(archive))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??;
+
+        let mut report = ImportReport::default();
+        for (name, contents) in entries {
+            report.push(match contents {
+                Ok(contents) => self.import_file(actor, &name, &contents).await,
+                Err(error) => FileReport::failed(&name, None, error),
+            });
+        }
+        Ok(report)
+    }
+
+    /// Import one Markdown document; `name` is used in the report and as the fallback slug
+    pub async fn import_file(&self, actor: &Actor, name: &str, contents: &[u8]) -> FileReport {
+        let Ok(text) = std::str::from_utf8(contents) else {
+            return FileReport::failed(name, None, "file is not valid UTF-8");
+        };
+        let document = match parse_document(text) {
+            Ok(document) => document,
+            Err(error) => return FileReport::failed(name, None, error),
+        };
+        let front_matter = document.front_matter;
+
+        let slug = front_matter.slug.unwrap_or_else(|| file_stem(name));
+        let parse = |value: &Option<String>| value.as_deref().map(parse_date).transpose();
+        let (date, created, updated) = match (
+            parse(&front_matter.date),
+            parse(&front_matter.created),
+            parse(&front_matter.updated),
+        ) {
+            (Ok(date), Ok(created), Ok(updated)) => (date, created, updated),
+            (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => {
+                return FileReport::failed(name, Some(slug), error);
+            }
+        };
+
+        // Undated posts keep their original publication date when re-imported
+        let published_at = if front_matter.draft {
+            None
+        } else {
+            match date {
+                Some(date) => Some(date),
+                None => match self.post_service.get_post_by_slug(Some(actor), &slug).await {
+                    Ok(Some(SlugLookup::Current(existing))) => existing.published_at.or(Some(Utc::now())),
+                    Ok(_) => Some(Utc::now()),
+                    Err(error) => return FileReport::failed(name, Some(slug), error.to_string()),
+                },
+            }
+        };
+
+        let mut post = Post::new(front_matter.title.unwrap_or_default(), slug.clone(), document.body);
+        post.set_tags(front_matter.tags);
+        post.published_at = published_at;
+        if let Some(created) = created.or(date) {
+            post.created_at = created;
+            post.updated_at = created;
+        }
+        if let Some(updated) = updated {
+            post.updated_at = updated;
+        }
+
+        let (status, post) = match self.post_service.import_post(actor, post).await {
+            Ok(ImportOutcome::Created(post)) => (ImportStatus::Created, post),
+            Ok(ImportOutcome::Updated(post)) => (ImportStatus::Updated, post),
+            Ok(ImportOutcome::Unchanged(post)) => (ImportStatus::Unchanged, post),
+            Err(ServiceError::Validation(errors)) => {
+                return FileReport {
+                    violations: Some(errors),
+                    ..FileReport::failed(name, Some(slug), "validation failed")
+                };
+            }
+            Err(error) => return FileReport::failed(name, Some(slug), error.to_string()),
+        };
+
+        FileReport {
+            file: name.to_string(),
+            status,
+            id: post.id,
+            slug: Some(post.slug),
+            error: None,
+            violations: None,
+        }
+    }
+}
+
+type ZipEntry = (String, Result<Vec<u8>, String>);
+
+fn read_zip_entries(archive: Vec<u8>) -> Result<Vec<ZipEntry>, String> {
+    let mut zip = ZipArchive::new(Cursor::new(archive)).map_err(|e| format!("invalid zip archive: {}", e))?;
+    if zip.len() > MAX_ARCHIVE_ENTRIES {
+        return Err(format!("zip archive has more than {} entries", MAX_ARCHIVE_ENTRIES));
+    }
+
+    let mut extracted = 0;
+    let mut entries = Vec::new();
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index).map_err(|e| format!("invalid zip archive: {}", e))?;
+        let name = match entry.name() {
+            Ok(name) => name.into_owned(),
+            Err(e) => return Err(format!("invalid zip archive: {}", e)),
+        };
+        if entry.is_dir() || is_hidden(&name) || !has_extension(Path::new(&name), &["md", "markdown"]) {
+            continue;
+        }
+
+        let mut contents = Vec::new();
+        let read = (&mut entry).take(MAX_FILE_BYTES + 1).read_to_end(&mut contents);
+        // Counted from the bytes read rather than the declared sizes, which the archive can misstate
+        extracted += contents.len() as u64;
+        if extracted > MAX_EXTRACTED_BYTES {
+            return Err(format!("zip archive extracts to more than {} MiB", MAX_EXTRACTED_BYTES / (1024 * 1024)));
+        }
+        let result = match read {
+            Ok(_) if contents.len() as u64 > MAX_FILE_BYTES => Err(too_large()),
+            Ok(_) => Ok(contents),
+            Err(e) => Err(format!("unreadable entry: {}", e)),
+        };
+        entries.push((name, result));
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(entries)
+}
+
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        if is_hidden(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            collect_markdown_files(&path, files)?;
+        } else if has_extension(&path, &["md", "markdown"]) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn read_limited(path: &Path) -> Result<Vec<u8>, String> {
+    let size = fs::metadata(path).map_err(|e| e.to_string())?.len();
+    if size > MAX_FILE_BYTES {
+        return Err(too_large());
+    }
+    fs::read(path).map_err(|e| e.to_string())
+}
+
+fn too_large() -> String {
+    format!("file is larger than {} bytes", MAX_FILE_BYTES)
+}
+
+/// Dot files and macOS resource forks (`__MACOSX/`, `._name`) are never posts
+fn is_hidden(name: &str) -> bool {
+    name.split('/').any(|part| part.starts_with('.') || part == "__MACOSX")
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+fn file_stem(name: &str) -> String {
+    Path::new(name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
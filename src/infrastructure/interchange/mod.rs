@@ -0,0 +1,15 @@
+pub mod front_matter;
+pub mod markdown_exporter;
+pub mod markdown_importer;
+pub mod ndjson;
+pub mod wxr;
+pub mod wxr_importer;
+
+pub use front_matter::{
+    format_date, parse_date, parse_document, render_document, FrontMatter, MarkdownDocument,
+};
+pub use markdown_exporter::MarkdownExporter;
+pub use markdown_importer::{FileReport, ImportReport, ImportStatus, MarkdownImporter};
+pub use ndjson::{LineError, NdjsonExporter, NdjsonImporter, NdjsonReport, PostRecord};
+pub use wxr::{WxrItem, WxrReader, WxrTerm};
+pub use wxr_importer::{WxrImporter, WxrItemReport, WxrItemStatus, WxrReport};
@@ -0,0 +1,336 @@
+use crate::application::{Actor, PostService, ServiceError};
+use crate::domain::{Post, PostImport, UpsertOutcome, ValidationErrors};
+use actix_web::web::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt, stream};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// Posts read from the database per export query
+pub const EXPORT_BATCH_SIZE: i64 = 500;
+/// Lines written per import transaction
+pub const IMPORT_BATCH_SIZE: usize = 500;
+/// Longest line accepted by the importer, newline excluded
+pub const MAX_LINE_BYTES: usize = 2 * 1024 * 1024;
+
+/// One post per line of an NDJSON backup
+/// Every stored field is kept, so importing an export recreates the same posts; a line updating an
+/// existing post leaves the dates it omits untouched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostRecord {
+    /// Kept on import when no other post holds it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub title: String,
+    pub slug: String,
+    pub body: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `null` for drafts; when absent, new posts are drafts and existing posts keep their date
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<Option<DateTime<Utc>>>,
+    /// Defaults to the time of the import for new posts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Defaults to `created_at` for new posts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Defaults to the importing user for new posts; never changed on existing ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_id: Option<i32>,
+}
+
+impl From<Post> for PostRecord {
+    fn from(post: Post) -> Self {
+        Self {
+            id: post.id,
+            title: post.title,
+            slug: post.slug,
+            body: post.body,
+            tags: post.tags,
+            published_at: Some(post.published_at),
+            created_at: Some(post.created_at),
+            updated_at: Some(post.updated_at),
+            author_id: post.author_id,
+        }
+    }
+}
+
+impl From<PostRecord> for PostImport {
+    fn from(record: PostRecord) -> Self {
+        let mut post = Post::new(record.title, record.slug, record.body);
+        post.id = record.id;
+        post.set_tags(record.tags);
+        post.published_at = record.published_at.flatten();
+        post.author_id = record.author_id;
+        if let Some(created_at) = record.created_at {
+            post.created_at = created_at;
+            post.updated_at = created_at;
+        }
+        if let Some(updated_at) = record.updated_at {
+            post.updated_at = updated_at;
+        }
+        PostImport {
+            post,
+            published_at: record.published_at,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+/// Distinguish an explicit `null` (`Some(None)`) from an absent member (`None`)
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// A line the importer could not write
+#[derive(Debug, Clone, Serialize)]
+pub struct LineError {
+    /// 1-based line number in the request body
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violations: Option<ValidationErrors>,
+}
+
+/// Summary of an NDJSON import; blank lines are ignored and not counted
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NdjsonReport {
+    pub inserted: usize,
+    pub updated: usize,
+    pub failed: usize,
+    pub errors: Vec<LineError>,
+}
+
+impl NdjsonReport {
+    fn fail(&mut self, line: usize, slug: Option<String>, error: ServiceError) {
+        let (error, violations) = match error {
+            ServiceError::Validation(errors) => ("validation failed".to_string(), Some(errors)),
+            error => (error.to_string(), None),
+        };
+        self.failed += 1;
+        self.errors.push(LineError { line, slug, error, violations });
+    }
+}
+
+/// Streams every post, drafts included, as newline-delimited JSON in ID order
+/// Posts are read in keyset-paginated batches, so the table is never held in memory
+pub struct NdjsonExporter {
+    post_service: Arc<PostService>,
+}
+
+impl NdjsonExporter {
+    pub fn new(post_service: Arc<PostService>) -> Self {
+        Self { post_service }
+    }
+
+    /// One chunk per batch; an error ends the stream, truncating the response
+    pub fn export(&self) -> impl Stream<Item = Result<Bytes, String>> + use<> {
+        let post_service = Arc::clone(&self.post_service);
+
+        stream::unfold(Some(0), move |after_id| {
+            let post_service = Arc::clone(&post_service);
+            async move {
+                let batch = match post_service.get_posts_after(after_id?, EXPORT_BATCH_SIZE).await {
+                    Ok(batch) if batch.is_empty() => return None,
+                    Ok(batch) => batch,
+                    Err(error) => return Some((Err(error.to_string()), None)),
+                };
+                let last_id = batch.last().and_then(|post| post.id);
+
+                let mut chunk = Vec::new();
+                for post in batch {
+                    if let Err(e) = serde_json::to_writer(&mut chunk, &PostRecord::from(post)) {
+                        return Some((Err(format!("JSON error: {}", e)), None));
+                    }
+                    chunk.push(b'\n');
+                }
+                Some((Ok(Bytes::from(chunk)), last_id))
+            }
+        })
+    }
+}
+
+/// Imports an NDJSON stream, creating or updating posts by slug
+/// Lines are written in batches of [`IMPORT_BATCH_SIZE`], one transaction each; a failing line is
+/// reported and skipped without rolling back the rest of its batch
+pub struct NdjsonImporter {
+    post_service: Arc<PostService>,
+}
+
+impl NdjsonImporter {
+    pub fn new(post_service: Arc<PostService>) -> Self {
+        Self { post_service }
+    }
+
+    /// Read `body` to the end; only an unreadable body aborts the import, after earlier batches were committed
+    pub async fn import<S, E>(&self, actor: &Actor, mut body: S) -> Result<NdjsonReport, String>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: Display,
+    {
+        let mut report = NdjsonReport::default();
+        let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+        let mut buffer = Vec::new();
+        let mut line = 0;
+        // Set while skipping the rest of a line longer than `MAX_LINE_BYTES`
+        let mut oversized = false;
+
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|e| format!("failed to read body: {}", e))?;
+            let mut rest = &chunk[..];
+
+            while let Some(end) = rest.iter().position(|&byte| byte == b'\n') {
+                line += 1;
+                if oversized {
+                    oversized = false;
+                    report.fail(line, None, line_too_long());
+                } else {
+                    buffer.extend_from_slice(&rest[..end]);
+                    self.push_line(actor, line, &buffer, &mut batch, &mut report).await;
+                }
+                buffer.clear();
+                rest = &rest[end + 1..];
+            }
+
+            if !oversized {
+                buffer.extend_from_slice(rest);
+                if buffer.len() > MAX_LINE_BYTES {
+                    oversized = true;
+                    buffer.clear();
+                }
+            }
+        }
+
+        // The last line does not need a trailing newline
+        if oversized {
+            report.fail(line + 1, None, line_too_long());
+        } else if !buffer.is_empty() {
+            self.push_line(actor, line + 1, &buffer, &mut batch, &mut report).await;
+        }
+        self.flush(actor, &mut batch, &mut report).await;
+        Ok(report)
+    }
+
+    async fn push_line(
+        &self,
+        actor: &Actor,
+        line: usize,
+        bytes: &[u8],
+        batch: &mut Vec<(usize, PostImport)>,
+        report: &mut NdjsonReport,
+    ) {
+        if bytes.trim_ascii().is_empty() {
+            return;
+        }
+        match serde_json::from_slice::<PostRecord>(bytes) {
+            Ok(record) => batch.push((line, PostImport::from(record))),
+            Err(e) => report.fail(line, None, ServiceError::InvalidInput(format!("invalid JSON: {}", e))),
+        }
+        if batch.len() >= IMPORT_BATCH_SIZE {
+            self.flush(actor, batch, report).await;
+        }
+    }
+
+    async fn flush(&self, actor: &Actor, batch: &mut Vec<(usize, PostImport)>, report: &mut NdjsonReport) {
+        if batch.is_empty() {
+            return;
+        }
+        let (lines, posts): (Vec<_>, Vec<_>) = std::mem::take(batch).into_iter().unzip();
+        let slugs: Vec<String> = posts.iter().map(|import| import.post.slug.clone()).collect();
+
+        let results = match self.post_service.import_posts(actor, posts).await {
+            Ok(results) => results,
+            Err(error) => {
+                for (line, slug) in lines.into_iter().zip(slugs) {
+                    report.fail(line, Some(slug), ServiceError::Repository(error.to_string()));
+                }
+                return;
+            }
+        };
+
+        for ((line, slug), result) in lines.into_iter().zip(slugs).zip(results) {
+            match result {
+                Ok(UpsertOutcome::Inserted(_)) => report.inserted += 1,
+                Ok(UpsertOutcome::Updated(_)) => report.updated += 1,
+                Err(error) => report.fail(line, Some(slug), error),
+            }
+        }
+    }
+}
+
+fn line_too_long() -> ServiceError {
+    ServiceError::InvalidInput(format!("line is longer than {} bytes", MAX_LINE_BYTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{PostRepository, Role};
+    use crate::infrastructure::database::memory_pool;
+    use crate::infrastructure::observability::PostMetrics;
+    use crate::infrastructure::persistence::SqlitePostRepository;
+
+    fn importer() -> (NdjsonImporter, SqlitePostRepository) {
+        let pool = Arc::new(memory_pool());
+        let repository = Arc::new(SqlitePostRepository::new(Arc::clone(&pool)));
+        let service = PostService::new(repository, Arc::new(PostMetrics));
+        (NdjsonImporter::new(Arc::new(service)), SqlitePostRepository::new(pool))
+    }
+
+    async fn import(importer: &NdjsonImporter, lines: &str) -> NdjsonReport {
+        let editor = Actor { user_id: None, role: Role::Editor };
+        let body = stream::iter([Ok::<_, String>(Bytes::from(lines.to_string()))]);
+        importer.import(&editor, body).await.expect("readable body")
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().expect("valid timestamp")
+    }
+
+    #[tokio::test]
+    async fn updates_keep_the_dates_the_line_leaves_out() {
+        let (importer, repository) = importer();
+        let report = import(
+            &importer,
+            r#"{"title":"Hello","slug":"hello-world","body":"aa","published_at":"2024-01-01T00:00:00Z","created_at":"2023-12-31T00:00:00Z","updated_at":"2024-01-02T00:00:00Z"}"#,
+        )
+        .await;
+        assert_eq!(report.inserted, 1);
+
+        let report = import(&importer, r#"{"title":"Hello2","slug":"hello-world","body":"bb"}"#).await;
+        assert_eq!(report.updated, 1);
+
+        let post = repository.find_by_slug("hello-world").await.unwrap().expect("post exists");
+        assert_eq!(post.title, "Hello2");
+        assert_eq!(post.body, "bb");
+        assert_eq!(post.published_at, Some(at("2024-01-01T00:00:00Z")));
+        assert_eq!(post.created_at, at("2023-12-31T00:00:00Z"));
+        assert_eq!(post.updated_at, at("2024-01-02T00:00:00Z"));
+    }
+
+    #[tokio::test]
+    async fn updates_write_the_dates_the_line_contains() {
+        let (importer, repository) = importer();
+        import(&importer, r#"{"title":"Hello","slug":"hello-world","body":"aa","published_at":"2024-01-01T00:00:00Z"}"#).await;
+
+        let report = import(
+            &importer,
+            r#"{"title":"Hello","slug":"hello-world","body":"aa","published_at":null,"updated_at":"2024-02-01T00:00:00Z"}"#,
+        )
+        .await;
+        assert_eq!(report.updated, 1);
+
+        let post = repository.find_by_slug("hello-world").await.unwrap().expect("post exists");
+        assert_eq!(post.published_at, None);
+        assert_eq!(post.updated_at, at("2024-02-01T00:00:00Z"));
+    }
+}
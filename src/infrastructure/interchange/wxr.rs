@@ -0,0 +1,193 @@
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+use std::io::BufRead;
+
+/// One `<item>` of a WordPress eXtended RSS export
+#[derive(Debug, Clone, Default)]
+pub struct WxrItem {
+    pub post_id: Option<i64>,
+    pub title: String,
+    /// `wp:post_name`; empty for drafts that never got a permalink
+    pub post_name: String,
+    /// `content:encoded`, WordPress HTML
+    pub content: String,
+    /// `wp:status`: `publish`, `future`, `draft`, `pending`, `private` or `trash`
+    pub status: String,
+    /// `wp:post_type`: `post`, `page`, `attachment`, …
+    pub post_type: String,
+    /// `wp:post_date_gmt`, `0000-00-00 00:00:00` while unpublished
+    pub post_date_gmt: String,
+    /// `wp:post_date`, in the blog's local time zone
+    pub post_date: String,
+    pub post_modified_gmt: String,
+    pub categories: Vec<WxrTerm>,
+    pub approved_comments: usize,
+}
+
+/// A category or tag attached to an item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WxrTerm {
+    /// `category` or `post_tag`
+    pub domain: String,
+    pub nicename: String,
+    pub name: String,
+}
+
+/// Pull parser yielding WXR items one at a time, so exports of any size stay out of memory
+pub struct WxrReader<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+}
+
+/// Element currently capturing text inside an item
+enum Field {
+    Title,
+    PostId,
+    PostName,
+    Content,
+    Status,
+    PostType,
+    PostDateGmt,
+    PostDate,
+    PostModifiedGmt,
+    Term(WxrTerm),
+    CommentApproved,
+}
+
+impl<R: BufRead> WxrReader<R> {
+    pub fn new(source: R) -> Self {
+        Self {
+            reader: Reader::from_reader(source),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Next item of the export, or `None` at the end of the document
+    pub fn next_item(&mut self) -> Result<Option<WxrItem>, String> {
+        loop {
+            match self.read_event()? {
+                Event::Start(start) if start.name().as_ref() == "item" => return self.read_item().map(Some),
+                Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    fn read_item(&mut self) -> Result<WxrItem, String> {
+        let mut item = WxrItem::default();
+        // Depth below `<item>`; only direct children are item fields, except inside `<wp:comment>`
+        let mut depth = 0usize;
+        let mut in_comment = false;
+        let mut field: Option<Field> = None;
+        let mut text = String::new();
+
+        loop {
+            match self.read_event()? {
+                Event::Start(start) => {
+                    depth += 1;
+                    let name = start.name().as_ref().to_string();
+                    if depth == 1 && name == "wp:comment" {
+                        in_comment = true;
+                    }
+                    field = match (depth, in_comment) {
+                        (1, false) => item_field(&name, &start)?,
+                        (2, true) if name == "wp:comment_approved" => Some(Field::CommentApproved),
+                        _ => None,
+                    };
+                    text.clear();
+                }
+                Event::End(end) => {
+                    if depth == 0 && end.name().as_ref() == "item" {
+                        return Ok(item);
+                    }
+                    if let Some(done) = field.take() {
+                        store(&mut item, done, std::mem::take(&mut text));
+                    }
+                    if depth == 1 && end.name().as_ref() == "wp:comment" {
+                        in_comment = false;
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                Event::Text(content) if field.is_some() => text.push_str(&content.xml10_content()),
+                Event::CData(content) if field.is_some() => text.push_str(&content.xml10_content()),
+                Event::GeneralRef(reference) if field.is_some() => text.push_str(&resolve(&reference)?),
+                Event::Eof => return Err("unexpected end of file inside <item>".to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    fn read_event(&mut self) -> Result<Event<'static>, String> {
+        self.buf.clear();
+        self.reader
+            .read_event_into(&mut self.buf)
+            .map(|event| event.into_owned())
+            .map_err(|e| format!("invalid WXR at byte {}: {}", self.reader.buffer_position(), e))
+    }
+}
+
+fn item_field(name: &str, start: &BytesStart) -> Result<Option<Field>, String> {
+    Ok(match name {
+        "title" => Some(Field::Title),
+        "wp:post_id" => Some(Field::PostId),
+        "wp:post_name" => Some(Field::PostName),
+        "content:encoded" => Some(Field::Content),
+        "wp:status" => Some(Field::Status),
+        "wp:post_type" => Some(Field::PostType),
+        "wp:post_date_gmt" => Some(Field::PostDateGmt),
+        "wp:post_date" => Some(Field::PostDate),
+        "wp:post_modified_gmt" => Some(Field::PostModifiedGmt),
+        "category" => Some(Field::Term(WxrTerm {
+            domain: attribute(start, "domain")?,
+            nicename: attribute(start, "nicename")?,
+            name: String::new(),
+        })),
+        _ => None,
+    })
+}
+
+fn store(item: &mut WxrItem, field: Field, text: String) {
+    match field {
+        Field::Title => item.title = text,
+        Field::PostId => item.post_id = text.trim().parse().ok(),
+        Field::PostName => item.post_name = text.trim().to_string(),
+        Field::Content => item.content = text,
+        Field::Status => item.status = text.trim().to_string(),
+        Field::PostType => item.post_type = text.trim().to_string(),
+        Field::PostDateGmt => item.post_date_gmt = text.trim().to_string(),
+        Field::PostDate => item.post_date = text.trim().to_string(),
+        Field::PostModifiedGmt => item.post_modified_gmt = text.trim().to_string(),
+        Field::Term(term) => item.categories.push(WxrTerm { name: text, ..term }),
+        Field::CommentApproved => {
+            if text.trim() == "1" {
+                item.approved_comments += 1;
+            }
+        }
+    }
+}
+
+fn attribute(start: &BytesStart, name: &str) -> Result<String, String> {
+    match start.try_get_attribute(name) {
+        Ok(Some(attribute)) => attribute
+            .normalized_value(XmlVersion::Implicit1_0)
+            .map(|value| value.into_owned())
+            .map_err(|e| format!("invalid attribute `{}`: {}", name, e)),
+        Ok(None) => Ok(String::new()),
+        Err(e) => Err(format!("invalid attribute `{}`: {}", name, e)),
+    }
+}
+
+/// Expand `&amp;`-style and numeric character references found in element text
+fn resolve(reference: &BytesRef) -> Result<String, String> {
+    if reference.is_char_ref() {
+        return match reference.resolve_char_ref() {
+            Ok(Some(c)) => Ok(c.to_string()),
+            _ => Err(format!("invalid character reference `&{};`", reference.xml10_content())),
+        };
+    }
+    let name = reference.xml10_content();
+    unescape(&format!("&{};", name))
+        .map(|value| value.into_owned())
+        .map_err(|_| format!("unknown entity `&{};`", name))
+}
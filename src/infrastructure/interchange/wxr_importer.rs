@@ -0,0 +1,284 @@
+use crate::application::{Actor, ImportOutcome, PostService, ServiceError};
+use crate::domain::validation::rules::{SLUG_MAX_LENGTH, TAG_MAX_LENGTH};
+use crate::domain::{Post, ValidationErrors};
+use crate::infrastructure::interchange::wxr::{WxrItem, WxrReader};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Items parsed ahead of the import; bounds memory while the database catches up
+const PARSE_AHEAD: usize = 16;
+
+/// WordPress' default category, attached to every uncategorised post
+const DEFAULT_CATEGORY: &str = "uncategorized";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WxrItemStatus {
+    Created,
+    Updated,
+    Unchanged,
+    /// Not a blog post (pages, attachments, trashed items, …)
+    Skipped,
+    Failed,
+}
+
+/// What happened, or would happen during a dry run, to one WXR item
+#[derive(Debug, Clone, Serialize)]
+pub struct WxrItemReport {
+    /// 1-based position of the item in the export
+    pub item: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wordpress_id: Option<i64>,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    pub status: WxrItemStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Approved comments left behind because the blog has no comment storage
+    #[serde(skip_serializing_if = "is_zero")]
+    pub comments_skipped: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violations: Option<ValidationErrors>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WxrReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub comments_skipped: usize,
+    pub items: Vec<WxrItemReport>,
+}
+
+impl WxrReport {
+    fn push(&mut self, item: WxrItemReport) {
+        match item.status {
+            WxrItemStatus::Created => self.created += 1,
+            WxrItemStatus::Updated => self.updated += 1,
+            WxrItemStatus::Unchanged => self.unchanged += 1,
+            WxrItemStatus::Skipped => self.skipped += 1,
+            WxrItemStatus::Failed => self.failed += 1,
+        }
+        self.comments_skipped += item.comments_skipped;
+        self.items.push(item);
+    }
+}
+
+/// Imports a WordPress eXtended RSS export, creating or updating posts by slug
+/// HTML content is converted to Markdown; categories and tags both become post tags
+pub struct WxrImporter {
+    post_service: Arc<PostService>,
+}
+
+impl WxrImporter {
+    pub fn new(post_service: Arc<PostService>) -> Self {
+        Self { post_service }
+    }
+
+    /// Stream the export at `path`; with `dry_run` every item is checked but nothing is written
+    pub async fn import(&self, actor: &Actor, path: &Path, dry_run: bool) -> Result<WxrReport, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let (sender, mut receiver) = mpsc::channel(PARSE_AHEAD);
+
+        let parser = tokio::task::spawn_blocking(move || {
+            let mut reader = WxrReader::new(BufReader::new(file));
+            loop {
+                let next = reader.next_item();
+                let done = !matches!(next, Ok(Some(_)));
+                // Stop parsing once the importer has given up
+                if sender.blocking_send(next).is_err() || done {
+                    break;
+                }
+            }
+        });
+
+        let mut report = WxrReport { dry_run, ..WxrReport::default() };
+        while let Some(next) = receiver.recv().await {
+            let Some(item) = next? else {
+                break;
+            };
+            let index = report.items.len() + 1;
+            report.push(self.import_item(actor, index, item, dry_run).await);
+        }
+
+        parser.await.map_err(|e| format!("Task join error: {}", e))?;
+        Ok(report)
+    }
+
+    async fn import_item(&self, actor: &Actor, index: usize, item: WxrItem, dry_run: bool) -> WxrItemReport {
+        let mut report = WxrItemReport {
+            item: index,
+            wordpress_id: item.post_id,
+            title: item.title.trim().to_string(),
+            slug: None,
+            status: WxrItemStatus::Skipped,
+            tags: Vec::new(),
+            comments_skipped: item.approved_comments,
+            reason: None,
+            violations: None,
+        };
+
+        let post = match to_post(&item) {
+            Ok(post) => post,
+            Err(reason) => {
+                report.reason = Some(reason);
+                return report;
+            }
+        };
+        report.slug = Some(post.slug.clone());
+        report.tags = post.tags.clone();
+
+        let outcome = if dry_run {
+            self.post_service.preview_import(actor, post).await
+        } else {
+            self.post_service.import_post(actor, post).await
+        };
+        match outcome {
+            Ok(ImportOutcome::Created(_)) => report.status = WxrItemStatus::Created,
+            Ok(ImportOutcome::Updated(_)) => report.status = WxrItemStatus::Updated,
+            Ok(ImportOutcome::Unchanged(_)) => report.status = WxrItemStatus::Unchanged,
+            Err(ServiceError::Validation(errors)) => {
+                report.status = WxrItemStatus::Failed;
+                report.reason = Some("validation failed".to_string());
+                report.violations = Some(errors);
+            }
+            Err(error) => {
+                report.status = WxrItemStatus::Failed;
+                report.reason = Some(error.to_string());
+            }
+        }
+        report
+    }
+}
+
+/// Map a WXR item to a post, or explain why it is not imported
+fn to_post(item: &WxrItem) -> Result<Post, String> {
+    if item.post_type != "post" {
+        return Err(format!("post type `{}` is not imported", item.post_type));
+    }
+
+    let published = match item.status.as_str() {
+        "publish" | "future" => true,
+        "draft" | "pending" | "private" => false,
+        other => return Err(format!("status `{}` is not imported", other)),
+    };
+
+    let date = wordpress_date(&item.post_date_gmt).or_else(|| wordpress_date(&item.post_date));
+    let modified = wordpress_date(&item.post_modified_gmt);
+
+    let mut slug = slugify(&percent_decode(&item.post_name), SLUG_MAX_LENGTH);
+    if slug.is_empty() {
+        slug = slugify(&item.title, SLUG_MAX_LENGTH);
+    }
+    if slug.is_empty()
+        && let Some(id) = item.post_id
+    {
+        slug = format!("post-{}", id);
+    }
+
+    let mut post = Post::new(item.title.trim().to_string(), slug, html_to_markdown(&item.content));
+    post.set_tags(
+        item.categories
+            .iter()
+            .filter(|term| term.domain == "category" || term.domain == "post_tag")
+            .map(|term| {
+                let nicename = percent_decode(&term.nicename);
+                let source = if nicename.is_empty() { &term.name } else { &nicename };
+                slugify(source, TAG_MAX_LENGTH)
+            })
+            .filter(|tag| !tag.is_empty() && tag != DEFAULT_CATEGORY)
+            .collect(),
+    );
+    if let Some(date) = date {
+        post.created_at = date;
+        post.updated_at = modified.unwrap_or(date);
+    }
+    post.published_at = if published { Some(date.unwrap_or_else(Utc::now)) } else { None };
+    Ok(post)
+}
+
+/// WordPress dates are `YYYY-MM-DD HH:MM:SS`, all zeros when unset
+fn wordpress_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|date| date.and_utc())
+}
+
+/// Convert WordPress HTML to Markdown, restoring the paragraphs WordPress only adds on display
+fn html_to_markdown(html: &str) -> String {
+    let html = if html.to_ascii_lowercase().contains("<p") { html.to_string() } else { autop(html) };
+    html2md::parse_html(&html).trim().to_string()
+}
+
+/// Wrap blank-line separated blocks in `<p>` and turn single newlines into `<br>`, like `wpautop`
+fn autop(text: &str) -> String {
+    const BLOCK_TAGS: &[&str] = &[
+        "<h", "<ul", "<ol", "<pre", "<blockquote", "<div", "<table", "<figure", "<hr", "<!--",
+    ];
+
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| {
+            let lower = block.to_ascii_lowercase();
+            if BLOCK_TAGS.iter().any(|tag| lower.starts_with(tag)) {
+                block.to_string()
+            } else {
+                format!("<p>{}</p>", block.replace('\n', "<br>\n"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lowercase ASCII letters and digits joined by single hyphens, at most `max` characters
+/// Other scripts are transliterated first, so `Café` becomes `cafe`
+fn slugify(value: &str, max: usize) -> String {
+    let mut slug = String::new();
+    for c in deunicode::deunicode(value).chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(max);
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Decode `%XX` escapes WordPress uses for non-ASCII permalinks
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
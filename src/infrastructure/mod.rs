@@ -1,8 +1,22 @@
+pub mod config;
 pub mod database;
+pub mod feeds;
+pub mod interchange;
+pub mod observability;
 pub mod persistence;
+pub mod rate_limit;
+pub mod rendering;
+pub mod security;
+pub mod seo;
+pub mod static_site;
 pub mod web;
 
 // Re-export specific items to avoid ambiguous glob re-exports
+pub use config::{CorsConfig, JwtConfig, OidcConfig, RateLimitConfig, SecurityHeadersConfig, SessionConfig, SiteConfig};
 pub use database::{DbPool, establish_connection_pool, run_with_connection};
-pub use persistence::{SqlitePostRepository, PostModel, NewPostModel};
-pub use web::{PostHandler, CreatePostRequest, UpdatePostRequest, PostResponse};
+pub use interchange::{MarkdownExporter, MarkdownImporter, NdjsonExporter, NdjsonImporter, WxrImporter};
+pub use security::{Argon2PasswordHasher, JwtCodec, OidcClient};
+pub use rate_limit::InMemoryRateLimitStore;
+pub use static_site::StaticSiteExporter;
+pub use persistence::{SqlitePostRepository, SqliteApiTokenRepository, SqliteUserRepository, SqliteSessionRepository, SqliteRefreshTokenRepository, SqliteUserIdentityRepository, SqliteRecoveryCodeRepository, SqliteTwoFactorPolicyRepository, PostModel, NewPostModel, PostChangesModel};
+pub use web::{PostHandler, FeedHandler, SitemapHandler, PageHandler, ImportHandler, ExportHandler, TokenHandler, AuthHandler, JwtHandler, HealthHandler, MetricsHandler, OidcHandler, TwoFactorHandler, CreatePostRequest, UpdatePostRequest, PatchPostRequest, PostResponse, BatchRequest, CreateTokenRequest, LoginRequest, RefreshTokenRequest, OidcLoginQuery, OidcCallbackQuery, TwoFactorCodeRequest, TwoFactorPolicy, ApiError, problem_details, require_auth, require_auth_for_writes};
@@ -0,0 +1,18 @@
+use crate::infrastructure::config::site_config::{env_or, env_var};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Send `tracing` events to standard output, as JSON lines unless `LOG_FORMAT=text`
+/// `RUST_LOG` picks the levels, `info` by default; spans log their timings when they close
+pub fn init_logging() {
+    let filter = EnvFilter::try_new(env_or("RUST_LOG", "info")).unwrap_or_else(|e| panic!("RUST_LOG: {}", e));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE);
+
+    match env_var("LOG_FORMAT").as_deref() {
+        None | Some("json") => builder.json().with_current_span(true).with_span_list(true).init(),
+        Some("text") => builder.init(),
+        Some(other) => panic!("LOG_FORMAT must be json or text, not `{}`", other),
+    }
+}
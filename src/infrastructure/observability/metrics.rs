@@ -0,0 +1,160 @@
+use crate::domain::{PostActivity, PostEvent};
+use diesel::r2d2::{event, HandleEvent, State};
+use prometheus::{
+    histogram_opts, opts, Encoder, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Buckets for database calls and pool waits, which are mostly well under the HTTP defaults
+const DATABASE_BUCKETS: &[f64] = &[0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Route label of requests that matched no route, so unknown paths cannot grow the label set
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Content type of [`Metrics::encode`]
+pub const METRICS_CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// The process-wide metrics
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+/// Prometheus metrics of the HTTP server, the database and post activity
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    db_operation_duration: HistogramVec,
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    db_pool_wait: Histogram,
+    db_pool_timeouts: IntCounter,
+    post_events: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let metrics = Self {
+            http_requests: IntCounterVec::new(
+                opts!("http_requests_total", "HTTP requests handled"),
+                &["method", "route", "status"],
+            )
+            .expect("valid metric"),
+            http_request_duration: HistogramVec::new(
+                histogram_opts!("http_request_duration_seconds", "Time to handle an HTTP request"),
+                &["method", "route", "status"],
+            )
+            .expect("valid metric"),
+            db_operation_duration: HistogramVec::new(
+                histogram_opts!(
+                    "db_operation_duration_seconds",
+                    "Time of a repository operation, waiting for a connection included",
+                    DATABASE_BUCKETS.to_vec()
+                ),
+                &["operation", "outcome"],
+            )
+            .expect("valid metric"),
+            db_pool_connections: IntGaugeVec::new(
+                opts!("db_pool_connections", "Open database connections by state"),
+                &["state"],
+            )
+            .expect("valid metric"),
+            db_pool_max_connections: IntGauge::new("db_pool_max_connections", "Size limit of the database pool")
+                .expect("valid metric"),
+            db_pool_wait: Histogram::with_opts(histogram_opts!(
+                "db_pool_wait_seconds",
+                "Time waited to check a connection out of the pool",
+                DATABASE_BUCKETS.to_vec()
+            ))
+            .expect("valid metric"),
+            db_pool_timeouts: IntCounter::new("db_pool_timeouts_total", "Connection checkouts that timed out")
+                .expect("valid metric"),
+            post_events: IntCounterVec::new(opts!("posts_total", "Posts created, published and deleted"), &["event"])
+                .expect("valid metric"),
+            registry,
+        };
+
+        let collectors: Vec<Box<dyn prometheus::core::Collector>> = vec![
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.http_request_duration.clone()),
+            Box::new(metrics.db_operation_duration.clone()),
+            Box::new(metrics.db_pool_connections.clone()),
+            Box::new(metrics.db_pool_max_connections.clone()),
+            Box::new(metrics.db_pool_wait.clone()),
+            Box::new(metrics.db_pool_timeouts.clone()),
+            Box::new(metrics.post_events.clone()),
+        ];
+        for collector in collectors {
+            metrics.registry.register(collector).expect("metric names are unique");
+        }
+        metrics
+    }
+
+    /// Count a handled request; `route` is the matched pattern, such as `/posts/{id}`
+    pub fn record_http_request(&self, method: &str, route: Option<&str>, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route.unwrap_or(UNMATCHED_ROUTE), status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration.with_label_values(&labels).observe(elapsed.as_secs_f64());
+    }
+
+    pub fn record_db_operation(&self, operation: &str, succeeded: bool, elapsed: Duration) {
+        let outcome = if succeeded { "ok" } else { "error" };
+        self.db_operation_duration
+            .with_label_values(&[operation, outcome])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Take the pool's current state; it is read at scrape time rather than tracked
+    pub fn observe_pool(&self, state: State, max_size: u32) {
+        let idle = i64::from(state.idle_connections);
+        self.db_pool_connections.with_label_values(&["idle"]).set(idle);
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set(i64::from(state.connections) - idle);
+        self.db_pool_max_connections.set(i64::from(max_size));
+    }
+
+    /// All metrics in the Prometheus text format
+    pub fn encode(&self) -> Result<String, String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| format!("Metrics encoding error: {}", e))?;
+        String::from_utf8(buffer).map_err(|e| format!("Metrics encoding error: {}", e))
+    }
+}
+
+/// Pool event handler recording connection wait times and timeouts
+#[derive(Debug)]
+pub struct PoolMetrics;
+
+impl HandleEvent for PoolMetrics {
+    fn handle_checkout(&self, event: event::CheckoutEvent) {
+        metrics().db_pool_wait.observe(event.duration().as_secs_f64());
+    }
+
+    fn handle_timeout(&self, event: event::TimeoutEvent) {
+        metrics().db_pool_wait.observe(event.timeout().as_secs_f64());
+        metrics().db_pool_timeouts.inc();
+    }
+}
+
+/// PostActivity adapter counting post events in `posts_total`
+pub struct PostMetrics;
+
+impl PostActivity for PostMetrics {
+    fn record(&self, event: PostEvent) {
+        let event = match event {
+            PostEvent::Created => "created",
+            PostEvent::Published => "published",
+            PostEvent::Deleted => "deleted",
+        };
+        metrics().post_events.with_label_values(&[event]).inc();
+    }
+}
@@ -0,0 +1,5 @@
+pub mod logging;
+pub mod metrics;
+
+pub use logging::init_logging;
+pub use metrics::{metrics, Metrics, PoolMetrics, PostMetrics, METRICS_CONTENT_TYPE};
@@ -1,5 +1,19 @@
 pub mod models;
+pub mod sqlite_api_token_repository;
 pub mod sqlite_post_repository;
+pub mod sqlite_recovery_code_repository;
+pub mod sqlite_refresh_token_repository;
+pub mod sqlite_session_repository;
+pub mod sqlite_two_factor_policy_repository;
+pub mod sqlite_user_identity_repository;
+pub mod sqlite_user_repository;
 
 pub use models::*;
+pub use sqlite_api_token_repository::SqliteApiTokenRepository;
 pub use sqlite_post_repository::SqlitePostRepository;
+pub use sqlite_recovery_code_repository::SqliteRecoveryCodeRepository;
+pub use sqlite_refresh_token_repository::SqliteRefreshTokenRepository;
+pub use sqlite_session_repository::SqliteSessionRepository;
+pub use sqlite_two_factor_policy_repository::SqliteTwoFactorPolicyRepository;
+pub use sqlite_user_identity_repository::SqliteUserIdentityRepository;
+pub use sqlite_user_repository::SqliteUserRepository;
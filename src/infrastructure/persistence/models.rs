@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -10,29 +11,408 @@ pub struct PostModel {
     pub title: String,
     pub slug: String,
     pub body: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub published_at: Option<NaiveDateTime>,
+    pub author_id: Option<i32>,
 }
 
 /// Model for inserting new posts
 #[derive(Insertable)]
 #[diesel(table_name = crate::schema::posts)]
 pub struct NewPostModel<'a> {
+    /// `None` lets SQLite assign the next ID
+    pub id: Option<i32>,
     pub title: &'a str,
     pub body: &'a str,
     pub slug: &'a str,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub published_at: Option<NaiveDateTime>,
+    pub author_id: Option<i32>,
 }
 
+/// Changeset for partial updates; `None` fields are left out of the `UPDATE`
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::posts)]
+pub struct PostChangesModel<'a> {
+    pub title: Option<&'a str>,
+    pub slug: Option<&'a str>,
+    pub body: Option<&'a str>,
+    /// `Some(None)` writes `NULL`
+    pub published_at: Option<Option<NaiveDateTime>>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Model for attaching a tag to a post
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::post_tags)]
+pub struct NewPostTagModel<'a> {
+    pub post_id: i32,
+    pub tag: &'a str,
+}
+
+/// Model for recording a slug a post no longer uses
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::slug_history)]
+pub struct NewSlugHistoryModel<'a> {
+    pub slug: &'a str,
+    pub post_id: i32,
+}
+
+/// Tags live in their own table, so the converted post starts without any
 impl From<PostModel> for crate::domain::Post {
     fn from(model: PostModel) -> Self {
-        crate::domain::Post::with_id(model.id, model.title, model.slug, model.body)
+        crate::domain::Post {
+            id: Some(model.id),
+            title: model.title,
+            slug: model.slug,
+            body: model.body,
+            tags: Vec::new(),
+            published_at: model.published_at.map(|at| at.and_utc()),
+            created_at: model.created_at.and_utc(),
+            updated_at: model.updated_at.and_utc(),
+            author_id: model.author_id,
+        }
     }
 }
 
 impl<'a> From<&'a crate::domain::Post> for NewPostModel<'a> {
     fn from(post: &'a crate::domain::Post) -> Self {
         NewPostModel {
+            id: None,
             title: &post.title,
             slug: &post.slug,
             body: &post.body,
+            created_at: post.created_at.naive_utc(),
+            updated_at: post.updated_at.naive_utc(),
+            published_at: post.published_at.map(|at| at.naive_utc()),
+            author_id: post.author_id,
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::PostPatch> for PostChangesModel<'a> {
+    fn from(patch: &'a crate::domain::PostPatch) -> Self {
+        PostChangesModel {
+            title: patch.title.as_deref(),
+            slug: patch.slug.as_deref(),
+            body: patch.body.as_deref(),
+            published_at: patch.published_at.map(|at| at.map(|at| at.naive_utc())),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+/// Database model for API tokens
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::api_tokens)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApiTokenModel {
+    pub id: i32,
+    pub name: String,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+/// Model for inserting new API tokens
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::api_tokens)]
+pub struct NewApiTokenModel<'a> {
+    pub name: &'a str,
+    pub token_hash: &'a str,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl From<ApiTokenModel> for crate::domain::ApiToken {
+    fn from(model: ApiTokenModel) -> Self {
+        crate::domain::ApiToken {
+            id: Some(model.id),
+            name: model.name,
+            token_hash: model.token_hash,
+            created_at: model.created_at.and_utc(),
+            expires_at: model.expires_at.map(|at| at.and_utc()),
+            last_used_at: model.last_used_at.map(|at| at.and_utc()),
+            revoked_at: model.revoked_at.map(|at| at.and_utc()),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::ApiToken> for NewApiTokenModel<'a> {
+    fn from(token: &'a crate::domain::ApiToken) -> Self {
+        NewApiTokenModel {
+            name: &token.name,
+            token_hash: &token.token_hash,
+            created_at: token.created_at.naive_utc(),
+            expires_at: token.expires_at.map(|at| at.naive_utc()),
+        }
+    }
+}
+
+/// Database model for user accounts
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::users)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UserModel {
+    pub id: i32,
+    pub email: String,
+    pub display_name: String,
+    pub password_hash: String,
+    pub failed_login_attempts: i32,
+    pub locked_until: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub role: String,
+    pub totp_secret: Option<String>,
+    pub totp_enabled_at: Option<NaiveDateTime>,
+    pub totp_last_step: Option<i64>,
+}
+
+/// Model for inserting new users
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::users)]
+pub struct NewUserModel<'a> {
+    pub email: &'a str,
+    pub display_name: &'a str,
+    pub role: &'a str,
+    pub password_hash: &'a str,
+    pub failed_login_attempts: i32,
+    pub locked_until: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<UserModel> for crate::domain::User {
+    fn from(model: UserModel) -> Self {
+        crate::domain::User {
+            id: Some(model.id),
+            email: model.email,
+            display_name: model.display_name,
+            // An unknown role grants the least privileges rather than failing every query
+            role: model.role.parse().unwrap_or(crate::domain::Role::Contributor),
+            password_hash: model.password_hash,
+            failed_login_attempts: model.failed_login_attempts,
+            locked_until: model.locked_until.map(|at| at.and_utc()),
+            totp_secret: model.totp_secret,
+            totp_enabled_at: model.totp_enabled_at.map(|at| at.and_utc()),
+            totp_last_step: model.totp_last_step,
+            created_at: model.created_at.and_utc(),
+            updated_at: model.updated_at.and_utc(),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::User> for NewUserModel<'a> {
+    fn from(user: &'a crate::domain::User) -> Self {
+        NewUserModel {
+            email: &user.email,
+            display_name: &user.display_name,
+            role: user.role.as_str(),
+            password_hash: &user.password_hash,
+            failed_login_attempts: user.failed_login_attempts,
+            locked_until: user.locked_until.map(|at| at.naive_utc()),
+            created_at: user.created_at.naive_utc(),
+            updated_at: user.updated_at.naive_utc(),
+        }
+    }
+}
+
+/// Database model for login sessions
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::sessions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SessionModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// Model for inserting new sessions
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::sessions)]
+pub struct NewSessionModel<'a> {
+    pub user_id: i32,
+    pub token_hash: &'a str,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub user_agent: Option<&'a str>,
+    pub ip_address: Option<&'a str>,
+}
+
+impl From<SessionModel> for crate::domain::Session {
+    fn from(model: SessionModel) -> Self {
+        crate::domain::Session {
+            id: Some(model.id),
+            user_id: model.user_id,
+            token_hash: model.token_hash,
+            created_at: model.created_at.and_utc(),
+            expires_at: model.expires_at.and_utc(),
+            last_seen_at: model.last_seen_at.and_utc(),
+            revoked_at: model.revoked_at.map(|at| at.and_utc()),
+            user_agent: model.user_agent,
+            ip_address: model.ip_address,
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::Session> for NewSessionModel<'a> {
+    fn from(session: &'a crate::domain::Session) -> Self {
+        NewSessionModel {
+            user_id: session.user_id,
+            token_hash: &session.token_hash,
+            created_at: session.created_at.naive_utc(),
+            expires_at: session.expires_at.naive_utc(),
+            last_seen_at: session.last_seen_at.naive_utc(),
+            user_agent: session.user_agent.as_deref(),
+            ip_address: session.ip_address.as_deref(),
+        }
+    }
+}
+
+/// Database model for refresh tokens
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::refresh_tokens)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct RefreshTokenModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+/// Model for inserting new refresh tokens
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::refresh_tokens)]
+pub struct NewRefreshTokenModel<'a> {
+    pub user_id: i32,
+    pub token_hash: &'a str,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+impl From<RefreshTokenModel> for crate::domain::RefreshToken {
+    fn from(model: RefreshTokenModel) -> Self {
+        crate::domain::RefreshToken {
+            id: Some(model.id),
+            user_id: model.user_id,
+            token_hash: model.token_hash,
+            created_at: model.created_at.and_utc(),
+            expires_at: model.expires_at.and_utc(),
+            revoked_at: model.revoked_at.map(|at| at.and_utc()),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::RefreshToken> for NewRefreshTokenModel<'a> {
+    fn from(token: &'a crate::domain::RefreshToken) -> Self {
+        NewRefreshTokenModel {
+            user_id: token.user_id,
+            token_hash: &token.token_hash,
+            created_at: token.created_at.naive_utc(),
+            expires_at: token.expires_at.naive_utc(),
+        }
+    }
+}
+
+/// Database model for links between users and OpenID Connect accounts
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::user_identities)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UserIdentityModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub issuer: String,
+    pub subject: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Model for inserting new user identities
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::user_identities)]
+pub struct NewUserIdentityModel<'a> {
+    pub user_id: i32,
+    pub issuer: &'a str,
+    pub subject: &'a str,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<UserIdentityModel> for crate::domain::UserIdentity {
+    fn from(model: UserIdentityModel) -> Self {
+        crate::domain::UserIdentity {
+            id: Some(model.id),
+            user_id: model.user_id,
+            issuer: model.issuer,
+            subject: model.subject,
+            created_at: model.created_at.and_utc(),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::UserIdentity> for NewUserIdentityModel<'a> {
+    fn from(identity: &'a crate::domain::UserIdentity) -> Self {
+        NewUserIdentityModel {
+            user_id: identity.user_id,
+            issuer: &identity.issuer,
+            subject: &identity.subject,
+            created_at: identity.created_at.naive_utc(),
+        }
+    }
+}
+
+/// Database model for two-factor recovery codes
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::recovery_codes)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct RecoveryCodeModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub code_hash: String,
+    pub created_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+}
+
+/// Model for inserting new recovery codes
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::recovery_codes)]
+pub struct NewRecoveryCodeModel<'a> {
+    pub user_id: i32,
+    pub code_hash: &'a str,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<RecoveryCodeModel> for crate::domain::RecoveryCode {
+    fn from(model: RecoveryCodeModel) -> Self {
+        crate::domain::RecoveryCode {
+            id: Some(model.id),
+            user_id: model.user_id,
+            code_hash: model.code_hash,
+            created_at: model.created_at.and_utc(),
+            used_at: model.used_at.map(|at| at.and_utc()),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::domain::RecoveryCode> for NewRecoveryCodeModel<'a> {
+    fn from(code: &'a crate::domain::RecoveryCode) -> Self {
+        NewRecoveryCodeModel {
+            user_id: code.user_id,
+            code_hash: &code.code_hash,
+            created_at: code.created_at.naive_utc(),
         }
     }
 }
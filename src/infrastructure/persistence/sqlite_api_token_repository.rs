@@ -0,0 +1,126 @@
+use crate::domain::{ApiToken, ApiTokenRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::{ApiTokenModel, NewApiTokenModel};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the ApiTokenRepository port
+pub struct SqliteApiTokenRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteApiTokenRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for SqliteApiTokenRepository {
+    async fn find_all(&self) -> Result<Vec<ApiToken>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("api_tokens.find_all", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::api_tokens::dsl::*;
+
+                api_tokens
+                    .order(id.asc())
+                    .select(ApiTokenModel::as_select())
+                    .load(conn)
+                    .map(|models: Vec<ApiTokenModel>| models.into_iter().map(ApiToken::from).collect())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn find_by_hash(&self, wanted_hash: &str) -> Result<Option<ApiToken>, String> {
+        let pool = Arc::clone(&self.pool);
+        let wanted_hash = wanted_hash.to_string();
+
+        spawn_db_call("api_tokens.find_by_hash", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::api_tokens::dsl::*;
+
+                api_tokens
+                    .filter(token_hash.eq(&wanted_hash))
+                    .select(ApiTokenModel::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|model| model.map(ApiToken::from))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn save(&self, token: ApiToken) -> Result<ApiToken, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("api_tokens.save", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::api_tokens::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::insert_into(api_tokens)
+                        .values(&NewApiTokenModel::from(&token))
+                        .execute(conn)?;
+
+                    // Hashes are unique, so the new row is found by its hash
+                    api_tokens
+                        .filter(token_hash.eq(&token.token_hash))
+                        .select(ApiTokenModel::as_select())
+                        .first(conn)
+                        .map(ApiToken::from)
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn revoke(&self, token_id: i32, at: DateTime<Utc>) -> Result<Option<ApiToken>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("api_tokens.revoke", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::api_tokens::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::update(api_tokens.filter(id.eq(token_id)).filter(revoked_at.is_null()))
+                        .set(revoked_at.eq(at.naive_utc()))
+                        .execute(conn)?;
+
+                    api_tokens
+                        .filter(id.eq(token_id))
+                        .select(ApiTokenModel::as_select())
+                        .first(conn)
+                        .optional()
+                        .map(|model| model.map(ApiToken::from))
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn touch(&self, token_id: i32, at: DateTime<Utc>) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("api_tokens.touch", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::api_tokens::dsl::*;
+
+                diesel::update(api_tokens.filter(id.eq(token_id)))
+                    .set(last_used_at.eq(at.naive_utc()))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
@@ -1,11 +1,17 @@
-use crate::domain::{Post, PostRepository};
-use crate::infrastructure::database::{DbPool, run_with_connection};
-use crate::infrastructure::persistence::models::{PostModel, NewPostModel};
+use crate::domain::{
+    BatchGuard, OperationError, OperationOutcome, Post, PostImport, PostLink, PostOperation, PostPatch, PostRepository,
+    UpsertOutcome, ValidationErrors, Violation, ViolationCode,
+};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::{PostModel, NewPostModel, PostChangesModel, NewPostTagModel, NewSlugHistoryModel};
 use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::*;
+use diesel::result::DatabaseErrorKind;
 use diesel::sql_query;
 use diesel::sql_types::Integer;
 use diesel::QueryableByName;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // Helper struct for getting the last inserted ID
@@ -31,27 +37,44 @@ impl PostRepository for SqlitePostRepository {
     async fn find_all(&self) -> Result<Vec<Post>, String> {
         let pool = Arc::clone(&self.pool);
         
-        tokio::task::spawn_blocking(move || {
+        spawn_db_call("posts.find_all", move || {
             run_with_connection(&pool, |conn| {
                 use crate::schema::posts::dsl::*;
                 
                 posts
                     .select(PostModel::as_select())
                     .load(conn)
-                    .map(|models: Vec<PostModel>| {
-                        models.into_iter().map(Post::from).collect()
-                    })
+                    .and_then(|models: Vec<PostModel>| with_tags(conn, models))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn find_after_id(&self, after_id: i32, limit: i64) -> Result<Vec<Post>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("posts.find_after_id", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::posts::dsl::*;
+
+                posts
+                    .filter(id.gt(after_id))
+                    .order(id.asc())
+                    .limit(limit)
+                    .select(PostModel::as_select())
+                    .load(conn)
+                    .and_then(|models: Vec<PostModel>| with_tags(conn, models))
                     .map_err(|e| format!("Database error: {}", e))
             })
         })
         .await
-        .map_err(|e| format!("Task error: {}", e))?
     }
 
     async fn find_by_id(&self, post_id: i32) -> Result<Option<Post>, String> {
         let pool = Arc::clone(&self.pool);
         
-        tokio::task::spawn_blocking(move || {
+        spawn_db_call("posts.find_by_id", move || {
             run_with_connection(&pool, |conn| {
                 use crate::schema::posts::dsl::*;
                 
@@ -59,61 +82,179 @@ impl PostRepository for SqlitePostRepository {
                     .filter(id.eq(post_id))
                     .first::<PostModel>(conn)
                     .optional()
-                    .map(|opt| opt.map(Post::from))
+                    .and_then(|opt| with_tags_one(conn, opt))
                     .map_err(|e| format!("Database error: {}", e))
             })
         })
         .await
-        .map_err(|e| format!("Task error: {}", e))?
     }
 
-    async fn save(&self, post: Post) -> Result<Post, String> {
+    async fn find_by_slug(&self, post_slug: &str) -> Result<Option<Post>, String> {
         let pool = Arc::clone(&self.pool);
-        
-        tokio::task::spawn_blocking(move || {
+        let post_slug = post_slug.to_string();
+
+        spawn_db_call("posts.find_by_slug", move || {
             run_with_connection(&pool, |conn| {
                 use crate::schema::posts::dsl::*;
 
-                let new_post = NewPostModel::from(&post);
+                posts
+                    .filter(slug.eq(&post_slug))
+                    .first::<PostModel>(conn)
+                    .optional()
+                    .and_then(|opt| with_tags_one(conn, opt))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
 
-                // Use transaction for atomic operation
-                conn.transaction(|conn| {
-                    // Insert the post
-                    diesel::insert_into(posts)
-                        .values(&new_post)
-                        .execute(conn)?;
+    async fn find_by_retired_slug(&self, retired_slug: &str) -> Result<Option<Post>, String> {
+        let pool = Arc::clone(&self.pool);
+        let retired_slug = retired_slug.to_string();
 
-                    // Get the last inserted row ID
-                    let result = sql_query("SELECT last_insert_rowid() as last_insert_rowid")
-                        .get_result::<LastId>(conn)?;
+        spawn_db_call("posts.find_by_retired_slug", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::{posts, slug_history};
+
+                slug_history::table
+                    .inner_join(posts::table)
+                    .filter(slug_history::slug.eq(&retired_slug))
+                    .select(PostModel::as_select())
+                    .first::<PostModel>(conn)
+                    .optional()
+                    .and_then(|opt| with_tags_one(conn, opt))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
 
-                    let last_id = result.last_insert_rowid;
+    async fn release_retired_slug(&self, retired_slug: &str) -> Result<bool, String> {
+        let pool = Arc::clone(&self.pool);
+        let retired_slug = retired_slug.to_string();
 
-                    // Fetch the newly created post
-                    let created_post: PostModel = posts
-                        .filter(id.eq(last_id))
-                        .first(conn)?;
+        spawn_db_call("posts.release_retired_slug", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::slug_history::dsl::*;
 
-                    Ok(Post::from(created_post))
-                })
-                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+                diesel::delete(slug_history.filter(slug.eq(&retired_slug)))
+                    .execute(conn)
+                    .map(|rows_affected| rows_affected > 0)
+                    .map_err(|e| format!("Database error: {}", e))
             })
         })
         .await
-        .map_err(|e| format!("Task error: {}", e))?
+    }
+
+    async fn find_latest_published(&self, offset: i64, limit: i64, tag_filter: Option<&str>) -> Result<Vec<Post>, String> {
+        let pool = Arc::clone(&self.pool);
+        let tag_filter = tag_filter.map(str::to_string);
+
+        spawn_db_call("posts.find_latest_published", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::post_tags;
+                use crate::schema::posts::dsl::*;
+
+                let mut query = posts
+                    .filter(published_at.le(Utc::now().naive_utc()))
+                    .order((published_at.desc(), id.desc()))
+                    .offset(offset)
+                    .limit(limit)
+                    .select(PostModel::as_select())
+                    .into_boxed();
+
+                if let Some(tag_filter) = &tag_filter {
+                    query = query.filter(
+                        id.eq_any(
+                            post_tags::table
+                                .filter(post_tags::tag.eq(tag_filter))
+                                .select(post_tags::post_id),
+                        ),
+                    );
+                }
+
+                query
+                    .load(conn)
+                    .and_then(|models: Vec<PostModel>| with_tags(conn, models))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn count_published(&self) -> Result<i64, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("posts.count_published", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::posts::dsl::*;
+
+                posts
+                    .filter(published_at.le(Utc::now().naive_utc()))
+                    .count()
+                    .get_result(conn)
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn find_published_links(&self, offset: i64, limit: i64) -> Result<Vec<PostLink>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("posts.find_published_links", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::posts::dsl::*;
+
+                posts
+                    .filter(published_at.le(Utc::now().naive_utc()))
+                    .order(id.asc())
+                    .offset(offset)
+                    .limit(limit)
+                    .select((slug, updated_at, published_at))
+                    .load::<(String, NaiveDateTime, Option<NaiveDateTime>)>(conn)
+                    .map(|rows| {
+                        rows.into_iter()
+                            .map(|(post_slug, modified, published)| PostLink {
+                                slug: post_slug,
+                                last_modified: published.map_or(modified, |p| p.max(modified)).and_utc(),
+                            })
+                            .collect()
+                    })
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn save(&self, post: Post) -> Result<Post, OperationError> {
+        let pool = Arc::clone(&self.pool);
+        
+        spawn_db_call("posts.save", move || {
+            run_with_connection(&pool, |conn| {
+                // Use transaction for atomic operation
+                Ok(conn.transaction(|conn| insert_post(conn, &post)).map_err(write_error))
+            })
+        })
+        .await
+        .map_err(OperationError::Failed)?
     }
 
     async fn update(&self, post_id: i32, post: Post) -> Result<Option<Post>, String> {
         let pool = Arc::clone(&self.pool);
         
-        tokio::task::spawn_blocking(move || {
+        spawn_db_call("posts.update", move || {
             run_with_connection(&pool, |conn| {
                 use crate::schema::posts::dsl::*;
 
                 // First update the post
                 let rows_affected = diesel::update(posts)
                     .filter(id.eq(post_id))
-                    .set((title.eq(&post.title), body.eq(&post.body)))
+                    .set((
+                        title.eq(&post.title),
+                        body.eq(&post.body),
+                        updated_at.eq(Utc::now().naive_utc()),
+                    ))
                     .execute(conn)
                     .map_err(|e| format!("Database error: {}", e))?;
 
@@ -126,28 +267,444 @@ impl PostRepository for SqlitePostRepository {
                     .filter(id.eq(post_id))
                     .first::<PostModel>(conn)
                     .optional()
-                    .map(|opt| opt.map(Post::from))
+                    .and_then(|opt| with_tags_one(conn, opt))
                     .map_err(|e| format!("Database error: {}", e))
             })
         })
         .await
-        .map_err(|e| format!("Task error: {}", e))?
+    }
+
+    async fn patch(&self, post_id: i32, changes: PostPatch) -> Result<Option<Post>, OperationError> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("posts.patch", move || {
+            run_with_connection(&pool, |conn| {
+                Ok(conn.transaction(|conn| patch_post(conn, post_id, &changes)).map_err(write_error))
+            })
+        })
+        .await
+        .map_err(OperationError::Failed)?
     }
 
     async fn delete(&self, post_id: i32) -> Result<bool, String> {
         let pool = Arc::clone(&self.pool);
         
-        tokio::task::spawn_blocking(move || {
+        spawn_db_call("posts.delete", move || {
             run_with_connection(&pool, |conn| {
-                use crate::schema::posts::dsl::*;
+                conn.transaction(|conn| delete_post(conn, post_id))
+                    .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
 
-                diesel::delete(posts.filter(id.eq(post_id)))
-                    .execute(conn)
-                    .map(|rows_affected| rows_affected > 0)
-                    .map_err(|e| format!("Database error: {}", e))
+    async fn upsert_batch(&self, batch: Vec<PostImport>) -> Result<Vec<Result<UpsertOutcome, String>>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("posts.upsert_batch", move || {
+            run_with_connection(&pool, |conn| {
+                conn.transaction(|conn| {
+                    // A savepoint per post rolls back only the post that failed
+                    Ok(batch
+                        .iter()
+                        .map(|import| {
+                            conn.transaction(|conn| upsert_one(conn, import))
+                                .unwrap_or_else(|e| Err(format!("Database error: {}", e)))
+                        })
+                        .collect())
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
             })
         })
         .await
-        .map_err(|e| format!("Task error: {}", e))?
     }
+
+    async fn apply_batch(
+        &self,
+        operations: Vec<PostOperation>,
+        atomic: bool,
+        guard: BatchGuard,
+    ) -> Result<Vec<Result<OperationOutcome, OperationError>>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("posts.apply_batch", move || {
+            run_with_connection(&pool, |conn| {
+                let applied = conn.transaction(|conn| {
+                    let mut results = Vec::with_capacity(operations.len());
+                    for operation in operations {
+                        // A savepoint per operation undoes its partial writes when it fails
+                        let result = conn
+                            .transaction(|conn| apply_operation(conn, operation, &guard))
+                            .unwrap_or_else(|e| Err(write_error(e)));
+
+                        let failed = result.is_err();
+                        results.push(result);
+                        if atomic && failed {
+                            return Err(BatchAbort::RolledBack(results));
+                        }
+                    }
+                    Ok(results)
+                });
+
+                match applied {
+                    Ok(results) | Err(BatchAbort::RolledBack(results)) => Ok(results),
+                    Err(BatchAbort::Database(e)) => Err(format!("Database error: {}", e)),
+                }
+            })
+        })
+        .await
+    }
+}
+
+type BatchResults = Vec<Result<OperationOutcome, OperationError>>;
+
+/// Ends a batch transaction with a rollback while keeping the results gathered so far
+enum BatchAbort {
+    RolledBack(BatchResults),
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for BatchAbort {
+    fn from(error: diesel::result::Error) -> Self {
+        BatchAbort::Database(error)
+    }
+}
+
+/// Apply one batch operation, validated against the writes of the operations before it
+/// Rejections that happen before anything is written are returned as `Ok(Err(..))`
+fn apply_operation(
+    conn: &mut SqliteConnection,
+    operation: PostOperation,
+    guard: &BatchGuard,
+) -> QueryResult<Result<OperationOutcome, OperationError>> {
+    use crate::schema::posts::dsl::*;
+
+    match &operation {
+        PostOperation::Create(post) => {
+            let conflicts = slug_conflicts(conn, &post.slug, None)?;
+            if !conflicts.is_empty() {
+                return Ok(Err(OperationError::Invalid(conflicts)));
+            }
+            Ok(Ok(OperationOutcome::Created(insert_post(conn, post)?)))
+        }
+        PostOperation::Update(post_id, patch) => {
+            let post_id = *post_id;
+            let existing = posts.filter(id.eq(post_id)).first::<PostModel>(conn).optional()?;
+            let Some(mut post) = with_tags_one(conn, existing)? else {
+                return Ok(Err(OperationError::NotFound));
+            };
+            if let Err(error) = guard(&post, &operation) {
+                return Ok(Err(error));
+            }
+
+            let was_draft = post.published_at.is_none();
+            let changes = post.apply_patch(patch.clone());
+            if let Err(errors) = post.validate() {
+                return Ok(Err(OperationError::Invalid(errors)));
+            }
+            if let Some(new_slug) = &changes.slug {
+                let conflicts = slug_conflicts(conn, new_slug, Some(post_id))?;
+                if !conflicts.is_empty() {
+                    return Ok(Err(OperationError::Invalid(conflicts)));
+                }
+            }
+
+            if changes.is_empty() {
+                return Ok(Ok(OperationOutcome::Updated { post, published: false }));
+            }
+            Ok(patch_post(conn, post_id, &changes)?
+                .map(|post| {
+                    let published = was_draft && post.published_at.is_some();
+                    OperationOutcome::Updated { post, published }
+                })
+                .ok_or(OperationError::NotFound))
+        }
+        PostOperation::Delete(post_id) => {
+            let post_id = *post_id;
+            let existing = posts.filter(id.eq(post_id)).first::<PostModel>(conn).optional()?;
+            let Some(post) = with_tags_one(conn, existing)? else {
+                return Ok(Err(OperationError::NotFound));
+            };
+            if let Err(error) = guard(&post, &operation) {
+                return Ok(Err(error));
+            }
+
+            Ok(if delete_post(conn, post_id)? {
+                Ok(OperationOutcome::Deleted(post_id))
+            } else {
+                Err(OperationError::NotFound)
+            })
+        }
+    }
+}
+
+/// A write failure; the unique slug index turns a slug taken between check and write into a conflict
+fn write_error(error: diesel::result::Error) -> OperationError {
+    match error {
+        diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            OperationError::Conflict("The slug was taken by another post while this request ran; retry it".to_string())
+        }
+        error => OperationError::Failed(format!("Database error: {}", error)),
+    }
+}
+
+/// Violations for a slug used by another post, either currently or as a redirect
+fn slug_conflicts(conn: &mut SqliteConnection, wanted: &str, owner_id: Option<i32>) -> QueryResult<ValidationErrors> {
+    use crate::schema::{posts, slug_history};
+
+    let mut errors = ValidationErrors::new();
+
+    let current = posts::table
+        .filter(posts::slug.eq(wanted))
+        .select(posts::id)
+        .first::<i32>(conn)
+        .optional()?;
+    if let Some(current) = current {
+        if Some(current) != owner_id {
+            errors.add(Violation::new("slug", ViolationCode::Taken, "is already used by another post"));
+        }
+        return Ok(errors);
+    }
+
+    let redirect = slug_history::table
+        .inner_join(posts::table)
+        .filter(slug_history::slug.eq(wanted))
+        .select((posts::id, posts::slug))
+        .first::<(i32, String)>(conn)
+        .optional()?;
+    if let Some((redirect_owner, redirect_target)) = redirect
+        && Some(redirect_owner) != owner_id
+    {
+        errors.add(Violation::new(
+            "slug",
+            ViolationCode::Redirected,
+            format!("still redirects to '{}'; release the redirect first", redirect_target),
+        ));
+    }
+
+    Ok(errors)
+}
+
+/// Insert a post with its tags and read it back
+fn insert_post(conn: &mut SqliteConnection, post: &Post) -> QueryResult<Post> {
+    use crate::schema::posts::dsl::*;
+
+    // Insert the post
+    diesel::insert_into(posts)
+        .values(&NewPostModel::from(post))
+        .execute(conn)?;
+
+    // Get the last inserted row ID
+    let result = sql_query("SELECT last_insert_rowid() as last_insert_rowid")
+        .get_result::<LastId>(conn)?;
+
+    let last_id = result.last_insert_rowid;
+
+    replace_tags(conn, last_id, &post.tags)?;
+
+    // Fetch the newly created post
+    let created_post: PostModel = posts
+        .filter(id.eq(last_id))
+        .first(conn)?;
+
+    with_tags_one(conn, Some(created_post))
+        .map(|created| created.expect("post was just inserted"))
+}
+
+/// Write the changed fields of a post, retiring its previous slug when it changes
+fn patch_post(conn: &mut SqliteConnection, post_id: i32, changes: &PostPatch) -> QueryResult<Option<Post>> {
+    use crate::schema::posts::dsl::*;
+
+    if let Some(new_slug) = &changes.slug {
+        let previous_slug = posts
+            .filter(id.eq(post_id))
+            .select(slug)
+            .first::<String>(conn)
+            .optional()?;
+
+        if let Some(previous_slug) = previous_slug {
+            retire_slug(conn, post_id, &previous_slug, new_slug)?;
+        }
+    }
+
+    // Diesel rejects an empty changeset, so only issue the UPDATE when something changed
+    if !changes.is_empty() {
+        let rows_affected = diesel::update(posts)
+            .filter(id.eq(post_id))
+            .set(&PostChangesModel {
+                updated_at: Some(Utc::now().naive_utc()),
+                ..PostChangesModel::from(changes)
+            })
+            .execute(conn)?;
+
+        if rows_affected == 0 {
+            return Ok(None);
+        }
+    }
+
+    if let Some(new_tags) = &changes.tags {
+        replace_tags(conn, post_id, new_tags)?;
+    }
+
+    let patched = posts
+        .filter(id.eq(post_id))
+        .first::<PostModel>(conn)
+        .optional()?;
+    with_tags_one(conn, patched)
+}
+
+/// Delete a post with its tags and retired slugs
+fn delete_post(conn: &mut SqliteConnection, post_id: i32) -> QueryResult<bool> {
+    use crate::schema::posts::dsl::*;
+
+    // Retired slugs of a deleted post have nothing left to redirect to
+    diesel::delete(
+        crate::schema::slug_history::table
+            .filter(crate::schema::slug_history::post_id.eq(post_id)),
+    )
+    .execute(conn)?;
+
+    replace_tags(conn, post_id, &[])?;
+
+    diesel::delete(posts.filter(id.eq(post_id)))
+        .execute(conn)
+        .map(|rows_affected| rows_affected > 0)
+}
+
+/// Update the post owning the import's slug, keeping the dates the import leaves out, or insert it
+/// Rejections that happen before anything is written are returned as `Ok(Err(..))`
+fn upsert_one(conn: &mut SqliteConnection, import: &PostImport) -> QueryResult<Result<UpsertOutcome, String>> {
+    use crate::schema::posts::dsl::*;
+
+    let post = &import.post;
+    let existing_id = posts
+        .filter(slug.eq(&post.slug))
+        .select(id)
+        .first::<i32>(conn)
+        .optional()?;
+
+    if let Some(existing_id) = existing_id {
+        diesel::update(posts.filter(id.eq(existing_id)))
+            .set(&PostChangesModel {
+                title: Some(&post.title),
+                slug: None,
+                body: Some(&post.body),
+                published_at: import.published_at.map(|at| at.map(|at| at.naive_utc())),
+                created_at: import.created_at.map(|at| at.naive_utc()),
+                updated_at: import.updated_at.map(|at| at.naive_utc()),
+            })
+            .execute(conn)?;
+        replace_tags(conn, existing_id, &post.tags)?;
+        return Ok(Ok(UpsertOutcome::Updated(existing_id)));
+    }
+
+    let redirected = crate::schema::slug_history::table
+        .find(&post.slug)
+        .select(crate::schema::slug_history::post_id)
+        .first::<i32>(conn)
+        .optional()?;
+    if let Some(owner_id) = redirected {
+        return Ok(Err(format!("slug still redirects to post {}; release the redirect first", owner_id)));
+    }
+
+    let free_id = match post.id {
+        Some(wanted) => posts.find(wanted).select(id).first::<i32>(conn).optional()?.is_none().then_some(wanted),
+        None => None,
+    };
+    diesel::insert_into(posts)
+        .values(&NewPostModel {
+            id: free_id,
+            ..NewPostModel::from(post)
+        })
+        .execute(conn)?;
+
+    let new_id = sql_query("SELECT last_insert_rowid() as last_insert_rowid")
+        .get_result::<LastId>(conn)?
+        .last_insert_rowid;
+    replace_tags(conn, new_id, &post.tags)?;
+    Ok(Ok(UpsertOutcome::Inserted(new_id)))
+}
+
+/// Record `previous_slug` as a redirect to the post and drop any history entry for the slug it now takes
+fn retire_slug(
+    conn: &mut SqliteConnection,
+    owner_id: i32,
+    previous_slug: &str,
+    new_slug: &str,
+) -> QueryResult<()> {
+    use crate::schema::slug_history::dsl::*;
+
+    if previous_slug == new_slug {
+        return Ok(());
+    }
+
+    diesel::delete(slug_history.filter(slug.eq(new_slug)))
+        .execute(conn)?;
+
+    diesel::replace_into(slug_history)
+        .values(&NewSlugHistoryModel {
+            slug: previous_slug,
+            post_id: owner_id,
+        })
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// SQLite caps the number of bound parameters, so tag lookups are chunked
+const TAG_LOOKUP_CHUNK: usize = 500;
+
+/// Build domain posts from rows, loading their tags in as few queries as possible
+fn with_tags(conn: &mut SqliteConnection, models: Vec<PostModel>) -> QueryResult<Vec<Post>> {
+    use crate::schema::post_tags::dsl::*;
+
+    let ids: Vec<i32> = models.iter().map(|model| model.id).collect();
+    let mut tags_by_post: HashMap<i32, Vec<String>> = HashMap::new();
+
+    for chunk in ids.chunks(TAG_LOOKUP_CHUNK) {
+        let rows: Vec<(i32, String)> = post_tags
+            .filter(post_id.eq_any(chunk))
+            .order((post_id, tag))
+            .select((post_id, tag))
+            .load(conn)?;
+
+        for (owner_id, name) in rows {
+            tags_by_post.entry(owner_id).or_default().push(name);
+        }
+    }
+
+    Ok(models
+        .into_iter()
+        .map(|model| {
+            let tags = tags_by_post.remove(&model.id).unwrap_or_default();
+            Post {
+                tags,
+                ..Post::from(model)
+            }
+        })
+        .collect())
+}
+
+fn with_tags_one(conn: &mut SqliteConnection, model: Option<PostModel>) -> QueryResult<Option<Post>> {
+    match model {
+        Some(model) => Ok(with_tags(conn, vec![model])?.pop()),
+        None => Ok(None),
+    }
+}
+
+/// Replace every tag of a post
+fn replace_tags(conn: &mut SqliteConnection, owner_id: i32, new_tags: &[String]) -> QueryResult<()> {
+    use crate::schema::post_tags::dsl::*;
+
+    diesel::delete(post_tags.filter(post_id.eq(owner_id))).execute(conn)?;
+
+    let rows: Vec<NewPostTagModel> = new_tags
+        .iter()
+        .map(|name| NewPostTagModel { post_id: owner_id, tag: name })
+        .collect();
+    if !rows.is_empty() {
+        diesel::insert_or_ignore_into(post_tags).values(&rows).execute(conn)?;
+    }
+
+    Ok(())
 }
@@ -0,0 +1,82 @@
+use crate::domain::{RecoveryCode, RecoveryCodeRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::NewRecoveryCodeModel;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the RecoveryCodeRepository port
+pub struct SqliteRecoveryCodeRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteRecoveryCodeRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RecoveryCodeRepository for SqliteRecoveryCodeRepository {
+    async fn replace_all(&self, owner: i32, codes: Vec<RecoveryCode>) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("recovery_codes.replace_all", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::recovery_codes::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::delete(recovery_codes.filter(user_id.eq(owner))).execute(conn)?;
+                    let models: Vec<NewRecoveryCodeModel> = codes.iter().map(NewRecoveryCodeModel::from).collect();
+                    diesel::insert_into(recovery_codes).values(&models).execute(conn)?;
+                    Ok(())
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn redeem(&self, owner: i32, wanted_hash: &str, at: DateTime<Utc>) -> Result<bool, String> {
+        let pool = Arc::clone(&self.pool);
+        let wanted_hash = wanted_hash.to_string();
+
+        spawn_db_call("recovery_codes.redeem", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::recovery_codes::dsl::*;
+
+                diesel::update(
+                    recovery_codes
+                        .filter(user_id.eq(owner))
+                        .filter(code_hash.eq(&wanted_hash))
+                        .filter(used_at.is_null()),
+                )
+                .set(used_at.eq(at.naive_utc()))
+                .execute(conn)
+                .map(|rows| rows > 0)
+                .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn count_unused(&self, owner: i32) -> Result<usize, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("recovery_codes.count_unused", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::recovery_codes::dsl::*;
+
+                recovery_codes
+                    .filter(user_id.eq(owner))
+                    .filter(used_at.is_null())
+                    .count()
+                    .get_result::<i64>(conn)
+                    .map(|count| count as usize)
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,109 @@
+use crate::domain::{RefreshToken, RefreshTokenRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::{NewRefreshTokenModel, RefreshTokenModel};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the RefreshTokenRepository port
+pub struct SqliteRefreshTokenRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteRefreshTokenRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for SqliteRefreshTokenRepository {
+    async fn find_by_hash(&self, wanted_hash: &str) -> Result<Option<RefreshToken>, String> {
+        let pool = Arc::clone(&self.pool);
+        let wanted_hash = wanted_hash.to_string();
+
+        spawn_db_call("refresh_tokens.find_by_hash", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::refresh_tokens::dsl::*;
+
+                refresh_tokens
+                    .filter(token_hash.eq(&wanted_hash))
+                    .select(RefreshTokenModel::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|model| model.map(RefreshToken::from))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn save(&self, token: RefreshToken) -> Result<RefreshToken, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("refresh_tokens.save", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::refresh_tokens::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::insert_into(refresh_tokens)
+                        .values(&NewRefreshTokenModel::from(&token))
+                        .execute(conn)?;
+
+                    // Hashes are unique, so the new row is found by its hash
+                    refresh_tokens
+                        .filter(token_hash.eq(&token.token_hash))
+                        .select(RefreshTokenModel::as_select())
+                        .first(conn)
+                        .map(RefreshToken::from)
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn revoke(&self, token_id: i32, at: DateTime<Utc>) -> Result<bool, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("refresh_tokens.revoke", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::refresh_tokens::dsl::*;
+
+                diesel::update(
+                    refresh_tokens
+                        .filter(id.eq(token_id))
+                        .filter(revoked_at.is_null())
+                        .filter(expires_at.gt(at.naive_utc())),
+                )
+                .set(revoked_at.eq(at.naive_utc()))
+                .execute(conn)
+                .map(|rows| rows > 0)
+                .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn revoke_all(&self, owner: i32, at: DateTime<Utc>) -> Result<usize, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("refresh_tokens.revoke_all", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::refresh_tokens::dsl::*;
+
+                diesel::update(
+                    refresh_tokens
+                        .filter(user_id.eq(owner))
+                        .filter(revoked_at.is_null())
+                        .filter(expires_at.gt(at.naive_utc())),
+                )
+                .set(revoked_at.eq(at.naive_utc()))
+                .execute(conn)
+                .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,148 @@
+use crate::domain::{Session, SessionRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::{NewSessionModel, SessionModel};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the SessionRepository port
+pub struct SqliteSessionRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteSessionRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SqliteSessionRepository {
+    async fn find_by_hash(&self, wanted_hash: &str) -> Result<Option<Session>, String> {
+        let pool = Arc::clone(&self.pool);
+        let wanted_hash = wanted_hash.to_string();
+
+        spawn_db_call("sessions.find_by_hash", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::sessions::dsl::*;
+
+                sessions
+                    .filter(token_hash.eq(&wanted_hash))
+                    .select(SessionModel::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|model| model.map(Session::from))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn find_active_by_user(&self, owner: i32, now: DateTime<Utc>) -> Result<Vec<Session>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("sessions.find_active_by_user", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::sessions::dsl::*;
+
+                sessions
+                    .filter(user_id.eq(owner))
+                    .filter(revoked_at.is_null())
+                    .filter(expires_at.gt(now.naive_utc()))
+                    .order((last_seen_at.desc(), id.desc()))
+                    .select(SessionModel::as_select())
+                    .load(conn)
+                    .map(|models: Vec<SessionModel>| models.into_iter().map(Session::from).collect())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn save(&self, session: Session) -> Result<Session, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("sessions.save", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::sessions::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::insert_into(sessions)
+                        .values(&NewSessionModel::from(&session))
+                        .execute(conn)?;
+
+                    // Hashes are unique, so the new row is found by its hash
+                    sessions
+                        .filter(token_hash.eq(&session.token_hash))
+                        .select(SessionModel::as_select())
+                        .first(conn)
+                        .map(Session::from)
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn touch(&self, session_id: i32, at: DateTime<Utc>) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("sessions.touch", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::sessions::dsl::*;
+
+                diesel::update(sessions.filter(id.eq(session_id)))
+                    .set(last_seen_at.eq(at.naive_utc()))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn revoke(&self, owner: i32, session_id: i32, at: DateTime<Utc>) -> Result<bool, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("sessions.revoke", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::sessions::dsl::*;
+
+                diesel::update(
+                    sessions
+                        .filter(id.eq(session_id))
+                        .filter(user_id.eq(owner))
+                        .filter(revoked_at.is_null())
+                        .filter(expires_at.gt(at.naive_utc())),
+                )
+                .set(revoked_at.eq(at.naive_utc()))
+                .execute(conn)
+                .map(|rows| rows > 0)
+                .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn revoke_all(&self, owner: i32, at: DateTime<Utc>) -> Result<usize, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("sessions.revoke_all", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::sessions::dsl::*;
+
+                diesel::update(
+                    sessions
+                        .filter(user_id.eq(owner))
+                        .filter(revoked_at.is_null())
+                        .filter(expires_at.gt(at.naive_utc())),
+                )
+                .set(revoked_at.eq(at.naive_utc()))
+                .execute(conn)
+                .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,56 @@
+use crate::domain::{Role, TwoFactorPolicyRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the TwoFactorPolicyRepository port
+pub struct SqliteTwoFactorPolicyRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteTwoFactorPolicyRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TwoFactorPolicyRepository for SqliteTwoFactorPolicyRepository {
+    async fn required_roles(&self) -> Result<Vec<Role>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("two_factor_policy.required_roles", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::two_factor_roles::dsl::*;
+
+                two_factor_roles
+                    .select(role)
+                    .load::<String>(conn)
+                    // Unknown roles are skipped rather than failing every login
+                    .map(|names| names.iter().filter_map(|name| name.parse().ok()).collect())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn set_required_roles(&self, roles: Vec<Role>) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("two_factor_policy.set_required_roles", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::two_factor_roles::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::delete(two_factor_roles).execute(conn)?;
+                    let rows: Vec<_> = roles.iter().map(|required| role.eq(required.as_str())).collect();
+                    diesel::insert_into(two_factor_roles).values(&rows).execute(conn)?;
+                    Ok(())
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
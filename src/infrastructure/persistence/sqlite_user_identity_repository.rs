@@ -0,0 +1,68 @@
+use crate::domain::{UserIdentity, UserIdentityRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::{NewUserIdentityModel, UserIdentityModel};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the UserIdentityRepository port
+pub struct SqliteUserIdentityRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteUserIdentityRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserIdentityRepository for SqliteUserIdentityRepository {
+    async fn find(&self, wanted_issuer: &str, wanted_subject: &str) -> Result<Option<UserIdentity>, String> {
+        let pool = Arc::clone(&self.pool);
+        let wanted_issuer = wanted_issuer.to_string();
+        let wanted_subject = wanted_subject.to_string();
+
+        spawn_db_call("user_identities.find", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::user_identities::dsl::*;
+
+                user_identities
+                    .filter(issuer.eq(&wanted_issuer))
+                    .filter(subject.eq(&wanted_subject))
+                    .select(UserIdentityModel::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|model| model.map(UserIdentity::from))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn save(&self, identity: UserIdentity) -> Result<UserIdentity, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("user_identities.save", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::user_identities::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::insert_into(user_identities)
+                        .values(&NewUserIdentityModel::from(&identity))
+                        .execute(conn)?;
+
+                    // Issuer and subject are unique together, so the new row is found by them
+                    user_identities
+                        .filter(issuer.eq(&identity.issuer))
+                        .filter(subject.eq(&identity.subject))
+                        .select(UserIdentityModel::as_select())
+                        .first(conn)
+                        .map(UserIdentity::from)
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
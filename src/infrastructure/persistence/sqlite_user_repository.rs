@@ -0,0 +1,194 @@
+use crate::domain::{Role, User, UserRepository};
+use crate::infrastructure::database::{DbPool, run_with_connection, spawn_db_call};
+use crate::infrastructure::persistence::models::{NewUserModel, UserModel};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+
+/// SQLite implementation of the UserRepository port
+pub struct SqliteUserRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn find_all(&self) -> Result<Vec<User>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.find_all", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                users
+                    .order(id.asc())
+                    .select(UserModel::as_select())
+                    .load(conn)
+                    .map(|models: Vec<UserModel>| models.into_iter().map(User::from).collect())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn find_by_id(&self, user_id: i32) -> Result<Option<User>, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.find_by_id", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                users
+                    .filter(id.eq(user_id))
+                    .select(UserModel::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|model| model.map(User::from))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn find_by_email(&self, wanted_email: &str) -> Result<Option<User>, String> {
+        let pool = Arc::clone(&self.pool);
+        let wanted_email = wanted_email.to_string();
+
+        spawn_db_call("users.find_by_email", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                users
+                    .filter(email.eq(&wanted_email))
+                    .select(UserModel::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|model| model.map(User::from))
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn save(&self, user: User) -> Result<User, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.save", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                conn.transaction(|conn| {
+                    diesel::insert_into(users)
+                        .values(&NewUserModel::from(&user))
+                        .execute(conn)?;
+
+                    // Emails are unique, so the new row is found by its email
+                    users
+                        .filter(email.eq(&user.email))
+                        .select(UserModel::as_select())
+                        .first(conn)
+                        .map(User::from)
+                })
+                .map_err(|e: diesel::result::Error| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn update_role(&self, user_id: i32, new_role: Role) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.update_role", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                diesel::update(users.filter(id.eq(user_id)))
+                    .set((role.eq(new_role.as_str()), updated_at.eq(Utc::now().naive_utc())))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn update_login_state(
+        &self,
+        user_id: i32,
+        attempts: i32,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.update_login_state", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                diesel::update(users.filter(id.eq(user_id)))
+                    .set((
+                        failed_login_attempts.eq(attempts),
+                        locked_until.eq(until.map(|at| at.naive_utc())),
+                    ))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn update_two_factor(
+        &self,
+        user_id: i32,
+        secret: Option<String>,
+        enabled_at: Option<DateTime<Utc>>,
+    ) -> Result<(), String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.update_two_factor", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                diesel::update(users.filter(id.eq(user_id)))
+                    .set((
+                        totp_secret.eq(secret),
+                        totp_enabled_at.eq(enabled_at.map(|at| at.naive_utc())),
+                        totp_last_step.eq(None::<i64>),
+                        updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+
+    async fn advance_totp_step(&self, user_id: i32, step: i64) -> Result<bool, String> {
+        let pool = Arc::clone(&self.pool);
+
+        spawn_db_call("users.advance_totp_step", move || {
+            run_with_connection(&pool, |conn| {
+                use crate::schema::users::dsl::*;
+
+                // A single conditional update, so two requests racing with the same code cannot both win
+                diesel::update(
+                    users
+                        .filter(id.eq(user_id))
+                        .filter(totp_last_step.is_null().or(totp_last_step.lt(step))),
+                )
+                .set(totp_last_step.eq(step))
+                .execute(conn)
+                .map(|rows| rows > 0)
+                .map_err(|e| format!("Database error: {}", e))
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,55 @@
+use crate::infrastructure::rate_limit::token_bucket::{RateLimit, RateLimitDecision, RateLimitStore, TokenBucket};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often buckets that have refilled are dropped, so idle clients do not pile up
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+struct Buckets {
+    buckets: HashMap<String, (TokenBucket, RateLimit)>,
+    pruned_at: Instant,
+}
+
+/// In-memory implementation of the RateLimitStore; each server process counts on its own
+pub struct InMemoryRateLimitStore {
+    state: Mutex<Buckets>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                pruned_at: Instant::now(),
+            }),
+        }
+    }
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, String> {
+        let now = Instant::now();
+        let mut state = self.state.lock().map_err(|e| format!("Rate limit store error: {}", e))?;
+
+        if now.duration_since(state.pruned_at) >= PRUNE_INTERVAL {
+            state.buckets.retain(|_, (bucket, limit)| !bucket.is_full_at(limit, now));
+            state.pruned_at = now;
+        }
+
+        let (bucket, stored_limit) = state
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| (TokenBucket::full(limit, now), *limit));
+        *stored_limit = *limit;
+        Ok(bucket.take(limit, now))
+    }
+}
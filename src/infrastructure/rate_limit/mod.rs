@@ -0,0 +1,5 @@
+pub mod memory_store;
+pub mod token_bucket;
+
+pub use memory_store::InMemoryRateLimitStore;
+pub use token_bucket::{RateLimit, RateLimitDecision, RateLimitStore, TokenBucket};
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use std::time::{Duration, Instant};
+
+/// Requests allowed per period; a full bucket also allows them all at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub capacity: u32,
+    /// Time an empty bucket takes to fill up again
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub fn per_minute(capacity: u32) -> Self {
+        Self {
+            capacity,
+            period: Duration::from_secs(60),
+        }
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        f64::from(self.capacity) / self.period.as_secs_f64()
+    }
+}
+
+/// Outcome of taking a token, with what the `RateLimit-*` headers report
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until the bucket is full again
+    pub reset_after: u64,
+    /// Seconds until the next token, when the request was refused
+    pub retry_after: u64,
+}
+
+/// Storage of the buckets, one per client and route class
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Take a token from the bucket under `key`, which starts full
+    async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, String>;
+}
+
+/// A token bucket, refilled continuously at `capacity` tokens per `period`
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(limit.capacity),
+            updated_at: now,
+        }
+    }
+
+    /// Refill for the time passed since the last request, then take a token if one is left
+    pub fn take(&mut self, limit: &RateLimit, now: Instant) -> RateLimitDecision {
+        let rate = limit.tokens_per_second();
+        let capacity = f64::from(limit.capacity);
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.updated_at = now;
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+        RateLimitDecision {
+            allowed,
+            limit: limit.capacity,
+            remaining: self.tokens.floor() as u32,
+            reset_after: ((capacity - self.tokens) / rate).ceil() as u64,
+            retry_after: if allowed { 0 } else { ((1.0 - self.tokens) / rate).ceil().max(1.0) as u64 },
+        }
+    }
+
+    /// Whether the bucket has refilled completely, so forgetting it changes nothing
+    pub fn is_full_at(&self, limit: &RateLimit, now: Instant) -> bool {
+        now.saturating_duration_since(self.updated_at) >= limit.period
+    }
+}
@@ -0,0 +1,51 @@
+use pulldown_cmark::{html, Event, Options, Parser, TagEnd};
+
+fn options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+}
+
+/// Render a post body written in Markdown to HTML
+/// Raw HTML in the body is escaped and shown as text, so a post cannot inject scripts into pages and feeds
+pub fn render_markdown(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, options()).map(|event| match event {
+        Event::Html(value) | Event::InlineHtml(value) => Event::Text(value),
+        event => event,
+    });
+    let mut output = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// Plain-text summary of a Markdown body, cut at a word boundary after at most `max_chars` characters
+pub fn excerpt(markdown: &str, max_chars: usize) -> String {
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, options()) {
+        match event {
+            Event::Text(value) | Event::Code(value) => text.push_str(&value),
+            Event::SoftBreak | Event::HardBreak | Event::End(TagEnd::Paragraph | TagEnd::Heading(_)) => {
+                text.push(' ')
+            }
+            _ => {}
+        }
+    }
+
+    let mut summary = String::new();
+    let mut length = 0;
+    for word in text.split_whitespace() {
+        let separator = usize::from(length > 0);
+        let word_length = word.chars().count();
+        if length + separator + word_length > max_chars {
+            summary.push('…');
+            break;
+        }
+        if separator == 1 {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+        length += separator + word_length;
+    }
+    summary
+}
@@ -0,0 +1,5 @@
+pub mod markdown;
+pub mod qr_code;
+
+pub use markdown::{excerpt, render_markdown};
+pub use qr_code::qr_code_svg;
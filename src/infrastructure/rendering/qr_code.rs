@@ -0,0 +1,14 @@
+use qrcode::render::svg;
+use qrcode::QrCode;
+
+/// Smallest rendered size; authenticator apps scan it easily from a screen
+const MIN_SIZE_PX: u32 = 200;
+
+/// An SVG image of a QR code holding `data`
+pub fn qr_code_svg(data: &str) -> Result<String, String> {
+    let code = QrCode::new(data.as_bytes()).map_err(|e| format!("QR code error: {}", e))?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(MIN_SIZE_PX, MIN_SIZE_PX)
+        .build())
+}
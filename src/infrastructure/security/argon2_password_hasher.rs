@@ -0,0 +1,51 @@
+use crate::domain::PasswordHasher;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use async_trait::async_trait;
+use rand::RngCore;
+
+/// Argon2id with the crate's default parameters (19 MiB, 2 iterations), stored as PHC strings
+/// Hashing is deliberately slow, so it runs on the blocking thread pool
+#[derive(Default)]
+pub struct Argon2PasswordHasher;
+
+impl Argon2PasswordHasher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl PasswordHasher for Argon2PasswordHasher {
+    async fn hash(&self, password: &str) -> Result<String, String> {
+        let password = password.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut salt = [0u8; 16];
+            rand::rng().fill_bytes(&mut salt);
+            let salt = SaltString::encode_b64(&salt).map_err(|e| format!("Hashing error: {}", e))?;
+
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|e| format!("Hashing error: {}", e))
+        })
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+    }
+
+    async fn verify(&self, password: &str, hash: &str) -> Result<bool, String> {
+        let password = password.to_string();
+        let hash = hash.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            // Parameters are read from the PHC string, so hashes made with older settings still verify
+            let Ok(parsed) = PasswordHash::new(&hash) else {
+                return false;
+            };
+            Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok()
+        })
+        .await
+        .map_err(|e| format!("Task error: {}", e))
+    }
+}
@@ -0,0 +1,196 @@
+use crate::domain::{AccessClaims, AccessTokenCodec, Role};
+use crate::infrastructure::config::{JwtAlgorithm, JwtConfig};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::DateTime;
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters,
+    OctetKeyPairType, OctetKeyParameters, OctetKeyType, PublicKeyUse, ThumbprintHash,
+};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Registered claims of an access token, plus the user's role
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    iss: String,
+    aud: String,
+    /// User ID
+    sub: String,
+    iat: i64,
+    exp: i64,
+    role: Role,
+}
+
+/// A key that verifies the tokens whose `kid` header matches `id`
+struct VerificationKey {
+    id: String,
+    key: DecodingKey,
+}
+
+/// JWT implementation of the AccessTokenCodec port
+/// Tokens are signed with the newest configured key and verified with any of them; key IDs are RFC 7638
+/// thumbprints, so rotating keys needs no extra configuration
+pub struct JwtCodec {
+    algorithm: Algorithm,
+    signing_key_id: String,
+    signing_key: EncodingKey,
+    verification_keys: Vec<VerificationKey>,
+    jwks: JwkSet,
+    validation: Validation,
+    issuer: String,
+    audience: String,
+}
+
+impl JwtCodec {
+    pub fn new(config: &JwtConfig) -> Result<Self, String> {
+        let (algorithm, signing_key, verification_keys, published) = match config.algorithm {
+            JwtAlgorithm::Hs256 => hmac_keys(&config.secrets),
+            JwtAlgorithm::EdDsa => ed25519_keys(&config.key_files)?,
+        };
+
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[&config.issuer]);
+        validation.set_audience(&[&config.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        Ok(Self {
+            algorithm,
+            signing_key_id: verification_keys[0].id.clone(),
+            signing_key,
+            verification_keys,
+            jwks: JwkSet { keys: published },
+            validation,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+        })
+    }
+
+    /// Public keys for `/.well-known/jwks.json`; empty with HS256, whose secrets must stay private
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+}
+
+impl AccessTokenCodec for JwtCodec {
+    fn issue(&self, claims: &AccessClaims) -> Result<String, String> {
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.signing_key_id.clone());
+
+        let claims = Claims {
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            sub: claims.user_id.to_string(),
+            iat: claims.issued_at.timestamp(),
+            exp: claims.expires_at.timestamp(),
+            role: claims.role,
+        };
+        encode(&header, &claims, &self.signing_key).map_err(|e| format!("JWT error: {}", e))
+    }
+
+    fn verify(&self, token: &str) -> Option<AccessClaims> {
+        let key_id = decode_header(token).ok()?.kid?;
+        let key = self.verification_keys.iter().find(|key| key.id == key_id)?;
+        let claims = decode::<Claims>(token, &key.key, &self.validation).ok()?.claims;
+
+        Some(AccessClaims {
+            user_id: claims.sub.parse().ok()?,
+            role: claims.role,
+            issued_at: DateTime::from_timestamp(claims.iat, 0)?,
+            expires_at: DateTime::from_timestamp(claims.exp, 0)?,
+        })
+    }
+}
+
+type Keys = (Algorithm, EncodingKey, Vec<VerificationKey>, Vec<Jwk>);
+
+/// HS256 keys from the configured secrets, or a random secret when there are none
+fn hmac_keys(secrets: &[String]) -> Keys {
+    let secrets: Vec<Vec<u8>> = if secrets.is_empty() {
+        vec![random_bytes().to_vec()]
+    } else {
+        secrets.iter().map(|secret| secret.as_bytes().to_vec()).collect()
+    };
+
+    let verification_keys = secrets
+        .iter()
+        .map(|secret| {
+            let jwk = Jwk {
+                common: CommonParameters::default(),
+                algorithm: AlgorithmParameters::OctetKey(OctetKeyParameters {
+                    key_type: OctetKeyType::Octet,
+                    value: URL_SAFE_NO_PAD.encode(secret),
+                }),
+            };
+            VerificationKey {
+                id: jwk.thumbprint(ThumbprintHash::SHA256),
+                key: DecodingKey::from_secret(secret),
+            }
+        })
+        .collect();
+
+    (Algorithm::HS256, EncodingKey::from_secret(&secrets[0]), verification_keys, Vec::new())
+}
+
+/// Ed25519 keys from the configured PEM files, or a random key when there are none
+fn ed25519_keys(paths: &[PathBuf]) -> Result<Keys, String> {
+    let signing_key = match paths.first() {
+        Some(path) => SigningKey::from_pkcs8_pem(&read(path)?)
+            .map_err(|_| format!("{}: the newest JWT key must be an Ed25519 private key", path.display()))?,
+        None => SigningKey::from_bytes(&random_bytes()),
+    };
+
+    let mut public_keys = vec![signing_key.verifying_key()];
+    for path in paths.iter().skip(1) {
+        let pem = read(path)?;
+        let key = SigningKey::from_pkcs8_pem(&pem)
+            .map(|key| key.verifying_key())
+            .or_else(|_| VerifyingKey::from_public_key_pem(&pem))
+            .map_err(|_| format!("{}: not an Ed25519 private or public key", path.display()))?;
+        public_keys.push(key);
+    }
+
+    let mut verification_keys = Vec::new();
+    let mut published = Vec::new();
+    for public_key in public_keys {
+        let mut jwk = Jwk {
+            common: CommonParameters::default(),
+            algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(public_key.as_bytes()),
+            }),
+        };
+        let id = jwk.thumbprint(ThumbprintHash::SHA256);
+        jwk.common = CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(KeyAlgorithm::EdDSA),
+            key_id: Some(id.clone()),
+            ..CommonParameters::default()
+        };
+
+        verification_keys.push(VerificationKey {
+            id,
+            key: DecodingKey::from_ed_der(public_key.as_bytes()),
+        });
+        published.push(jwk);
+    }
+
+    let der = signing_key.to_pkcs8_der().map_err(|e| format!("Ed25519 key error: {}", e))?;
+    Ok((Algorithm::EdDSA, EncodingKey::from_ed_der(der.as_bytes()), verification_keys, published))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn random_bytes() -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+}
@@ -0,0 +1,7 @@
+pub mod argon2_password_hasher;
+pub mod jwt_codec;
+pub mod oidc_client;
+
+pub use argon2_password_hasher::Argon2PasswordHasher;
+pub use jwt_codec::JwtCodec;
+pub use oidc_client::OidcClient;
@@ -0,0 +1,607 @@
+use crate::domain::{AuthorizationRequest, IdentityClaims, IdentityProvider, IdentityProviderError};
+use crate::infrastructure::config::OidcConfig;
+use async_trait::async_trait;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::{Client, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::time::{Duration, Instant};
+use tokio::sync::{OnceCell, RwLock};
+
+/// Longest wait for any request to the provider
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Keys are fetched again for an unknown `kid` at most this often, so forged tokens cannot hammer the provider
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Signature algorithms accepted for ID tokens; HMAC would need the client secret as key, so it is left out
+const ASYMMETRIC_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// The parts of the discovery document this client uses
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+    #[serde(default)]
+    id_token_signing_alg_values_supported: Vec<String>,
+    #[serde(default)]
+    token_endpoint_auth_methods_supported: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+/// OAuth 2.0 error response of the token endpoint
+#[derive(Deserialize)]
+struct TokenError {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// ID token claims; `iss`, `aud` and `exp` are checked while decoding
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: Value,
+    azp: Option<String>,
+    nonce: Option<String>,
+    email: Option<String>,
+    /// A boolean, or the string `"true"` with some providers
+    email_verified: Option<Value>,
+    name: Option<String>,
+    preferred_username: Option<String>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+struct CachedKeys {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// OpenID Connect implementation of the IdentityProvider port
+/// The discovery document is fetched on first use and kept; signing keys are fetched again when the provider rotates them
+pub struct OidcClient {
+    http: Client,
+    config: OidcConfig,
+    metadata: OnceCell<ProviderMetadata>,
+    keys: RwLock<Option<CachedKeys>>,
+}
+
+impl OidcClient {
+    pub fn new(config: OidcConfig) -> Result<Self, String> {
+        let http = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("HTTP client error: {}", e))?;
+
+        Ok(Self {
+            http,
+            config,
+            metadata: OnceCell::new(),
+            keys: RwLock::new(None),
+        })
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata, IdentityProviderError> {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!("{}/.well-known/openid-configuration", self.config.issuer.trim_end_matches('/'));
+                let metadata: ProviderMetadata = self.get_json(&url).await?;
+
+                // Otherwise a document served elsewhere could vouch for another issuer's tokens
+                if metadata.issuer != self.config.issuer {
+                    return Err(IdentityProviderError::Unavailable(format!(
+                        "The discovery document is for issuer {}, not {}",
+                        metadata.issuer, self.config.issuer
+                    )));
+                }
+                Ok(metadata)
+            })
+            .await
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, IdentityProviderError> {
+        self.http
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(unavailable)?
+            .json()
+            .await
+            .map_err(unavailable)
+    }
+
+    /// The key that signed a token, fetching the provider's keys again if it is unknown
+    async fn signing_key(&self, key_id: Option<&str>) -> Result<Jwk, IdentityProviderError> {
+        if let Some(key) = self.keys.read().await.as_ref().and_then(|cached| find_key(&cached.keys, key_id)) {
+            return Ok(key);
+        }
+
+        let mut cached = self.keys.write().await;
+        // Another request may have refreshed the keys while this one waited
+        if let Some(key) = cached.as_ref().and_then(|cached| find_key(&cached.keys, key_id)) {
+            return Ok(key);
+        }
+        if cached.as_ref().is_some_and(|cached| cached.fetched_at.elapsed() < JWKS_REFRESH_INTERVAL) {
+            return Err(IdentityProviderError::Rejected("The ID token is signed with an unknown key".to_string()));
+        }
+
+        let keys: JwkSet = self.get_json(&self.metadata().await?.jwks_uri).await?;
+        let key = find_key(&keys, key_id);
+        *cached = Some(CachedKeys { keys, fetched_at: Instant::now() });
+        key.ok_or_else(|| IdentityProviderError::Rejected("The ID token is signed with an unknown key".to_string()))
+    }
+
+    /// Validate an ID token's signature, issuer, audience, expiry and nonce
+    async fn validate(&self, id_token: &str, nonce: &str) -> Result<IdentityClaims, IdentityProviderError> {
+        let header = decode_header(id_token).map_err(rejected)?;
+        let metadata = self.metadata().await?;
+        let supported = &metadata.id_token_signing_alg_values_supported;
+        // RS256 is the default when the provider does not list its algorithms
+        let allowed = if supported.is_empty() {
+            header.alg == Algorithm::RS256
+        } else {
+            supported.iter().any(|alg| alg.parse() == Ok(header.alg))
+        };
+        if !allowed || !ASYMMETRIC_ALGORITHMS.contains(&header.alg) {
+            return Err(IdentityProviderError::Rejected(format!(
+                "ID tokens signed with {:?} are not accepted",
+                header.alg
+            )));
+        }
+
+        let jwk = self.signing_key(header.kid.as_deref()).await?;
+        let key = DecodingKey::from_jwk(&jwk).map_err(rejected)?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation).map_err(rejected)?.claims;
+        check_binding(&claims, nonce, &self.config.client_id)?;
+
+        let groups = claim_values(&claims.other, &self.config.roles_claim);
+        let email_verified = match claims.email_verified {
+            Some(Value::Bool(verified)) => verified,
+            Some(Value::String(verified)) => verified == "true",
+            _ => false,
+        };
+        Ok(IdentityClaims {
+            issuer: claims.iss,
+            subject: claims.sub,
+            email: claims.email,
+            email_verified,
+            name: claims.name.or(claims.preferred_username),
+            groups,
+        })
+    }
+}
+
+#[async_trait]
+impl IdentityProvider for OidcClient {
+    fn issuer(&self) -> &str {
+        &self.config.issuer
+    }
+
+    async fn authorization_url(&self, request: &AuthorizationRequest) -> Result<String, IdentityProviderError> {
+        let metadata = self.metadata().await?;
+        let mut url = Url::parse(&metadata.authorization_endpoint).map_err(unavailable)?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_url)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &request.state)
+            .append_pair("nonce", &request.nonce)
+            .append_pair("code_challenge", &request.code_challenge)
+            .append_pair("code_challenge_method", "S256");
+        Ok(url.into())
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+        nonce: &str,
+    ) -> Result<IdentityClaims, IdentityProviderError> {
+        let metadata = self.metadata().await?;
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.config.redirect_url),
+            ("code_verifier", code_verifier),
+            ("client_id", &self.config.client_id),
+        ];
+        let mut request = self.http.post(&metadata.token_endpoint);
+        if let Some(secret) = &self.config.client_secret {
+            // client_secret_basic is the default; client_secret_post only when the provider supports nothing else
+            let methods = &metadata.token_endpoint_auth_methods_supported;
+            if methods.is_empty() || methods.iter().any(|method| method == "client_secret_basic") {
+                request = request.basic_auth(&self.config.client_id, Some(secret));
+            } else {
+                form.push(("client_secret", secret));
+            }
+        }
+
+        let response = request.form(&form).send().await.map_err(unavailable)?;
+        let status = response.status();
+        if status == StatusCode::BAD_REQUEST || status == StatusCode::UNAUTHORIZED {
+            let detail = match response.json::<TokenError>().await {
+                Ok(error) => error.error_description.unwrap_or(error.error),
+                Err(_) => status.to_string(),
+            };
+            return Err(IdentityProviderError::Rejected(format!("The identity provider refused the login: {}", detail)));
+        }
+        let tokens: TokenResponse = response
+            .error_for_status()
+            .map_err(unavailable)?
+            .json()
+            .await
+            .map_err(unavailable)?;
+
+        let id_token = tokens
+            .id_token
+            .ok_or_else(|| IdentityProviderError::Unavailable("The token response has no ID token".to_string()))?;
+        self.validate(&id_token, nonce).await
+    }
+}
+
+/// Refuse a token issued for another login, or to another client of the provider
+fn check_binding(claims: &IdTokenClaims, nonce: &str, client_id: &str) -> Result<(), IdentityProviderError> {
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(IdentityProviderError::Rejected("The ID token was issued for another login".to_string()));
+    }
+    // A token for several audiences must name this client as the one it was issued to
+    let audiences = claims.aud.as_array().map_or(1, Vec::len);
+    if claims.azp.as_deref().is_some_and(|azp| azp != client_id) || (audiences > 1 && claims.azp.is_none()) {
+        return Err(IdentityProviderError::Rejected("The ID token was issued to another client".to_string()));
+    }
+    Ok(())
+}
+
+/// The key with the given ID; a token without `kid` is accepted only when the provider has a single key
+fn find_key(keys: &JwkSet, key_id: Option<&str>) -> Option<Jwk> {
+    match key_id {
+        Some(key_id) => keys.find(key_id).cloned(),
+        None if keys.keys.len() == 1 => keys.keys.first().cloned(),
+        None => None,
+    }
+}
+
+/// Strings of a claim that holds a string or an array; `realm_access.roles` reaches into nested objects
+fn claim_values(claims: &Map<String, Value>, path: &str) -> Vec<String> {
+    let mut segments = path.split('.');
+    let Some(mut value) = segments.next().and_then(|name| claims.get(name)) else {
+        return Vec::new();
+    };
+    for segment in segments {
+        match value.get(segment) {
+            Some(nested) => value = nested,
+            None => return Vec::new(),
+        }
+    }
+
+    match value {
+        Value::String(value) => vec![value.clone()],
+        Value::Array(values) => values.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn unavailable(error: impl std::fmt::Display) -> IdentityProviderError {
+    IdentityProviderError::Unavailable(format!("Identity provider error: {}", error))
+}
+
+fn rejected(error: impl std::fmt::Display) -> IdentityProviderError {
+    IdentityProviderError::Rejected(format!("Invalid ID token: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::RoleMapping;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use ed25519_dalek::pkcs8::EncodePrivateKey;
+    use ed25519_dalek::SigningKey;
+    use jsonwebtoken::jwk::{AlgorithmParameters, CommonParameters, EllipticCurve, OctetKeyPairParameters, OctetKeyPairType};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    const CLIENT_ID: &str = "blog";
+    const NONCE: &str = "nonce-1";
+    const KEY_ID: &str = "key-1";
+    const GOOD_CODE: &str = "code-1";
+    const REDIRECT_URL: &str = "http://127.0.0.1:8080/auth/oidc/callback";
+
+    /// What the local issuer serves; `id_token` is the token its next code exchange returns
+    struct IssuerState {
+        url: String,
+        /// Issuer named by the discovery document
+        issuer: String,
+        jwks: JwkSet,
+        jwks_fetches: AtomicUsize,
+        id_token: Mutex<String>,
+        token_form: Mutex<HashMap<String, String>>,
+    }
+
+    /// OpenID provider on a free port of 127.0.0.1 with one Ed25519 signing key
+    struct MockIssuer {
+        state: web::Data<IssuerState>,
+    }
+
+    impl MockIssuer {
+        fn start() -> Self {
+            Self::start_as(None)
+        }
+
+        /// `issuer` overrides the issuer the discovery document names
+        fn start_as(issuer: Option<&str>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let state = web::Data::new(IssuerState {
+                issuer: issuer.map_or_else(|| url.clone(), str::to_string),
+                url,
+                jwks: JwkSet { keys: vec![ed25519_key(1).1] },
+                jwks_fetches: AtomicUsize::new(0),
+                id_token: Mutex::new(String::new()),
+                token_form: Mutex::new(HashMap::new()),
+            });
+
+            let data = state.clone();
+            let server = HttpServer::new(move || {
+                App::new()
+                    .app_data(data.clone())
+                    .route("/.well-known/openid-configuration", web::get().to(discovery))
+                    .route("/jwks", web::get().to(jwks))
+                    .route("/token", web::post().to(token))
+            })
+            .workers(1)
+            .listen(listener)
+            .unwrap()
+            .run();
+            actix_web::rt::spawn(server);
+            Self { state }
+        }
+
+        fn client(&self) -> OidcClient {
+            OidcClient::new(OidcConfig {
+                issuer: self.state.url.clone(),
+                client_id: CLIENT_ID.to_string(),
+                client_secret: None,
+                redirect_url: REDIRECT_URL.to_string(),
+                scopes: vec!["openid".to_string(), "email".to_string()],
+                roles_claim: "groups".to_string(),
+                role_mapping: RoleMapping::default(),
+            })
+            .unwrap()
+        }
+
+        /// Valid claims for this issuer, with `extra` merged in
+        fn claims(&self, extra: Value) -> Value {
+            let mut claims = json!({
+                "iss": self.state.url,
+                "sub": "42",
+                "aud": CLIENT_ID,
+                "exp": chrono::Utc::now().timestamp() + 300,
+                "nonce": NONCE,
+                "email": "ada@example.com",
+                "email_verified": true,
+                "groups": ["editors"],
+            });
+            claims.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            claims
+        }
+
+        /// Make the next code exchange return `claims` signed with `key`
+        fn issue(&self, header: &Header, key: &EncodingKey, claims: Value) {
+            *self.state.id_token.lock().unwrap() = encode(header, &claims, key).unwrap();
+        }
+    }
+
+    async fn discovery(state: web::Data<IssuerState>) -> HttpResponse {
+        HttpResponse::Ok().json(json!({
+            "issuer": state.issuer,
+            "authorization_endpoint": format!("{}/authorize", state.url),
+            "token_endpoint": format!("{}/token", state.url),
+            "jwks_uri": format!("{}/jwks", state.url),
+            // HS256 is advertised so only the client's own check keeps it out
+            "id_token_signing_alg_values_supported": ["EdDSA", "HS256"],
+        }))
+    }
+
+    async fn jwks(state: web::Data<IssuerState>) -> HttpResponse {
+        state.jwks_fetches.fetch_add(1, Ordering::SeqCst);
+        HttpResponse::Ok().json(&state.jwks)
+    }
+
+    async fn token(state: web::Data<IssuerState>, form: web::Form<HashMap<String, String>>) -> HttpResponse {
+        let form = form.into_inner();
+        if form.get("code").map(String::as_str) != Some(GOOD_CODE) {
+            return HttpResponse::BadRequest()
+                .json(json!({ "error": "invalid_grant", "error_description": "The code has expired" }));
+        }
+        *state.token_form.lock().unwrap() = form;
+        let id_token = state.id_token.lock().unwrap().clone();
+        HttpResponse::Ok().json(json!({ "access_token": "access", "token_type": "Bearer", "id_token": id_token }))
+    }
+
+    /// A deterministic Ed25519 key and its published JWK, both under `KEY_ID`
+    fn ed25519_key(seed: u8) -> (EncodingKey, Jwk) {
+        let signing_key = SigningKey::from_bytes(&[seed; 32]);
+        let der = signing_key.to_pkcs8_der().unwrap();
+        let jwk = Jwk {
+            common: CommonParameters { key_id: Some(KEY_ID.to_string()), ..CommonParameters::default() },
+            algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(signing_key.verifying_key().as_bytes()),
+            }),
+        };
+        (EncodingKey::from_ed_der(der.as_bytes()), jwk)
+    }
+
+    fn eddsa_header(key_id: &str) -> Header {
+        Header { kid: Some(key_id.to_string()), ..Header::new(Algorithm::EdDSA) }
+    }
+
+    async fn exchange(client: &OidcClient) -> Result<IdentityClaims, IdentityProviderError> {
+        client.exchange_code(GOOD_CODE, "verifier-1", NONCE).await
+    }
+
+    #[actix_web::test]
+    async fn a_signed_token_from_the_issuer_signs_the_user_in() {
+        let issuer = MockIssuer::start();
+        let client = issuer.client();
+        let request = AuthorizationRequest {
+            state: "state-1".to_string(),
+            nonce: NONCE.to_string(),
+            code_challenge: "challenge-1".to_string(),
+        };
+        let url = client.authorization_url(&request).await.unwrap();
+        assert!(url.starts_with(&format!("{}/authorize?", issuer.state.url)));
+        assert!(url.contains("code_challenge=challenge-1") && url.contains("code_challenge_method=S256"));
+
+        issuer.issue(&eddsa_header(KEY_ID), &ed25519_key(1).0, issuer.claims(json!({})));
+        let claims = exchange(&client).await.unwrap();
+        assert_eq!(claims.issuer, issuer.state.url);
+        assert_eq!(claims.subject, "42");
+        assert_eq!(claims.email.as_deref(), Some("ada@example.com"));
+        assert!(claims.email_verified);
+        assert_eq!(claims.groups, ["editors"]);
+
+        let form = issuer.state.token_form.lock().unwrap().clone();
+        assert_eq!(form["grant_type"], "authorization_code");
+        assert_eq!(form["code_verifier"], "verifier-1");
+        assert_eq!(form["redirect_uri"], REDIRECT_URL);
+        assert_eq!(form["client_id"], CLIENT_ID);
+    }
+
+    #[actix_web::test]
+    async fn a_code_the_issuer_refuses_is_rejected() {
+        let issuer = MockIssuer::start();
+        let result = issuer.client().exchange_code("code-2", "verifier-1", NONCE).await;
+        assert!(matches!(result, Err(IdentityProviderError::Rejected(detail)) if detail.contains("The code has expired")));
+    }
+
+    #[actix_web::test]
+    async fn a_discovery_document_for_another_issuer_is_refused() {
+        let issuer = MockIssuer::start_as(Some("https://id.example.com"));
+        issuer.issue(&eddsa_header(KEY_ID), &ed25519_key(1).0, issuer.claims(json!({})));
+        assert!(matches!(exchange(&issuer.client()).await, Err(IdentityProviderError::Unavailable(_))));
+    }
+
+    #[actix_web::test]
+    async fn a_token_from_another_issuer_is_rejected() {
+        let issuer = MockIssuer::start();
+        let claims = issuer.claims(json!({ "iss": "https://id.example.com" }));
+        issuer.issue(&eddsa_header(KEY_ID), &ed25519_key(1).0, claims);
+        let result = exchange(&issuer.client()).await;
+        assert!(matches!(result, Err(IdentityProviderError::Rejected(detail)) if detail.contains("InvalidIssuer")));
+    }
+
+    #[actix_web::test]
+    async fn a_token_with_a_forged_signature_is_rejected() {
+        let issuer = MockIssuer::start();
+        issuer.issue(&eddsa_header(KEY_ID), &ed25519_key(2).0, issuer.claims(json!({})));
+        let result = exchange(&issuer.client()).await;
+        assert!(matches!(result, Err(IdentityProviderError::Rejected(detail)) if detail.contains("InvalidSignature")));
+    }
+
+    #[actix_web::test]
+    async fn an_unknown_key_is_rejected_without_refetching_the_keys_every_time() {
+        let issuer = MockIssuer::start();
+        let client = issuer.client();
+        issuer.issue(&eddsa_header("key-2"), &ed25519_key(2).0, issuer.claims(json!({})));
+
+        assert!(matches!(exchange(&client).await, Err(IdentityProviderError::Rejected(_))));
+        assert!(matches!(exchange(&client).await, Err(IdentityProviderError::Rejected(_))));
+        assert_eq!(issuer.state.jwks_fetches.load(Ordering::SeqCst), 1);
+
+        // Known keys are served from the cache
+        issuer.issue(&eddsa_header(KEY_ID), &ed25519_key(1).0, issuer.claims(json!({})));
+        assert!(exchange(&client).await.is_ok());
+        assert_eq!(issuer.state.jwks_fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn hmac_signed_tokens_are_rejected_even_when_advertised() {
+        let issuer = MockIssuer::start();
+        let header = Header { kid: Some(KEY_ID.to_string()), ..Header::new(Algorithm::HS256) };
+        issuer.issue(&header, &EncodingKey::from_secret(b"client-secret"), issuer.claims(json!({})));
+
+        let result = exchange(&issuer.client()).await;
+        assert!(matches!(result, Err(IdentityProviderError::Rejected(detail)) if detail.contains("HS256")));
+        assert_eq!(issuer.state.jwks_fetches.load(Ordering::SeqCst), 0);
+    }
+
+    fn claims(extra: Value) -> IdTokenClaims {
+        let mut claims = json!({ "iss": "https://id.example.com", "sub": "42", "aud": CLIENT_ID, "nonce": NONCE });
+        claims.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(claims).unwrap()
+    }
+
+    fn is_rejected(result: Result<(), IdentityProviderError>) -> bool {
+        matches!(result, Err(IdentityProviderError::Rejected(_)))
+    }
+
+    #[test]
+    fn a_token_for_this_login_and_client_passes() {
+        assert!(check_binding(&claims(json!({})), NONCE, CLIENT_ID).is_ok());
+        assert!(check_binding(&claims(json!({ "azp": CLIENT_ID })), NONCE, CLIENT_ID).is_ok());
+        let shared = claims(json!({ "aud": [CLIENT_ID, "api"], "azp": CLIENT_ID }));
+        assert!(check_binding(&shared, NONCE, CLIENT_ID).is_ok());
+    }
+
+    #[test]
+    fn a_token_for_another_login_is_rejected() {
+        assert!(is_rejected(check_binding(&claims(json!({})), "nonce-2", CLIENT_ID)));
+        assert!(is_rejected(check_binding(&claims(json!({ "nonce": null })), NONCE, CLIENT_ID)));
+    }
+
+    #[test]
+    fn a_token_issued_to_another_client_is_rejected() {
+        assert!(is_rejected(check_binding(&claims(json!({ "azp": "other" })), NONCE, CLIENT_ID)));
+        let shared = claims(json!({ "aud": [CLIENT_ID, "api"] }));
+        assert!(is_rejected(check_binding(&shared, NONCE, CLIENT_ID)));
+        let foreign = claims(json!({ "aud": [CLIENT_ID, "api"], "azp": "api" }));
+        assert!(is_rejected(check_binding(&foreign, NONCE, CLIENT_ID)));
+    }
+
+    #[test]
+    fn roles_are_read_from_strings_arrays_and_nested_claims() {
+        let claims = claims(json!({
+            "groups": ["editors", 7, "writers"],
+            "role": "admin",
+            "realm_access": { "roles": ["editors"] },
+        }));
+        assert_eq!(claim_values(&claims.other, "groups"), ["editors", "writers"]);
+        assert_eq!(claim_values(&claims.other, "role"), ["admin"]);
+        assert_eq!(claim_values(&claims.other, "realm_access.roles"), ["editors"]);
+        assert!(claim_values(&claims.other, "realm_access.missing").is_empty());
+        assert!(claim_values(&claims.other, "missing").is_empty());
+    }
+}
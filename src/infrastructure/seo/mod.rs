@@ -0,0 +1,8 @@
+pub mod robots;
+pub mod sitemap;
+
+pub use robots::render_robots;
+pub use sitemap::{
+    page_count, render_index, render_urlset, sitemap_page_path, MAX_URLS_PER_SITEMAP,
+    SITEMAP_CONTENT_TYPE,
+};
@@ -0,0 +1,9 @@
+use crate::infrastructure::config::SiteConfig;
+
+/// Render `robots.txt`, allowing every crawler and pointing it at the sitemap
+pub fn render_robots(site: &SiteConfig) -> String {
+    format!(
+        "User-agent: *\nAllow: /\n\nSitemap: {}\n",
+        site.url("/sitemap.xml")
+    )
+}
@@ -0,0 +1,63 @@
+use crate::domain::PostLink;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::xml::escape;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::{self, Write};
+
+pub const SITEMAP_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+/// Maximum number of URLs a single sitemap may list (sitemaps.org protocol)
+pub const MAX_URLS_PER_SITEMAP: i64 = 50_000;
+
+const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// Number of child sitemaps needed for `count` posts
+pub fn page_count(count: i64) -> i64 {
+    (count + MAX_URLS_PER_SITEMAP - 1) / MAX_URLS_PER_SITEMAP
+}
+
+/// Site-relative path of a child sitemap (1-based)
+pub fn sitemap_page_path(page: i64) -> String {
+    format!("/sitemaps/{}.xml", page)
+}
+
+/// Render a `<urlset>` listing post permalinks
+pub fn render_urlset(site: &SiteConfig, links: &[PostLink]) -> String {
+    let mut xml = String::new();
+    write_urlset(&mut xml, site, links).expect("writing to a String cannot fail");
+    xml
+}
+
+/// Render a `<sitemapindex>` pointing at `pages` child sitemaps
+pub fn render_index(site: &SiteConfig, pages: i64) -> String {
+    let mut xml = String::new();
+    write_index(&mut xml, site, pages).expect("writing to a String cannot fail");
+    xml
+}
+
+fn write_urlset(xml: &mut String, site: &SiteConfig, links: &[PostLink]) -> fmt::Result {
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(xml, r#"<urlset xmlns="{}">"#, SITEMAP_NAMESPACE)?;
+    for link in links {
+        writeln!(xml, "<url>")?;
+        writeln!(xml, "<loc>{}</loc>", escape(&site.post_url(&link.slug)))?;
+        writeln!(xml, "<lastmod>{}</lastmod>", timestamp(link.last_modified))?;
+        writeln!(xml, "</url>")?;
+    }
+    writeln!(xml, "</urlset>")
+}
+
+fn write_index(xml: &mut String, site: &SiteConfig, pages: i64) -> fmt::Result {
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(xml, r#"<sitemapindex xmlns="{}">"#, SITEMAP_NAMESPACE)?;
+    for page in 1..=pages {
+        writeln!(xml, "<sitemap>")?;
+        writeln!(xml, "<loc>{}</loc>", escape(&site.url(&sitemap_page_path(page))))?;
+        writeln!(xml, "</sitemap>")?;
+    }
+    writeln!(xml, "</sitemapindex>")
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
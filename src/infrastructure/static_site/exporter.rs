@@ -0,0 +1,162 @@
+use crate::application::{PostPage, PostService};
+use crate::domain::{Post, PostLink};
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::{feed_path, FeedChannel, FeedFormat};
+use crate::infrastructure::seo::{
+    page_count, render_index, render_robots, render_urlset, sitemap_page_path, MAX_URLS_PER_SITEMAP,
+};
+use crate::infrastructure::static_site::output_dir::{ExportSummary, OutputDir};
+use crate::infrastructure::web::views::{
+    index_path, post_path, tag_path, IndexTemplate, NotFoundTemplate, PostTemplate, TagTemplate,
+};
+use askama::Template;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Number of posts fetched per query while walking the published posts
+const BATCH_SIZE: i64 = 500;
+
+/// Site-relative prefix media files are copied under
+const MEDIA_PREFIX: &str = "media";
+
+/// Renders the public site (pages, feeds, sitemap and media) into a directory
+/// Output only depends on the stored posts, so repeated exports produce identical files
+pub struct StaticSiteExporter {
+    post_service: Arc<PostService>,
+    site: Arc<SiteConfig>,
+}
+
+impl StaticSiteExporter {
+    pub fn new(post_service: Arc<PostService>, site: Arc<SiteConfig>) -> Self {
+        Self { post_service, site }
+    }
+
+    /// Export every published post into `out_dir`, rewriting only files whose content changed
+    pub async fn export(&self, out_dir: &Path) -> Result<ExportSummary, String> {
+        let posts = self.published_posts().await?;
+        let all: Vec<&Post> = posts.iter().collect();
+        let mut out = OutputDir::open(out_dir).map_err(io_error)?;
+
+        self.write_listing(&mut out, &all, None)?;
+        for (tag, tagged) in group_by_tag(&posts) {
+            self.write_listing(&mut out, &tagged, Some(tag))?;
+        }
+        for post in &posts {
+            let html = render(&PostTemplate::new(&self.site, post))?;
+            out.write(&html_file(&post_path(&post.slug)), html.as_bytes()).map_err(io_error)?;
+        }
+        let not_found = render(&NotFoundTemplate::new(&self.site))?;
+        out.write("404.html", not_found.as_bytes()).map_err(io_error)?;
+
+        self.write_sitemap(&mut out, &all)?;
+
+        if self.site.media_dir.is_dir() {
+            out.copy_dir(&self.site.media_dir, MEDIA_PREFIX).map_err(io_error)?;
+        }
+
+        out.finish().map_err(io_error)
+    }
+
+    async fn published_posts(&self) -> Result<Vec<Post>, String> {
+        let mut posts = Vec::new();
+        for page in 1.. {
+            let batch = self.post_service
+                .get_published_page(page, BATCH_SIZE, None)
+                .await
+                .map_err(|e| e.to_string())?;
+            posts.extend(batch.posts);
+            if !batch.has_next {
+                break;
+            }
+        }
+        Ok(posts)
+    }
+
+    /// Paginated HTML listing plus feeds, for the whole site or for one tag
+    fn write_listing(&self, out: &mut OutputDir, posts: &[&Post], tag: Option<&str>) -> Result<(), String> {
+        let page_size = self.site.page_size as usize;
+        let pages = posts.len().div_ceil(page_size).max(1);
+
+        for page in 1..=pages {
+            let start = ((page - 1) * page_size).min(posts.len());
+            let end = (page * page_size).min(posts.len());
+            let post_page = PostPage {
+                posts: posts[start..end].iter().map(|post| (*post).clone()).collect(),
+                page: page as i64,
+                has_next: page < pages,
+            };
+            let (path, html) = match tag {
+                Some(tag) => (tag_path(tag, post_page.page), render(&TagTemplate::new(&self.site, tag, &post_page))?),
+                None => (index_path(post_page.page), render(&IndexTemplate::new(&self.site, &post_page))?),
+            };
+            out.write(&html_file(&path), html.as_bytes()).map_err(io_error)?;
+        }
+
+        let latest: Vec<Post> = posts.iter().take(self.site.feed_size).map(|post| (*post).clone()).collect();
+        for format in FeedFormat::ALL {
+            let channel = match tag {
+                Some(tag) => FeedChannel::tag(&self.site, tag, format),
+                None => FeedChannel::site(&self.site, format),
+            };
+            let body = format.render(&self.site, &channel, &latest);
+            out.write(relative(&feed_path(tag, format)), body.as_bytes()).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    fn write_sitemap(&self, out: &mut OutputDir, posts: &[&Post]) -> Result<(), String> {
+        let links: Vec<PostLink> = posts
+            .iter()
+            .map(|post| PostLink {
+                slug: post.slug.clone(),
+                last_modified: post.published_at.map_or(post.updated_at, |p| p.max(post.updated_at)),
+            })
+            .collect();
+
+        let count = links.len() as i64;
+        if count > MAX_URLS_PER_SITEMAP {
+            out.write("sitemap.xml", render_index(&self.site, page_count(count)).as_bytes())
+                .map_err(io_error)?;
+            for (index, chunk) in links.chunks(MAX_URLS_PER_SITEMAP as usize).enumerate() {
+                let path = sitemap_page_path(index as i64 + 1);
+                out.write(relative(&path), render_urlset(&self.site, chunk).as_bytes())
+                    .map_err(io_error)?;
+            }
+        } else {
+            out.write("sitemap.xml", render_urlset(&self.site, &links).as_bytes())
+                .map_err(io_error)?;
+        }
+
+        out.write("robots.txt", render_robots(&self.site).as_bytes()).map_err(io_error)
+    }
+}
+
+/// Posts of each tag, keeping the newest-first order; tags iterate alphabetically
+fn group_by_tag(posts: &[Post]) -> BTreeMap<&str, Vec<&Post>> {
+    let mut tags: BTreeMap<&str, Vec<&Post>> = BTreeMap::new();
+    for post in posts {
+        for tag in &post.tags {
+            tags.entry(tag).or_default().push(post);
+        }
+    }
+    tags
+}
+
+/// Directory-style file for a page route, so `/blog/slug` is served from `blog/slug/index.html`
+fn html_file(path: &str) -> String {
+    let path = relative(path);
+    if path.is_empty() { "index.html".to_string() } else { format!("{}/index.html", path) }
+}
+
+fn relative(path: &str) -> &str {
+    path.trim_start_matches('/')
+}
+
+fn render(template: &impl Template) -> Result<String, String> {
+    template.render().map_err(|e| format!("Template error: {}", e))
+}
+
+fn io_error(error: std::io::Error) -> String {
+    format!("I/O error: {}", error)
+}
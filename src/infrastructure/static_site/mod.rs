@@ -0,0 +1,5 @@
+pub mod exporter;
+pub mod output_dir;
+
+pub use exporter::StaticSiteExporter;
+pub use output_dir::{ExportSummary, OutputDir};
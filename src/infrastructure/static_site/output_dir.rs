@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File listing everything the previous export produced, used to prune stale files
+pub const MANIFEST_FILE: &str = ".export-manifest";
+
+/// Counts reported once an export finishes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportSummary {
+    /// Files created or whose content changed
+    pub written: usize,
+    /// Files left untouched because their content was identical
+    pub unchanged: usize,
+    /// Files from the previous export that are no longer produced
+    pub removed: usize,
+}
+
+/// Output directory that only rewrites files whose content changed
+pub struct OutputDir {
+    root: PathBuf,
+    files: BTreeSet<String>,
+    summary: ExportSummary,
+}
+
+impl OutputDir {
+    pub fn open(root: &Path) -> io::Result<Self> {
+        fs::create_dir_all(root)?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            files: BTreeSet::new(),
+            summary: ExportSummary::default(),
+        })
+    }
+
+    /// Write `contents` to a path relative to the output root, skipping identical files
+    pub fn write(&mut self, path: &str, contents: &[u8]) -> io::Result<()> {
+        let target = self.root.join(path);
+        if fs::read(&target).is_ok_and(|existing| existing == contents) {
+            self.summary.unchanged += 1;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, contents)?;
+            self.summary.written += 1;
+        }
+        self.files.insert(path.to_string());
+        Ok(())
+    }
+
+    /// Copy every file below `source` into `prefix`, in a stable order
+    pub fn copy_dir(&mut self, source: &Path, prefix: &str) -> io::Result<()> {
+        let mut entries = fs::read_dir(source)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = format!("{}/{}", prefix, name);
+            if entry.file_type()?.is_dir() {
+                self.copy_dir(&entry.path(), &path)?;
+            } else {
+                self.write(&path, &fs::read(entry.path())?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete files the previous export produced but this one did not, then record the new manifest
+    pub fn finish(mut self) -> io::Result<ExportSummary> {
+        let manifest = self.root.join(MANIFEST_FILE);
+        let previous = fs::read_to_string(&manifest).unwrap_or_default();
+
+        // Only relative paths inside the root are ever recorded; ignore anything else
+        let stale_files = previous
+            .lines()
+            .filter(|path| !path.starts_with('/') && !path.split('/').any(|part| part == ".."))
+            .filter(|path| !self.files.contains(*path));
+
+        for stale in stale_files {
+            let target = self.root.join(stale);
+            match fs::remove_file(&target) {
+                Ok(()) => {
+                    self.summary.removed += 1;
+                    self.remove_empty_parents(&target);
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let listing: String = self.files.iter().map(|path| format!("{}\n", path)).collect();
+        if previous != listing {
+            fs::write(&manifest, listing)?;
+        }
+        Ok(self.summary)
+    }
+
+    fn remove_empty_parents(&self, file: &Path) {
+        let mut dir = file.parent();
+        while let Some(current) = dir.filter(|d| *d != self.root) {
+            // Fails (and stops) as soon as a directory still has content
+            if fs::remove_dir(current).is_err() {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+}
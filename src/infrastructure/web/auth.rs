@@ -0,0 +1,326 @@
+use crate::application::{
+    Actor, AuthService, JwtService, LoginClient, PendingLogin, PendingSecondFactor, TokenService, TwoFactorService,
+};
+use crate::domain::{Principal, Session, User};
+use crate::infrastructure::config::{SessionConfig, SESSION_COOKIE};
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::{Cookie, CookieJar, SameSite};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::{header, Method};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpMessage, HttpRequest};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const REALM: &str = "blog-rust";
+
+/// Cookie carrying a single sign-on login between the redirect to the provider and its callback
+const PENDING_LOGIN_COOKIE: &str = "blog_oidc_login";
+
+/// Time allowed for signing in at the provider
+const PENDING_LOGIN_TTL: Duration = Duration::minutes(10);
+
+/// Cookie carrying a single sign-on login from the provider's callback to the second factor
+const PENDING_SECOND_FACTOR_COOKIE: &str = "blog_oidc_two_factor";
+
+/// Routes left open to users who must enroll in two-factor authentication but have not yet
+const TWO_FACTOR_ENROLLMENT_PATHS: &[&str] = &["/auth/two-factor", "/auth/two-factor/enrollment", "/auth/two-factor/enrollment/confirm"];
+
+/// Writes that establish or end a session or access token, and so cannot require one
+const PUBLIC_WRITES: &[&str] = &[
+    "/auth/login",
+    "/auth/logout",
+    "/auth/token",
+    "/auth/token/refresh",
+    "/auth/token/revoke",
+    "/auth/oidc/two-factor",
+];
+
+/// Middleware requiring credentials on requests that change state; reads stay public
+pub async fn require_auth_for_writes<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let required = !matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS)
+        && !PUBLIC_WRITES.contains(&req.path());
+    guard(req, next, required).await
+}
+
+/// Middleware requiring credentials on every request, for reads that expose drafts or credentials
+pub async fn require_auth<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    guard(req, next, true).await
+}
+
+/// The authenticated [`Principal`] is stored in the request extensions for the handlers
+async fn guard<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+    required: bool,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    if required {
+        let identified = req.extensions().get::<Principal>().cloned();
+        let authenticated = match identified {
+            Some(principal) => Ok(principal),
+            None => authenticate(&req).await,
+        };
+        let checked = match authenticated {
+            Ok(principal) => check_enrollment(&req, &principal).await.map(|()| principal),
+            Err(error) => Err(error),
+        };
+        match checked {
+            Ok(principal) => {
+                req.extensions_mut().insert(principal);
+            }
+            // Rendered here rather than returned, so the problem details middleware sees the error
+            Err(error) => return Ok(req.error_response(error).map_into_right_body()),
+        }
+    } else if let Some(principal) = identify(&req).await {
+        // Public reads still depend on who makes them, since drafts are only shown to their author and editors
+        if check_enrollment(&req, &principal).await.is_err() {
+            req.extensions_mut().remove::<Principal>();
+        }
+    }
+    next.call(req).await.map(ServiceResponse::map_into_left_body)
+}
+
+/// Who a request acts for, if it carries valid credentials; never rejects it
+/// The principal is remembered, so the auth middleware does not look the credentials up again
+pub(crate) async fn identify(req: &ServiceRequest) -> Option<Principal> {
+    if let Some(principal) = req.extensions().get::<Principal>() {
+        return Some(principal.clone());
+    }
+    if bearer_token(req).is_none() && req.cookie(SESSION_COOKIE).is_none() {
+        return None;
+    }
+
+    let principal = authenticate(req).await.ok()?;
+    req.extensions_mut().insert(principal.clone());
+    Some(principal)
+}
+
+/// A bearer token wins over a session cookie when a request carries both
+async fn authenticate(req: &ServiceRequest) -> Result<Principal, ApiError> {
+    if let Some(secret) = bearer_token(req) {
+        // JWTs are three dot-separated segments; API tokens contain no dots
+        if secret.split('.').count() == 3 {
+            let jwt = req
+                .app_data::<web::Data<JwtService>>()
+                .ok_or_else(|| ApiError::internal("JWT service is not configured"))?;
+
+            return jwt
+                .authenticate(secret)
+                .map(Principal::Jwt)
+                .ok_or_else(|| invalid_token("The access token is invalid or expired"));
+        }
+
+        let tokens = req
+            .app_data::<web::Data<TokenService>>()
+            .ok_or_else(|| ApiError::internal("Token service is not configured"))?;
+
+        return tokens
+            .authenticate(secret)
+            .await
+            .map_err(ApiError::from)?
+            .map(Principal::Token)
+            .ok_or_else(|| invalid_token("The token is invalid, expired or revoked"));
+    }
+
+    let challenge = format!("Bearer realm=\"{}\"", REALM);
+    let config = req
+        .app_data::<web::Data<SessionConfig>>()
+        .ok_or_else(|| ApiError::internal("Sessions are not configured"))?;
+    let Some(secret) = req.cookie(SESSION_COOKIE).and_then(|cookie| session_secret(config, cookie)) else {
+        return Err(ApiError::unauthorized("A bearer token or session cookie is required", challenge));
+    };
+
+    let auth = req
+        .app_data::<web::Data<AuthService>>()
+        .ok_or_else(|| ApiError::internal("Auth service is not configured"))?;
+    auth.authenticate_session(&secret)
+        .await
+        .map_err(ApiError::from)?
+        .map(|(user, session)| Principal::User { user: Box::new(user), session })
+        .ok_or_else(|| ApiError::unauthorized("The session has expired or was revoked", challenge))
+}
+
+/// Sessions of users who must enroll in two-factor authentication only reach the enrollment routes
+async fn check_enrollment(req: &ServiceRequest, principal: &Principal) -> Result<(), ApiError> {
+    let Principal::User { user, .. } = principal else {
+        return Ok(());
+    };
+    if TWO_FACTOR_ENROLLMENT_PATHS.contains(&req.path()) {
+        return Ok(());
+    }
+
+    let two_factor = req
+        .app_data::<web::Data<TwoFactorService>>()
+        .ok_or_else(|| ApiError::internal("Two-factor service is not configured"))?;
+    if two_factor.enrollment_missing(user).await.map_err(ApiError::from)? {
+        return Err(ApiError::forbidden(
+            "Your role requires two-factor authentication; enroll at /auth/two-factor/enrollment first",
+        ));
+    }
+    Ok(())
+}
+
+fn invalid_token(detail: &str) -> ApiError {
+    ApiError::unauthorized(detail, format!("Bearer realm=\"{}\", error=\"invalid_token\"", REALM))
+}
+
+/// Credentials of an `Authorization: Bearer <token>` header; the scheme is case-insensitive
+fn bearer_token(req: &ServiceRequest) -> Option<&str> {
+    let value = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme.eq_ignore_ascii_case("bearer").then(|| token.trim()).filter(|token| !token.is_empty())
+}
+
+/// Who the request acts for; always set behind an auth middleware, elsewhere only when it carries valid credentials
+pub fn principal(req: &HttpRequest) -> Option<Principal> {
+    req.extensions().get::<Principal>().cloned()
+}
+
+/// Who the policies should check the request against; routes behind an auth middleware always have one
+pub fn actor(req: &HttpRequest) -> Result<Actor, ApiError> {
+    req.extensions()
+        .get::<Principal>()
+        .map(Actor::from)
+        .ok_or_else(|| ApiError::internal("The route is not protected by an auth middleware"))
+}
+
+/// Where a login came from, for the session list
+pub fn login_client(req: &HttpRequest) -> LoginClient {
+    LoginClient {
+        user_agent: req
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        // The socket address, not `X-Forwarded-For`, which clients can forge
+        ip_address: req.peer_addr().map(|addr| addr.ip().to_string()),
+    }
+}
+
+/// The user and session of a request made from a signed-in session; `what` names what API and access tokens cannot manage
+pub fn signed_in(req: &HttpRequest, what: &str) -> Result<(User, Session), ApiError> {
+    match principal(req) {
+        Some(Principal::User { user, session }) => Ok((*user, session)),
+        Some(Principal::Token(_) | Principal::Jwt(_)) => {
+            Err(ApiError::forbidden(format!("{} can only be managed from a signed-in session", what)))
+        }
+        None => Err(ApiError::internal("The route is not protected by an auth middleware")),
+    }
+}
+
+/// Signed, HttpOnly session cookie carrying `secret`
+pub fn session_cookie(config: &SessionConfig, secret: String) -> Cookie<'static> {
+    let cookie = Cookie::build(SESSION_COOKIE, secret)
+        .path("/")
+        .http_only(true)
+        .secure(config.secure)
+        .same_site(SameSite::Strict)
+        .max_age(Duration::seconds(config.ttl.num_seconds()))
+        .finish();
+    sign(config, cookie)
+}
+
+/// Signed cookie remembering a single sign-on login until the provider redirects back
+/// `Lax`, unlike the session cookie, because the callback is a navigation from the provider's site
+pub fn pending_login_cookie(config: &SessionConfig, pending: &PendingLogin) -> Cookie<'static> {
+    let cookie = Cookie::build(PENDING_LOGIN_COOKIE, encode_json(pending))
+        .path("/auth/oidc")
+        .http_only(true)
+        .secure(config.secure)
+        .same_site(SameSite::Lax)
+        .max_age(PENDING_LOGIN_TTL)
+        .finish();
+    sign(config, cookie)
+}
+
+/// The single sign-on login started by this browser, if its cookie is intact
+pub fn pending_login(config: &SessionConfig, req: &HttpRequest) -> Option<PendingLogin> {
+    signed_json(config, req, PENDING_LOGIN_COOKIE)
+}
+
+/// Cookie telling the browser to forget a single sign-on login, used or not
+pub fn pending_login_removal(config: &SessionConfig) -> Cookie<'static> {
+    oidc_cookie_removal(config, PENDING_LOGIN_COOKIE, SameSite::Lax)
+}
+
+/// Signed cookie remembering a single sign-on login until the second factor is entered
+/// `Strict`, since the code is posted from a page of this site
+pub fn pending_second_factor_cookie(config: &SessionConfig, pending: &PendingSecondFactor) -> Cookie<'static> {
+    let remaining = (pending.expires_at - chrono::Utc::now()).num_seconds().max(0);
+    let cookie = Cookie::build(PENDING_SECOND_FACTOR_COOKIE, encode_json(pending))
+        .path("/auth/oidc")
+        .http_only(true)
+        .secure(config.secure)
+        .same_site(SameSite::Strict)
+        .max_age(Duration::seconds(remaining))
+        .finish();
+    sign(config, cookie)
+}
+
+/// The single sign-on login waiting for a second factor in this browser, if its cookie is intact
+pub fn pending_second_factor(config: &SessionConfig, req: &HttpRequest) -> Option<PendingSecondFactor> {
+    signed_json(config, req, PENDING_SECOND_FACTOR_COOKIE)
+}
+
+/// Cookie telling the browser to forget a login that waited for a second factor
+pub fn pending_second_factor_removal(config: &SessionConfig) -> Cookie<'static> {
+    oidc_cookie_removal(config, PENDING_SECOND_FACTOR_COOKIE, SameSite::Strict)
+}
+
+fn encode_json(value: &impl Serialize) -> String {
+    serde_json::to_vec(value).map(|json| URL_SAFE_NO_PAD.encode(json)).unwrap_or_default()
+}
+
+/// Value of a signed cookie holding base64 JSON, if the signature is valid
+fn signed_json<T: DeserializeOwned>(config: &SessionConfig, req: &HttpRequest, name: &str) -> Option<T> {
+    let mut jar = CookieJar::new();
+    jar.add_original(req.cookie(name)?);
+    let value = jar.signed(&config.key).get(name)?.value().to_string();
+    serde_json::from_slice(&URL_SAFE_NO_PAD.decode(value).ok()?).ok()
+}
+
+fn oidc_cookie_removal(config: &SessionConfig, name: &'static str, same_site: SameSite) -> Cookie<'static> {
+    let mut cookie = Cookie::build(name, "")
+        .path("/auth/oidc")
+        .http_only(true)
+        .secure(config.secure)
+        .same_site(same_site)
+        .finish();
+    cookie.make_removal();
+    cookie
+}
+
+fn sign(config: &SessionConfig, cookie: Cookie<'static>) -> Cookie<'static> {
+    let mut jar = CookieJar::new();
+    jar.signed_mut(&config.key).add(cookie);
+    jar.delta().next().cloned().expect("a cookie was just added")
+}
+
+/// Cookie telling the browser to forget its session
+pub fn removal_cookie(config: &SessionConfig) -> Cookie<'static> {
+    let mut cookie = Cookie::build(SESSION_COOKIE, "")
+        .path("/")
+        .http_only(true)
+        .secure(config.secure)
+        .same_site(SameSite::Strict)
+        .finish();
+    cookie.make_removal();
+    cookie
+}
+
+/// Secret carried by a session cookie, if its signature is valid
+pub fn session_secret(config: &SessionConfig, cookie: Cookie<'static>) -> Option<String> {
+    let mut jar = CookieJar::new();
+    jar.add_original(cookie);
+    jar.signed(&config.key).get(SESSION_COOKIE).map(|cookie| cookie.value().to_string())
+}
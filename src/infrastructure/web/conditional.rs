@@ -0,0 +1,56 @@
+use actix_web::http::header::{EntityTag, ETag, HttpDate, IfModifiedSince, IfNoneMatch, LastModified};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::time::SystemTime;
+
+/// Respond with `body`, or `304 Not Modified` when the client's cached copy is still current
+/// `If-None-Match` takes precedence over `If-Modified-Since`, as required by RFC 9110
+pub fn conditional_response(
+    req: &HttpRequest,
+    content_type: &str,
+    body: String,
+    last_modified: Option<DateTime<Utc>>,
+) -> HttpResponse {
+    let etag = EntityTag::new_strong(content_hash(&body));
+    // HTTP dates only carry whole seconds
+    let last_modified = last_modified
+        .and_then(|at| DateTime::from_timestamp(at.timestamp(), 0))
+        .map(|at| HttpDate::from(SystemTime::from(at)));
+
+    let not_modified = match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => match (req.get_header::<IfModifiedSince>(), last_modified) {
+            (Some(IfModifiedSince(since)), Some(modified)) => modified <= since,
+            _ => false,
+        },
+    };
+
+    let mut builder = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    builder.insert_header(ETag(etag));
+    if let Some(modified) = last_modified {
+        builder.insert_header(LastModified(modified));
+    }
+
+    if not_modified {
+        builder.finish()
+    } else {
+        builder.content_type(content_type).body(body)
+    }
+}
+
+/// Bytes of the SHA-256 digest kept in an ETag; 128 bits is plenty to tell representations apart
+const ETAG_BYTES: usize = 16;
+
+/// Hex-encoded, truncated SHA-256 of the body, stable across builds and Rust versions unlike `DefaultHasher`
+fn content_hash(body: &str) -> String {
+    Sha256::digest(body.as_bytes())[..ETAG_BYTES]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -0,0 +1,33 @@
+use crate::infrastructure::config::CorsConfig;
+use actix_cors::Cors;
+
+/// Response headers browser apps on other origins may read
+const EXPOSED_HEADERS: &[&str] = &[
+    "etag",
+    "x-request-id",
+    "location",
+    "retry-after",
+    "www-authenticate",
+    "ratelimit-limit",
+    "ratelimit-remaining",
+    "ratelimit-reset",
+    "ratelimit-policy",
+];
+
+/// CORS middleware for the configured origins; requests from other origins get no CORS headers, so browsers block them
+pub fn cors(config: &CorsConfig) -> Cors {
+    let mut cors = Cors::default()
+        .allowed_methods(config.allowed_methods.iter().map(String::as_str))
+        .allowed_headers(config.allowed_headers.iter().map(String::as_str))
+        .expose_headers(EXPOSED_HEADERS.iter().copied())
+        .max_age(config.max_age_seconds)
+        // Browsers send `Origin` on same-origin writes too, which must keep working
+        .block_on_origin_mismatch(false);
+    for origin in &config.allowed_origins {
+        cors = if origin == "*" { cors.allow_any_origin() } else { cors.allowed_origin(origin) };
+    }
+    if config.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+    cors
+}
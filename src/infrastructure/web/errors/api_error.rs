@@ -0,0 +1,202 @@
+use crate::application::ServiceError;
+use crate::domain::ValidationErrors;
+use actix_web::http::{header, StatusCode};
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// RFC 7807 problem details document
+#[derive(Debug, Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Problem-specific extension members (e.g. `violations`)
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl ProblemDetails {
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    pub fn to_response(&self) -> HttpResponse {
+        HttpResponse::build(StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+            .content_type(PROBLEM_JSON)
+            .json(self)
+    }
+}
+
+/// Error returned by HTTP handlers, rendered as `application/problem+json`
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    problem_type: &'static str,
+    title: String,
+    detail: String,
+    extensions: Map<String, Value>,
+    /// `WWW-Authenticate` challenge sent with `401 Unauthorized`
+    challenge: Option<String>,
+}
+
+impl ApiError {
+    /// Generic problem for a status code, using `about:blank` and the status reason phrase as title
+    pub fn from_status(status: StatusCode, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            problem_type: "about:blank",
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            detail: detail.into(),
+            extensions: Map::new(),
+            challenge: None,
+        }
+    }
+
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::from_status(StatusCode::BAD_REQUEST, detail)
+    }
+
+    /// Missing or rejected credentials; `challenge` tells the client which scheme to use
+    pub fn unauthorized(detail: impl Into<String>, challenge: impl Into<String>) -> Self {
+        Self {
+            challenge: Some(challenge.into()),
+            ..Self::from_status(StatusCode::UNAUTHORIZED, detail)
+        }
+    }
+
+    /// Authenticated, but not allowed to do this
+    pub fn forbidden(detail: impl Into<String>) -> Self {
+        Self::from_status(StatusCode::FORBIDDEN, detail)
+    }
+
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::from_status(StatusCode::NOT_FOUND, detail)
+    }
+
+    pub fn internal(detail: impl Into<String>) -> Self {
+        Self::from_status(StatusCode::INTERNAL_SERVER_ERROR, detail)
+    }
+
+    pub fn validation(errors: ValidationErrors) -> Self {
+        let mut extensions = Map::new();
+        extensions.insert(
+            "violations".to_string(),
+            serde_json::to_value(&errors).unwrap_or(Value::Null),
+        );
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            problem_type: "/problems/validation-error",
+            title: "Validation failed".to_string(),
+            detail: "One or more fields are invalid".to_string(),
+            extensions,
+            challenge: None,
+        }
+    }
+
+    /// The password was right, but the account needs a second factor too; the client should ask for it and retry
+    pub fn two_factor_required(detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            problem_type: "/problems/two-factor-required",
+            title: "Two-factor code required".to_string(),
+            detail: detail.into(),
+            extensions: Map::new(),
+            challenge: None,
+        }
+    }
+
+    /// An all-or-nothing batch failed; `status` is the failing operation's and `results` holds every operation's
+    pub fn batch_rolled_back(status: StatusCode, failed_operation: usize, results: Value) -> Self {
+        let mut extensions = Map::new();
+        extensions.insert("failed_operation".to_string(), Value::from(failed_operation));
+        extensions.insert("results".to_string(), results);
+        Self {
+            status,
+            problem_type: "/problems/batch-rolled-back",
+            title: "Batch rolled back".to_string(),
+            detail: format!("Operation {} failed; no changes were applied", failed_operation),
+            extensions,
+            challenge: None,
+        }
+    }
+
+    /// A readiness check failed; `checks` holds every check's result
+    pub fn not_ready(failed: &[String], checks: Value) -> Self {
+        let mut extensions = Map::new();
+        extensions.insert("checks".to_string(), checks);
+        Self {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            problem_type: "/problems/not-ready",
+            title: "Not ready".to_string(),
+            detail: format!("Readiness checks failed: {}", failed.join("; ")),
+            extensions,
+            challenge: None,
+        }
+    }
+
+    pub fn problem(&self) -> ProblemDetails {
+        ProblemDetails {
+            problem_type: self.problem_type.to_string(),
+            title: self.title.clone(),
+            status: self.status.as_u16(),
+            detail: self.detail.clone(),
+            instance: None,
+            extensions: self.extensions.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.title, self.detail)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        // Client errors are the client's to fix; server errors need someone to look at them
+        if self.status.is_server_error() {
+            tracing::error!(status = self.status.as_u16(), detail = %self.detail, "{}", self.title);
+        }
+        let mut response = self.problem().to_response();
+        if let Some(challenge) = self.challenge.as_deref().and_then(|c| header::HeaderValue::from_str(c).ok()) {
+            response.headers_mut().insert(header::WWW_AUTHENTICATE, challenge);
+        }
+        response
+    }
+}
+
+impl From<ServiceError> for ApiError {
+    fn from(error: ServiceError) -> Self {
+        match error {
+            ServiceError::Validation(errors) => ApiError::validation(errors),
+            ServiceError::InvalidInput(message) => ApiError::bad_request(message),
+            ServiceError::NotFound(message) => ApiError::not_found(message),
+            // A failed login is not a request for credentials, so no challenge is sent
+            ServiceError::Unauthenticated(message) => ApiError::from_status(StatusCode::UNAUTHORIZED, message),
+            ServiceError::Forbidden(message) => ApiError::forbidden(message),
+            ServiceError::TwoFactorRequired(message) => ApiError::two_factor_required(message),
+            ServiceError::Locked(message) => ApiError::from_status(StatusCode::LOCKED, message),
+            ServiceError::Conflict(message) => ApiError::from_status(StatusCode::CONFLICT, message),
+            // Database errors can reveal the schema, so they are only logged
+            ServiceError::Repository(message) => {
+                tracing::error!(error = %message, "repository error");
+                ApiError::internal("The server could not complete the request")
+            }
+            ServiceError::Upstream(message) => ApiError::from_status(StatusCode::BAD_GATEWAY, message),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::infrastructure::web::errors::api_error::{ApiError, PROBLEM_JSON};
+use actix_web::dev::ServiceResponse;
+use actix_web::http::header;
+use actix_web::middleware::{ErrorHandlerResponse, ErrorHandlers};
+use actix_web::Result;
+
+/// Middleware that turns every error response into `application/problem+json`
+/// Covers handler errors as well as actix's own extractor, routing and method failures
+pub fn problem_details<B: 'static>() -> ErrorHandlers<B> {
+    ErrorHandlers::new().default_handler(render_problem)
+}
+
+fn render_problem<B>(res: ServiceResponse<B>) -> Result<ErrorHandlerResponse<B>> {
+    // HTML error pages rendered for browsers are already final
+    if res.response().error().is_none() && is_html(&res) {
+        return Ok(ErrorHandlerResponse::Response(res.map_into_left_body()));
+    }
+
+    let status = res.status();
+    let api_error = match res.response().error() {
+        Some(error) => match error.as_error::<ApiError>() {
+            Some(api_error) => api_error.problem(),
+            None => ApiError::from_status(status, error.to_string()).problem(),
+        },
+        None => ApiError::from_status(status, default_detail(&res)).problem(),
+    };
+
+    let (request, response) = res.into_parts();
+    let problem = api_error.with_instance(request.path());
+
+    // Keep headers such as `Allow` or `Retry-After` set by the original response
+    let mut rendered = problem.to_response();
+    for (name, value) in response.headers() {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            rendered.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+    rendered.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static(PROBLEM_JSON),
+    );
+
+    Ok(ErrorHandlerResponse::Response(
+        ServiceResponse::new(request, rendered).map_into_right_body(),
+    ))
+}
+
+fn is_html<B>(res: &ServiceResponse<B>) -> bool {
+    res.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/html"))
+}
+
+fn default_detail<B>(res: &ServiceResponse<B>) -> String {
+    let request = res.request();
+    match res.status().as_u16() {
+        404 => format!("No resource found at {}", request.path()),
+        405 => format!("Method {} is not allowed on {}", request.method(), request.path()),
+        _ => res
+            .status()
+            .canonical_reason()
+            .unwrap_or("Unexpected error")
+            .to_string(),
+    }
+}
+
@@ -0,0 +1,5 @@
+pub mod api_error;
+pub mod error_handlers;
+
+pub use api_error::{ApiError, ProblemDetails, PROBLEM_JSON};
+pub use error_handlers::problem_details;
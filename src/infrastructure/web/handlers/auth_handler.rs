@@ -0,0 +1,78 @@
+use crate::application::AuthService;
+use crate::infrastructure::config::{SessionConfig, SESSION_COOKIE};
+use crate::infrastructure::web::auth::{login_client, removal_cookie, session_cookie, session_secret, signed_in};
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{LoginRequest, LoginResponse, SessionResponse, UserResponse};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+/// HTTP handlers for password logins and the sessions they open
+#[derive(Clone)]
+pub struct AuthHandler {
+    auth_service: Arc<AuthService>,
+    session_config: Arc<SessionConfig>,
+}
+
+impl AuthHandler {
+    pub fn new(auth_service: Arc<AuthService>, session_config: Arc<SessionConfig>) -> Self {
+        Self { auth_service, session_config }
+    }
+
+    /// POST /auth/login - Check a password and set the session cookie
+    pub async fn login(&self, req: HttpRequest, login_data: web::Json<LoginRequest>) -> Result<HttpResponse> {
+        let request = login_data.into_inner();
+
+        let logged_in = self.auth_service
+            .login(&request.email, &request.password, request.code.as_deref(), login_client(&req))
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(HttpResponse::Ok()
+            .cookie(session_cookie(&self.session_config, logged_in.secret))
+            .json(LoginResponse {
+                user: UserResponse::from(logged_in.user),
+                session: SessionResponse::new(logged_in.session, true),
+            }))
+    }
+
+    /// POST /auth/logout - Revoke the current session, if any, and clear the cookie
+    pub async fn logout(&self, req: HttpRequest) -> Result<HttpResponse> {
+        if let Some(secret) = req.cookie(SESSION_COOKIE).and_then(|cookie| session_secret(&self.session_config, cookie)) {
+            self.auth_service.logout(&secret).await.map_err(ApiError::from)?;
+        }
+        Ok(HttpResponse::NoContent().cookie(removal_cookie(&self.session_config)).finish())
+    }
+
+    /// GET /auth/sessions - List the active sessions of the signed-in user
+    pub async fn list_sessions(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let (user, current) = signed_in(&req, "Sessions")?;
+        let user_id = user.id.unwrap_or(0);
+
+        let sessions = self.auth_service.list_sessions(user_id).await.map_err(ApiError::from)?;
+        let responses: Vec<SessionResponse> = sessions
+            .into_iter()
+            .map(|session| {
+                let is_current = session.id == current.id;
+                SessionResponse::new(session, is_current)
+            })
+            .collect();
+        Ok(HttpResponse::Ok().json(responses))
+    }
+
+    /// DELETE /auth/sessions/{id} - Revoke one of the signed-in user's sessions
+    pub async fn revoke_session(&self, req: HttpRequest, path: web::Path<i32>) -> Result<HttpResponse> {
+        let session_id = path.into_inner();
+        let (user, current) = signed_in(&req, "Sessions")?;
+
+        self.auth_service
+            .revoke_session(user.id.unwrap_or(0), session_id)
+            .await
+            .map_err(ApiError::from)?;
+
+        let mut response = HttpResponse::NoContent();
+        if current.id == Some(session_id) {
+            response.cookie(removal_cookie(&self.session_config));
+        }
+        Ok(response.finish())
+    }
+}
@@ -0,0 +1,50 @@
+use crate::application::post_policy;
+use crate::infrastructure::interchange::{MarkdownExporter, NdjsonExporter};
+use crate::infrastructure::web::auth::actor;
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{HttpRequest, HttpResponse, Result};
+use chrono::Utc;
+use std::sync::Arc;
+
+/// HTTP handlers for full content exports
+/// The export policy is checked before anything is read, so a denied export produces no partial output
+#[derive(Clone)]
+pub struct ExportHandler {
+    markdown_exporter: Arc<MarkdownExporter>,
+    ndjson_exporter: Arc<NdjsonExporter>,
+}
+
+impl ExportHandler {
+    pub fn new(markdown_exporter: Arc<MarkdownExporter>, ndjson_exporter: Arc<NdjsonExporter>) -> Self {
+        Self { markdown_exporter, ndjson_exporter }
+    }
+
+    /// GET /export - Zip of every post as Markdown with front matter, plus referenced media
+    pub async fn export_markdown(&self, req: HttpRequest) -> Result<HttpResponse> {
+        post_policy::can_export(&actor(&req)?).map_err(ApiError::from)?;
+
+        let archive = self.markdown_exporter
+            .export_zip()
+            .await
+            .map_err(ApiError::internal)?;
+
+        let file_name = format!("blog-export-{}.zip", Utc::now().format("%Y-%m-%d"));
+        Ok(HttpResponse::Ok()
+            .content_type("application/zip")
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(file_name)],
+            })
+            .body(archive))
+    }
+
+    /// GET /posts/export.ndjson - Every post, drafts included, streamed as one JSON object per line
+    pub async fn export_ndjson(&self, req: HttpRequest) -> Result<HttpResponse> {
+        post_policy::can_export(&actor(&req)?).map_err(ApiError::from)?;
+
+        Ok(HttpResponse::Ok()
+            .content_type("application/x-ndjson")
+            .streaming(self.ndjson_exporter.export()))
+    }
+}
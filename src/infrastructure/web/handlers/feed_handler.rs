@@ -0,0 +1,71 @@
+use crate::application::PostService;
+use crate::domain::Post;
+use crate::domain::validation::rules::is_valid_slug;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::{discovery_link_header, last_updated, FeedChannel, FeedFormat};
+use crate::infrastructure::web::conditional::conditional_response;
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+/// HTTP handlers for syndication feeds
+/// Every format is rendered from the same published-posts query
+#[derive(Clone)]
+pub struct FeedHandler {
+    post_service: Arc<PostService>,
+    site: Arc<SiteConfig>,
+}
+
+impl FeedHandler {
+    pub fn new(post_service: Arc<PostService>, site: Arc<SiteConfig>) -> Self {
+        Self { post_service, site }
+    }
+
+    /// GET /feed.rss, /feed.atom, /feed.json - Feed of the latest published posts
+    pub async fn site_feed(&self, req: HttpRequest, format: FeedFormat) -> Result<HttpResponse> {
+        let posts = self.post_service
+            .get_published_posts(self.site.feed_size, None)
+            .await
+            .map_err(ApiError::from)?;
+
+        let channel = FeedChannel::site(&self.site, format);
+        let body = format.render(&self.site, &channel, &posts);
+        Ok(self.respond(&req, format, body, &posts, None))
+    }
+
+    /// GET /tags/{slug}/feed.{rss,atom,json} - Feed of the latest published posts with a tag
+    pub async fn tag_feed(&self, req: HttpRequest, path: web::Path<String>, format: FeedFormat) -> Result<HttpResponse> {
+        let tag = path.into_inner();
+        if !is_valid_slug(&tag) {
+            return Err(ApiError::not_found("Tag not found").into());
+        }
+
+        let posts = self.post_service
+            .get_published_posts(self.site.feed_size, Some(&tag))
+            .await
+            .map_err(ApiError::from)?;
+        if posts.is_empty() {
+            return Err(ApiError::not_found("Tag not found").into());
+        }
+
+        let channel = FeedChannel::tag(&self.site, &tag, format);
+        let body = format.render(&self.site, &channel, &posts);
+        Ok(self.respond(&req, format, body, &posts, Some(&tag)))
+    }
+
+    fn respond(
+        &self,
+        req: &HttpRequest,
+        format: FeedFormat,
+        body: String,
+        posts: &[Post],
+        tag: Option<&str>,
+    ) -> HttpResponse {
+        let mut response = conditional_response(req, &format.content_type(), body, last_updated(posts));
+        if let Ok(links) = HeaderValue::from_str(&discovery_link_header(&self.site, tag)) {
+            response.headers_mut().insert(header::LINK, links);
+        }
+        response
+    }
+}
@@ -0,0 +1,50 @@
+use crate::infrastructure::database::{check_database, DbPool};
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{HealthCheckResponse, HealthResponse};
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{HttpResponse, Result};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// HTTP handlers for the liveness and readiness probes
+#[derive(Clone)]
+pub struct HealthHandler {
+    pool: Arc<DbPool>,
+}
+
+impl HealthHandler {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// GET /health/live - The process is up and answering; dependencies are not checked
+    pub async fn live(&self) -> Result<HttpResponse> {
+        Ok(HttpResponse::Ok()
+            .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+            .json(HealthResponse::pass(Vec::new())))
+    }
+
+    /// GET /health/ready - Whether the database answers and is fully migrated
+    /// Any failed check makes it a 503 problem listing every check, so the instance gets no traffic
+    pub async fn ready(&self) -> Result<HttpResponse> {
+        let pool = Arc::clone(&self.pool);
+        let checks = tokio::task::spawn_blocking(move || check_database(&pool))
+            .await
+            .map_err(|e| ApiError::internal(format!("Task error: {}", e)))?;
+
+        let failed: Vec<String> = checks
+            .iter()
+            .filter_map(|check| check.error.as_ref().map(|error| format!("{} ({})", check.name, error)))
+            .collect();
+        let checks: Vec<HealthCheckResponse> = checks.into_iter().map(HealthCheckResponse::from).collect();
+        if !failed.is_empty() {
+            let checks = serde_json::to_value(&checks).unwrap_or(Value::Null);
+            return Err(ApiError::not_ready(&failed, checks).into());
+        }
+
+        Ok(HttpResponse::Ok()
+            .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+            .json(HealthResponse::pass(checks)))
+    }
+}
+
@@ -0,0 +1,47 @@
+use crate::application::post_policy;
+use crate::infrastructure::interchange::{MarkdownImporter, NdjsonImporter};
+use crate::infrastructure::web::auth::actor;
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+/// Largest archive accepted by the import endpoints
+pub const MAX_ARCHIVE_BYTES: usize = 32 * 1024 * 1024;
+
+/// HTTP handlers for bulk content imports
+/// The import policy is checked before reading the body, so a denied import fails as a whole
+#[derive(Clone)]
+pub struct ImportHandler {
+    markdown_importer: Arc<MarkdownImporter>,
+    ndjson_importer: Arc<NdjsonImporter>,
+}
+
+impl ImportHandler {
+    pub fn new(markdown_importer: Arc<MarkdownImporter>, ndjson_importer: Arc<NdjsonImporter>) -> Self {
+        Self { markdown_importer, ndjson_importer }
+    }
+
+    /// POST /import/markdown - Create or update posts from a zip of Markdown files with front matter
+    pub async fn import_markdown(&self, req: HttpRequest, archive: web::Bytes) -> Result<HttpResponse> {
+        let actor = actor(&req)?;
+        post_policy::can_import(&actor).map_err(ApiError::from)?;
+
+        let report = self.markdown_importer
+            .import_zip(&actor, archive.to_vec())
+            .await
+            .map_err(ApiError::bad_request)?;
+        Ok(HttpResponse::Ok().json(report))
+    }
+
+    /// POST /posts/import.ndjson - Create or update posts by slug from a streamed NDJSON body, one post per line
+    pub async fn import_ndjson(&self, req: HttpRequest, body: web::Payload) -> Result<HttpResponse> {
+        let actor = actor(&req)?;
+        post_policy::can_import(&actor).map_err(ApiError::from)?;
+
+        let report = self.ndjson_importer
+            .import(&actor, body)
+            .await
+            .map_err(ApiError::bad_request)?;
+        Ok(HttpResponse::Ok().json(report))
+    }
+}
@@ -0,0 +1,74 @@
+use crate::application::{AuthService, JwtService};
+use crate::infrastructure::security::JwtCodec;
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{AccessTokenResponse, LoginRequest, RefreshTokenRequest};
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+
+/// How long clients may cache the JWKS; rotated keys are published well before they sign
+const JWKS_MAX_AGE_SECONDS: u32 = 300;
+
+/// HTTP handlers for JWT access tokens, their refresh tokens and the published signing keys
+#[derive(Clone)]
+pub struct JwtHandler {
+    auth_service: Arc<AuthService>,
+    jwt_service: Arc<JwtService>,
+    codec: Arc<JwtCodec>,
+}
+
+impl JwtHandler {
+    pub fn new(auth_service: Arc<AuthService>, jwt_service: Arc<JwtService>, codec: Arc<JwtCodec>) -> Self {
+        Self { auth_service, jwt_service, codec }
+    }
+
+    /// POST /auth/token - Check a password and issue an access token and a refresh token
+    pub async fn issue_token(&self, login_data: web::Json<LoginRequest>) -> Result<HttpResponse> {
+        let request = login_data.into_inner();
+
+        let user = self.auth_service
+            .verify_credentials(&request.email, &request.password, request.code.as_deref())
+            .await
+            .map_err(ApiError::from)?;
+        let tokens = self.jwt_service.issue(&user).await.map_err(ApiError::from)?;
+
+        Ok(token_response(tokens.into()))
+    }
+
+    /// POST /auth/token/refresh - Exchange a refresh token for new tokens
+    pub async fn refresh_token(&self, refresh_data: web::Json<RefreshTokenRequest>) -> Result<HttpResponse> {
+        let tokens = self.jwt_service
+            .refresh(&refresh_data.refresh_token)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(token_response(tokens.into()))
+    }
+
+    /// POST /auth/token/revoke - Revoke a refresh token; unknown tokens are ignored
+    pub async fn revoke_token(&self, refresh_data: web::Json<RefreshTokenRequest>) -> Result<HttpResponse> {
+        self.jwt_service
+            .revoke(&refresh_data.refresh_token)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// GET /.well-known/jwks.json - Public keys that verify access tokens
+    pub async fn jwks(&self) -> Result<HttpResponse> {
+        Ok(HttpResponse::Ok()
+            .insert_header(CacheControl(vec![
+                CacheDirective::Public,
+                CacheDirective::MaxAge(JWKS_MAX_AGE_SECONDS),
+            ]))
+            .json(self.codec.jwks()))
+    }
+}
+
+/// Responses carrying tokens must not be cached
+fn token_response(tokens: AccessTokenResponse) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(tokens)
+}
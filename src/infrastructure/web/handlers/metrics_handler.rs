@@ -0,0 +1,30 @@
+use crate::infrastructure::database::DbPool;
+use crate::infrastructure::observability::{metrics, METRICS_CONTENT_TYPE};
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{HttpResponse, Result};
+use std::sync::Arc;
+
+/// HTTP handler for the Prometheus scrape endpoint
+#[derive(Clone)]
+pub struct MetricsHandler {
+    pool: Arc<DbPool>,
+}
+
+impl MetricsHandler {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// GET /metrics - All metrics in the Prometheus text format
+    pub async fn metrics(&self) -> Result<HttpResponse> {
+        let metrics = metrics();
+        metrics.observe_pool(self.pool.state(), self.pool.max_size());
+        let body = metrics.encode().map_err(ApiError::internal)?;
+
+        Ok(HttpResponse::Ok()
+            .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+            .content_type(METRICS_CONTENT_TYPE)
+            .body(body))
+    }
+}
@@ -1,3 +1,27 @@
+pub mod auth_handler;
+pub mod export_handler;
+pub mod feed_handler;
+pub mod health_handler;
+pub mod import_handler;
+pub mod jwt_handler;
+pub mod metrics_handler;
+pub mod oidc_handler;
+pub mod page_handler;
 pub mod post_handler;
+pub mod sitemap_handler;
+pub mod token_handler;
+pub mod two_factor_handler;
 
+pub use auth_handler::AuthHandler;
+pub use export_handler::ExportHandler;
+pub use feed_handler::FeedHandler;
+pub use health_handler::HealthHandler;
+pub use import_handler::{ImportHandler, MAX_ARCHIVE_BYTES};
+pub use jwt_handler::JwtHandler;
+pub use metrics_handler::MetricsHandler;
+pub use oidc_handler::OidcHandler;
+pub use page_handler::PageHandler;
 pub use post_handler::PostHandler;
+pub use sitemap_handler::SitemapHandler;
+pub use token_handler::TokenHandler;
+pub use two_factor_handler::TwoFactorHandler;
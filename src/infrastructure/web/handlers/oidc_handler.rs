@@ -0,0 +1,141 @@
+use crate::application::{AuthService, OidcOutcome, OidcService, ServiceError};
+use crate::domain::User;
+use crate::infrastructure::config::{SessionConfig, SiteConfig};
+use crate::infrastructure::web::auth::{
+    login_client, pending_login, pending_login_cookie, pending_login_removal, pending_second_factor,
+    pending_second_factor_cookie, pending_second_factor_removal, session_cookie,
+};
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{OidcCallbackQuery, OidcLoginQuery, TwoFactorCodeRequest};
+use crate::infrastructure::web::views::{SignedInTemplate, TwoFactorTemplate};
+use actix_web::http::header::{self, CacheControl, CacheDirective};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use askama::Template;
+use std::sync::Arc;
+
+/// HTTP handlers for single sign-on with an OpenID Connect provider
+#[derive(Clone)]
+pub struct OidcHandler {
+    /// `None` when no provider is configured
+    oidc_service: Option<Arc<OidcService>>,
+    auth_service: Arc<AuthService>,
+    session_config: Arc<SessionConfig>,
+    site_config: Arc<SiteConfig>,
+}
+
+impl OidcHandler {
+    pub fn new(
+        oidc_service: Option<Arc<OidcService>>,
+        auth_service: Arc<AuthService>,
+        session_config: Arc<SessionConfig>,
+        site_config: Arc<SiteConfig>,
+    ) -> Self {
+        Self {
+            oidc_service,
+            auth_service,
+            session_config,
+            site_config,
+        }
+    }
+
+    /// GET /auth/oidc/login - Send the browser to the identity provider
+    pub async fn login(&self, query: web::Query<OidcLoginQuery>) -> Result<HttpResponse> {
+        let login = self.service()?
+            .begin(query.return_to.as_deref())
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(HttpResponse::Found()
+            .insert_header((header::LOCATION, login.authorization_url))
+            .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+            .cookie(pending_login_cookie(&self.session_config, &login.pending))
+            .finish())
+    }
+
+    /// GET /auth/oidc/callback - Finish the login the provider redirected back from and set the session cookie
+    pub async fn callback(&self, req: HttpRequest, query: web::Query<OidcCallbackQuery>) -> Result<HttpResponse> {
+        let service = self.service()?;
+        let query = query.into_inner();
+
+        let Some(pending) = pending_login(&self.session_config, &req) else {
+            return Err(ApiError::bad_request("No single sign-on login is in progress in this browser; start it again").into());
+        };
+        if let Some(error) = query.error {
+            let detail = query.error_description.unwrap_or(error);
+            let detail = format!("The identity provider refused the login: {}", detail);
+            return Err(ApiError::from_status(StatusCode::UNAUTHORIZED, detail).into());
+        }
+        let (Some(code), Some(state)) = (query.code, query.state) else {
+            return Err(ApiError::bad_request("The callback needs `code` and `state`").into());
+        };
+
+        match service.complete(&pending, &state, &code).await.map_err(ApiError::from)? {
+            OidcOutcome::SignedIn(user) => {
+                let mut response = self.sign_in(&req, user, pending.return_to).await?;
+                response.add_cookie(&pending_login_removal(&self.session_config))?;
+                Ok(response)
+            }
+            OidcOutcome::SecondFactorRequired(second_factor) => {
+                let html = render(TwoFactorTemplate::new(&self.site_config, None))?;
+                Ok(HttpResponse::Ok()
+                    .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+                    .cookie(pending_second_factor_cookie(&self.session_config, &second_factor))
+                    .cookie(pending_login_removal(&self.session_config))
+                    .content_type("text/html; charset=utf-8")
+                    .body(html))
+            }
+        }
+    }
+
+    /// POST /auth/oidc/two-factor - Finish a single sign-on login with the account's second factor
+    pub async fn second_factor(&self, req: HttpRequest, form: web::Form<TwoFactorCodeRequest>) -> Result<HttpResponse> {
+        // Not found, like the other single sign-on routes, when no provider is configured
+        self.service()?;
+        let Some(pending) = pending_second_factor(&self.session_config, &req) else {
+            return Err(ApiError::bad_request("No single sign-on login is waiting for a code in this browser; start it again").into());
+        };
+
+        match self.auth_service.verify_second_factor(&pending, &form.code).await {
+            Ok(user) => {
+                let mut response = self.sign_in(&req, user, pending.return_to).await?;
+                response.add_cookie(&pending_second_factor_removal(&self.session_config))?;
+                Ok(response)
+            }
+            // A wrong code asks again; the lockout of password logins counts these attempts too
+            Err(ServiceError::Unauthenticated(message)) => {
+                let html = render(TwoFactorTemplate::new(&self.site_config, Some(message)))?;
+                Ok(HttpResponse::Unauthorized()
+                    .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+                    .content_type("text/html; charset=utf-8")
+                    .body(html))
+            }
+            Err(error) => Err(ApiError::from(error).into()),
+        }
+    }
+
+    /// Open a session and show the landing page that moves on to `return_to`
+    async fn sign_in(&self, req: &HttpRequest, user: User, return_to: String) -> Result<HttpResponse> {
+        let logged_in = self.auth_service
+            .open_session(user, login_client(req))
+            .await
+            .map_err(ApiError::from)?;
+
+        let html = render(SignedInTemplate::new(&self.site_config, logged_in.user.display_name, return_to))?;
+        Ok(HttpResponse::Ok()
+            .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+            .cookie(session_cookie(&self.session_config, logged_in.secret))
+            .content_type("text/html; charset=utf-8")
+            .body(html))
+    }
+
+    fn service(&self) -> Result<&OidcService, ApiError> {
+        self.oidc_service
+            .as_deref()
+            .ok_or_else(|| ApiError::not_found("Single sign-on is not configured"))
+    }
+}
+
+fn render(template: impl Template) -> Result<String, ApiError> {
+    template.render().map_err(|e| ApiError::internal(format!("Template error: {}", e)))
+}
@@ -0,0 +1,108 @@
+use crate::application::{PostService, SlugLookup};
+use crate::domain::validation::rules::is_valid_slug;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::discovery_link_header;
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::views::{post_path, IndexTemplate, NotFoundTemplate, PostTemplate, TagTemplate};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use askama::Template;
+use std::sync::Arc;
+
+/// HTTP handlers for the server-rendered HTML pages
+/// Only published posts are ever shown; drafts and scheduled posts answer 404
+#[derive(Clone)]
+pub struct PageHandler {
+    post_service: Arc<PostService>,
+    site: Arc<SiteConfig>,
+}
+
+impl PageHandler {
+    pub fn new(post_service: Arc<PostService>, site: Arc<SiteConfig>) -> Self {
+        Self { post_service, site }
+    }
+
+    /// GET /, /page/{n} - Latest published posts
+    pub async fn index(&self, page: i64) -> Result<HttpResponse> {
+        if page < 1 {
+            return Ok(self.not_found_page());
+        }
+
+        let posts = self.post_service
+            .get_published_page(page, self.site.page_size, None)
+            .await
+            .map_err(ApiError::from)?;
+        if posts.posts.is_empty() && page > 1 {
+            return Ok(self.not_found_page());
+        }
+
+        // Keep advertising the site feeds through a `Link` header as well as in `<head>`
+        let mut response = render(StatusCode::OK, &IndexTemplate::new(&self.site, &posts))?;
+        if let Ok(links) = HeaderValue::from_str(&discovery_link_header(&self.site, None)) {
+            response.headers_mut().insert(header::LINK, links);
+        }
+        Ok(response)
+    }
+
+    /// GET /blog/{slug} - A published post, redirecting retired slugs
+    pub async fn post(&self, path: web::Path<String>) -> Result<HttpResponse> {
+        let slug = path.into_inner();
+
+        // Looked up as an anonymous reader, so drafts and scheduled posts are not found
+        match self.post_service.get_post_by_slug(None, &slug).await.map_err(ApiError::from)? {
+            Some(SlugLookup::Current(post)) => render(StatusCode::OK, &PostTemplate::new(&self.site, &post)),
+            Some(SlugLookup::Moved(post)) => Ok(HttpResponse::MovedPermanently()
+                .insert_header((header::LOCATION, post_path(&post.slug)))
+                .finish()),
+            None => Ok(self.not_found_page()),
+        }
+    }
+
+    /// GET /tags/{slug}, /tags/{slug}/page/{n} - Published posts with a tag
+    pub async fn tag(&self, tag: &str, page: i64) -> Result<HttpResponse> {
+        if !is_valid_slug(tag) || page < 1 {
+            return Ok(self.not_found_page());
+        }
+
+        let posts = self.post_service
+            .get_published_page(page, self.site.page_size, Some(tag))
+            .await
+            .map_err(ApiError::from)?;
+        if posts.posts.is_empty() {
+            return Ok(self.not_found_page());
+        }
+
+        render(StatusCode::OK, &TagTemplate::new(&self.site, tag, &posts))
+    }
+
+    /// Fallback for unmatched routes: an HTML page for browsers, problem+json for everyone else
+    pub async fn not_found(&self, req: HttpRequest) -> Result<HttpResponse> {
+        if accepts_html(&req) {
+            Ok(self.not_found_page())
+        } else {
+            Ok(HttpResponse::NotFound().finish())
+        }
+    }
+
+    fn not_found_page(&self) -> HttpResponse {
+        render(StatusCode::NOT_FOUND, &NotFoundTemplate::new(&self.site))
+            .unwrap_or_else(|_| HttpResponse::NotFound().finish())
+    }
+}
+
+fn render(status: StatusCode, template: &impl Template) -> Result<HttpResponse> {
+    let html = template
+        .render()
+        .map_err(|e| ApiError::internal(format!("Template error: {}", e)))?;
+    Ok(HttpResponse::build(status)
+        .content_type("text/html; charset=utf-8")
+        .body(html))
+}
+
+fn accepts_html(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
@@ -1,11 +1,19 @@
-use crate::application::PostService;
-use crate::infrastructure::web::models::{CreatePostRequest, UpdatePostRequest, PostResponse};
-use actix_web::{web, HttpResponse, Result};
+use crate::application::{BatchResult, PostService, SlugLookup};
+use crate::domain::{OperationOutcome, Post, PostOperation};
+use crate::application::Actor;
+use crate::infrastructure::web::auth::{actor, principal};
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{
+    BatchOperationRequest, BatchOperationResponse, BatchRequest, BatchResponse, CreatePostRequest,
+    UpdatePostRequest, PatchPostRequest, PostResponse,
+};
+use actix_web::http::{header, StatusCode};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
 use serde_json::json;
 use std::sync::Arc;
 
 /// HTTP handlers for post endpoints
-/// This is the adapter that translates HTTP requests to use case calls
+/// This is the adapter that translates HTTP requests to use case calls; policy denials become `403 Forbidden`
 #[derive(Clone)]
 pub struct PostHandler {
     post_service: Arc<PostService>,
@@ -16,80 +24,173 @@ impl PostHandler {
         Self { post_service }
     }
 
-    /// GET /posts - Get all posts
-    pub async fn get_all_posts(&self) -> Result<HttpResponse> {
-        match self.post_service.get_all_posts().await {
-            Ok(posts) => {
-                let responses: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
-                Ok(HttpResponse::Ok().json(responses))
-            }
-            Err(error) => Ok(HttpResponse::InternalServerError().json(json!({
-                "error": error
-            })))
-        }
+    /// GET /posts - Get all posts the caller may see
+    pub async fn get_all_posts(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let posts = self.post_service.get_visible_posts(viewer(&req).as_ref()).await.map_err(ApiError::from)?;
+        let responses: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+        Ok(HttpResponse::Ok().json(responses))
     }
 
     /// GET /posts/{id} - Get post by ID
-    pub async fn get_post_by_id(&self, path: web::Path<i32>) -> Result<HttpResponse> {
+    pub async fn get_post_by_id(&self, req: HttpRequest, path: web::Path<i32>) -> Result<HttpResponse> {
         let post_id = path.into_inner();
         
-        match self.post_service.get_post_by_id(post_id).await {
-            Ok(Some(post)) => Ok(HttpResponse::Ok().json(PostResponse::from(post))),
-            Ok(None) => Ok(HttpResponse::NotFound().json(json!({
-                "error": "Post not found"
-            }))),
-            Err(error) => Ok(HttpResponse::InternalServerError().json(json!({
-                "error": error
-            })))
+        match self.post_service.get_post_by_id(viewer(&req).as_ref(), post_id).await.map_err(ApiError::from)? {
+            Some(post) => Ok(HttpResponse::Ok().json(PostResponse::from(post))),
+            None => Err(ApiError::not_found("Post not found").into()),
+        }
+    }
+
+    /// GET /posts/slug/{slug} - Get post by slug, redirecting retired slugs
+    pub async fn get_post_by_slug(&self, req: HttpRequest, path: web::Path<String>) -> Result<HttpResponse> {
+        let slug = path.into_inner();
+
+        match self.post_service.get_post_by_slug(viewer(&req).as_ref(), &slug).await.map_err(ApiError::from)? {
+            Some(SlugLookup::Current(post)) => Ok(HttpResponse::Ok().json(PostResponse::from(post))),
+            Some(SlugLookup::Moved(post)) => Ok(HttpResponse::MovedPermanently()
+                .insert_header((header::LOCATION, format!("/posts/slug/{}", post.slug)))
+                .finish()),
+            None => Err(ApiError::not_found("Post not found").into()),
         }
     }
 
     /// POST /posts - Create new post
-    pub async fn create_post(&self, post_data: web::Json<CreatePostRequest>) -> Result<HttpResponse> {
+    pub async fn create_post(&self, req: HttpRequest, post_data: web::Json<CreatePostRequest>) -> Result<HttpResponse> {
         let request = post_data.into_inner();
         
-        match self.post_service.create_post(request.title, request.slug, request.body).await {
-            Ok(post) => Ok(HttpResponse::Created().json(PostResponse::from(post))),
-            Err(error) => Ok(HttpResponse::BadRequest().json(json!({
-                "error": error
-            })))
-        }
+        let post = self.post_service
+            .create_post(&actor(&req)?, Post::from(request))
+            .await
+            .map_err(ApiError::from)?;
+        Ok(HttpResponse::Created().json(PostResponse::from(post)))
     }
 
     /// PUT /posts/{id} - Update post
     pub async fn update_post(
         &self,
+        req: HttpRequest,
         path: web::Path<i32>,
         post_data: web::Json<UpdatePostRequest>
     ) -> Result<HttpResponse> {
         let post_id = path.into_inner();
         let request = post_data.into_inner();
         
-        match self.post_service.update_post(post_id, request.title, request.body).await {
-            Ok(Some(post)) => Ok(HttpResponse::Ok().json(PostResponse::from(post))),
-            Ok(None) => Ok(HttpResponse::NotFound().json(json!({
-                "error": "Post not found"
-            }))),
-            Err(error) => Ok(HttpResponse::BadRequest().json(json!({
-                "error": error
-            })))
+        match self.post_service.update_post(&actor(&req)?, post_id, request.title, request.body).await.map_err(ApiError::from)? {
+            Some(post) => Ok(HttpResponse::Ok().json(PostResponse::from(post))),
+            None => Err(ApiError::not_found("Post not found").into()),
+        }
+    }
+
+    /// PATCH /posts/{id} - Partially update post (JSON Merge Patch)
+    pub async fn patch_post(
+        &self,
+        req: HttpRequest,
+        path: web::Path<i32>,
+        patch_data: web::Json<PatchPostRequest>
+    ) -> Result<HttpResponse> {
+        let post_id = path.into_inner();
+        let patch = patch_data.into_inner().into();
+
+        match self.post_service.patch_post(&actor(&req)?, post_id, patch).await.map_err(ApiError::from)? {
+            Some(post) => Ok(HttpResponse::Ok().json(PostResponse::from(post))),
+            None => Err(ApiError::not_found("Post not found").into()),
         }
     }
 
     /// DELETE /posts/{id} - Delete post
-    pub async fn delete_post(&self, path: web::Path<i32>) -> Result<HttpResponse> {
+    pub async fn delete_post(&self, req: HttpRequest, path: web::Path<i32>) -> Result<HttpResponse> {
         let post_id = path.into_inner();
         
-        match self.post_service.delete_post(post_id).await {
-            Ok(true) => Ok(HttpResponse::Ok().json(json!({
+        if self.post_service.delete_post(&actor(&req)?, post_id).await.map_err(ApiError::from)? {
+            Ok(HttpResponse::Ok().json(json!({
                 "message": "Post deleted successfully"
-            }))),
-            Ok(false) => Ok(HttpResponse::NotFound().json(json!({
-                "error": "Post not found"
-            }))),
-            Err(error) => Ok(HttpResponse::InternalServerError().json(json!({
-                "error": error
             })))
+        } else {
+            Err(ApiError::not_found("Post not found").into())
+        }
+    }
+
+    /// POST /posts/batch - Apply create, update and delete operations in one transaction
+    pub async fn batch(&self, req: HttpRequest, batch_data: web::Json<BatchRequest>) -> Result<HttpResponse> {
+        let request = batch_data.into_inner();
+        let names: Vec<&'static str> = request.operations.iter().map(BatchOperationRequest::name).collect();
+        let operations = request.operations.into_iter().map(PostOperation::from).collect();
+
+        let report = self.post_service
+            .apply_batch(&actor(&req)?, operations, request.continue_on_error)
+            .await
+            .map_err(ApiError::from)?;
+
+        let failed_operation = report.results
+            .iter()
+            .position(|result| matches!(result, BatchResult::Failed(_)))
+            .unwrap_or_default();
+        let results: Vec<BatchOperationResponse> = names
+            .into_iter()
+            .zip(report.results)
+            .map(|(op, result)| batch_operation_response(op, result, failed_operation))
+            .collect();
+
+        if !report.committed {
+            let status = StatusCode::from_u16(results[failed_operation].status)
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            let results = serde_json::to_value(&results).map_err(|e| ApiError::internal(e.to_string()))?;
+            return Err(ApiError::batch_rolled_back(status, failed_operation, results).into());
+        }
+
+        let failed = results.iter().filter(|result| result.error.is_some()).count();
+        Ok(HttpResponse::Ok().json(BatchResponse {
+            applied: results.len() - failed,
+            failed,
+            results,
+        }))
+    }
+
+    /// DELETE /slug-redirects/{slug} - Release a retired slug
+    pub async fn release_slug_redirect(&self, req: HttpRequest, path: web::Path<String>) -> Result<HttpResponse> {
+        let slug = path.into_inner();
+
+        if self.post_service.release_slug_redirect(&actor(&req)?, &slug).await.map_err(ApiError::from)? {
+            Ok(HttpResponse::NoContent().finish())
+        } else {
+            Err(ApiError::not_found("Slug redirect not found").into())
+        }
+    }
+}
+
+/// Who reads a post; reads are public, so anonymous readers have no actor
+fn viewer(req: &HttpRequest) -> Option<Actor> {
+    principal(req).as_ref().map(Actor::from)
+}
+
+/// Render one batch result with the status the single-post endpoint would have used
+fn batch_operation_response(op: &'static str, result: BatchResult, failed_operation: usize) -> BatchOperationResponse {
+    let applied = |status: StatusCode, id: i32, post: Option<Post>| BatchOperationResponse {
+        op,
+        status: status.as_u16(),
+        id: Some(id),
+        post: post.map(PostResponse::from),
+        error: None,
+    };
+    let failed = |error: ApiError| {
+        let problem = error.problem();
+        BatchOperationResponse { op, status: problem.status, id: None, post: None, error: Some(problem) }
+    };
+
+    match result {
+        BatchResult::Applied(OperationOutcome::Created(post)) => {
+            applied(StatusCode::CREATED, post.id.unwrap_or(0), Some(post))
         }
+        BatchResult::Applied(OperationOutcome::Updated { post, .. }) => applied(StatusCode::OK, post.id.unwrap_or(0), Some(post)),
+        BatchResult::Applied(OperationOutcome::Deleted(id)) => applied(StatusCode::OK, id, None),
+        BatchResult::Failed(error) => failed(ApiError::from(error)),
+        BatchResult::RolledBack(_) => failed(ApiError::from_status(
+            StatusCode::FAILED_DEPENDENCY,
+            format!("Rolled back because operation {} failed", failed_operation),
+        )),
+        BatchResult::Skipped => failed(ApiError::from_status(
+            StatusCode::FAILED_DEPENDENCY,
+            format!("Not attempted because operation {} failed", failed_operation),
+        )),
     }
 }
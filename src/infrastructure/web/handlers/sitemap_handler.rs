@@ -0,0 +1,64 @@
+use crate::application::PostService;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::seo::{
+    page_count, render_index, render_robots, render_urlset, MAX_URLS_PER_SITEMAP,
+    SITEMAP_CONTENT_TYPE,
+};
+use crate::infrastructure::web::conditional::conditional_response;
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+/// HTTP handlers for crawler-facing documents
+#[derive(Clone)]
+pub struct SitemapHandler {
+    post_service: Arc<PostService>,
+    site: Arc<SiteConfig>,
+}
+
+impl SitemapHandler {
+    pub fn new(post_service: Arc<PostService>, site: Arc<SiteConfig>) -> Self {
+        Self { post_service, site }
+    }
+
+    /// GET /sitemap.xml - Every published post, or a sitemap index once the URL limit is exceeded
+    pub async fn sitemap(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let count = self.post_service.count_published_posts().await.map_err(ApiError::from)?;
+
+        if count > MAX_URLS_PER_SITEMAP {
+            let body = render_index(&self.site, page_count(count));
+            return Ok(conditional_response(&req, SITEMAP_CONTENT_TYPE, body, None));
+        }
+
+        self.urlset_page(&req, 1).await
+    }
+
+    /// GET /sitemaps/{page}.xml - One child sitemap of the index
+    pub async fn sitemap_page(&self, req: HttpRequest, path: web::Path<i64>) -> Result<HttpResponse> {
+        let page = path.into_inner();
+        let count = self.post_service.count_published_posts().await.map_err(ApiError::from)?;
+
+        if page < 1 || page > page_count(count).max(1) {
+            return Err(ApiError::not_found("Sitemap page not found").into());
+        }
+        self.urlset_page(&req, page).await
+    }
+
+    /// GET /robots.txt - Crawler rules pointing at the sitemap
+    pub async fn robots(&self) -> Result<HttpResponse> {
+        Ok(HttpResponse::Ok()
+            .content_type("text/plain; charset=utf-8")
+            .body(render_robots(&self.site)))
+    }
+
+    async fn urlset_page(&self, req: &HttpRequest, page: i64) -> Result<HttpResponse> {
+        let links = self.post_service
+            .get_published_post_links((page - 1) * MAX_URLS_PER_SITEMAP, MAX_URLS_PER_SITEMAP)
+            .await
+            .map_err(ApiError::from)?;
+
+        let last_modified = links.iter().map(|link| link.last_modified).max();
+        let body = render_urlset(&self.site, &links);
+        Ok(conditional_response(req, SITEMAP_CONTENT_TYPE, body, last_modified))
+    }
+}
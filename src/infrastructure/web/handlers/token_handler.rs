@@ -0,0 +1,46 @@
+use crate::application::TokenService;
+use crate::infrastructure::web::auth::actor;
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{CreateTokenRequest, CreatedTokenResponse, TokenResponse};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+/// HTTP handlers for API token management; every route requires an admin
+#[derive(Clone)]
+pub struct TokenHandler {
+    token_service: Arc<TokenService>,
+}
+
+impl TokenHandler {
+    pub fn new(token_service: Arc<TokenService>) -> Self {
+        Self { token_service }
+    }
+
+    /// GET /tokens - List tokens, revoked and expired ones included
+    pub async fn list_tokens(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let tokens = self.token_service.list_tokens(&actor(&req)?).await.map_err(ApiError::from)?;
+        let responses: Vec<TokenResponse> = tokens.into_iter().map(TokenResponse::from).collect();
+        Ok(HttpResponse::Ok().json(responses))
+    }
+
+    /// POST /tokens - Mint a token; the response is the only time the token is shown
+    pub async fn create_token(&self, req: HttpRequest, token_data: web::Json<CreateTokenRequest>) -> Result<HttpResponse> {
+        let request = token_data.into_inner();
+
+        let minted = self.token_service
+            .mint_token(&actor(&req)?, request.name, request.expires_at)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(HttpResponse::Created().json(CreatedTokenResponse::from(minted)))
+    }
+
+    /// DELETE /tokens/{id} - Revoke a token
+    pub async fn revoke_token(&self, req: HttpRequest, path: web::Path<i32>) -> Result<HttpResponse> {
+        let token_id = path.into_inner();
+
+        match self.token_service.revoke_token(&actor(&req)?, token_id).await.map_err(ApiError::from)? {
+            Some(token) => Ok(HttpResponse::Ok().json(TokenResponse::from(token))),
+            None => Err(ApiError::not_found("Token not found").into()),
+        }
+    }
+}
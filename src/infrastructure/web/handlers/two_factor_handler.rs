@@ -0,0 +1,102 @@
+use crate::application::TwoFactorService;
+use crate::infrastructure::rendering::qr_code_svg;
+use crate::infrastructure::web::auth::{actor, signed_in};
+use crate::infrastructure::web::errors::ApiError;
+use crate::infrastructure::web::models::{
+    RecoveryCodesResponse, TwoFactorCodeRequest, TwoFactorEnrollmentResponse, TwoFactorPolicy, TwoFactorStatusResponse,
+};
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+const MANAGED: &str = "Two-factor authentication";
+
+/// HTTP handlers for enrolling in two-factor authentication and for the roles that require it
+#[derive(Clone)]
+pub struct TwoFactorHandler {
+    two_factor_service: Arc<TwoFactorService>,
+}
+
+impl TwoFactorHandler {
+    pub fn new(two_factor_service: Arc<TwoFactorService>) -> Self {
+        Self { two_factor_service }
+    }
+
+    /// GET /auth/two-factor - Whether the signed-in user has two-factor authentication and must have it
+    pub async fn status(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let (user, _) = signed_in(&req, MANAGED)?;
+
+        let status = self.two_factor_service.status(&user).await.map_err(ApiError::from)?;
+        Ok(HttpResponse::Ok().json(TwoFactorStatusResponse::from(status)))
+    }
+
+    /// POST /auth/two-factor/enrollment - Start an enrollment with a new secret
+    pub async fn begin_enrollment(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let (user, _) = signed_in(&req, MANAGED)?;
+
+        let enrollment = self.two_factor_service.begin_enrollment(&user).await.map_err(ApiError::from)?;
+        let qr_code_svg = qr_code_svg(&enrollment.otpauth_uri)
+            .map_err(|e| ApiError::internal(format!("QR code error: {}", e)))?;
+        Ok(HttpResponse::Ok()
+            .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+            .json(TwoFactorEnrollmentResponse {
+                secret: enrollment.secret,
+                otpauth_uri: enrollment.otpauth_uri,
+                qr_code_svg,
+            }))
+    }
+
+    /// POST /auth/two-factor/enrollment/confirm - Enable two-factor authentication with a first code
+    pub async fn confirm_enrollment(&self, req: HttpRequest, code_data: web::Json<TwoFactorCodeRequest>) -> Result<HttpResponse> {
+        let (user, _) = signed_in(&req, MANAGED)?;
+
+        let recovery_codes = self.two_factor_service
+            .confirm_enrollment(&user, &code_data.code)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(recovery_codes_response(recovery_codes))
+    }
+
+    /// POST /auth/two-factor/recovery-codes - Replace the recovery codes
+    pub async fn regenerate_recovery_codes(&self, req: HttpRequest, code_data: web::Json<TwoFactorCodeRequest>) -> Result<HttpResponse> {
+        let (user, _) = signed_in(&req, MANAGED)?;
+
+        let recovery_codes = self.two_factor_service
+            .regenerate_recovery_codes(&user, &code_data.code)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(recovery_codes_response(recovery_codes))
+    }
+
+    /// DELETE /auth/two-factor - Turn two-factor authentication off
+    pub async fn disable(&self, req: HttpRequest, code_data: web::Json<TwoFactorCodeRequest>) -> Result<HttpResponse> {
+        let (user, _) = signed_in(&req, MANAGED)?;
+
+        self.two_factor_service
+            .disable(&user, &code_data.code)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// GET /auth/two-factor/policy - List the roles that must use two-factor authentication
+    pub async fn policy(&self) -> Result<HttpResponse> {
+        let required_roles = self.two_factor_service.required_roles().await.map_err(ApiError::from)?;
+        Ok(HttpResponse::Ok().json(TwoFactorPolicy { required_roles }))
+    }
+
+    /// PUT /auth/two-factor/policy - Require two-factor authentication for exactly the given roles
+    pub async fn set_policy(&self, req: HttpRequest, policy_data: web::Json<TwoFactorPolicy>) -> Result<HttpResponse> {
+        let required_roles = self.two_factor_service
+            .set_required_roles(&actor(&req)?, policy_data.into_inner().required_roles)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(HttpResponse::Ok().json(TwoFactorPolicy { required_roles }))
+    }
+}
+
+fn recovery_codes_response(recovery_codes: Vec<String>) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(RecoveryCodesResponse { recovery_codes })
+}
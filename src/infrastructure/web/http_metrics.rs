@@ -0,0 +1,25 @@
+use crate::infrastructure::observability::metrics;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::Error;
+use std::time::Instant;
+
+/// Middleware counting requests and their latency by method, matched route and status
+/// Requests are labelled with the route pattern, such as `/posts/{id}`, never the raw path
+pub async fn http_metrics<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error> {
+    let method = req.method().to_string();
+    let route = req.match_pattern();
+    let started = Instant::now();
+
+    let result = next.call(req).await;
+    let status = match &result {
+        Ok(response) => response.status(),
+        Err(error) => error.as_response_error().status_code(),
+    };
+    metrics().record_http_request(&method, route.as_deref(), status.as_u16(), started.elapsed());
+    result
+}
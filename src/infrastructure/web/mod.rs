@@ -1,5 +1,22 @@
+pub mod auth;
+pub mod conditional;
+pub mod cors;
+pub mod errors;
 pub mod handlers;
+pub mod http_metrics;
 pub mod models;
+pub mod rate_limit;
+pub mod request_id;
+pub mod security_headers;
+pub mod views;
 
+pub use auth::{principal, require_auth, require_auth_for_writes};
+pub use conditional::conditional_response;
+pub use cors::cors;
+pub use errors::*;
 pub use handlers::*;
+pub use http_metrics::http_metrics;
 pub use models::*;
+pub use rate_limit::{rate_limit, RateLimiter};
+pub use request_id::{request_id, RequestId, REQUEST_ID_HEADER};
+pub use security_headers::security_headers;
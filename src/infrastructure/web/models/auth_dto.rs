@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::application::IssuedTokens;
+use crate::domain::{Role, Session, User};
+
+/// DTO for logging in via HTTP
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+    /// TOTP or recovery code, for accounts with two-factor authentication
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+/// DTO for refreshing or revoking a refresh token via HTTP
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+/// Query of the single sign-on login redirect
+#[derive(Deserialize)]
+pub struct OidcLoginQuery {
+    /// Local path to return to once signed in
+    pub return_to: Option<String>,
+}
+
+/// Query the identity provider redirects back with: a code and state, or an OAuth 2.0 error
+#[derive(Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// DTO for returning a user via HTTP; the password hash never leaves the server
+#[derive(Serialize)]
+pub struct UserResponse {
+    pub id: i32,
+    pub email: String,
+    pub display_name: String,
+    pub role: Role,
+    pub two_factor_enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for returning session metadata via HTTP; the cookie secret is never stored
+#[derive(Serialize)]
+pub struct SessionResponse {
+    pub id: i32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
+/// DTO for a successful login; the session secret travels in the cookie only
+#[derive(Serialize)]
+pub struct LoginResponse {
+    pub user: UserResponse,
+    pub session: SessionResponse,
+}
+
+/// DTO for issued JWT tokens, shaped like an OAuth 2.0 token response
+#[derive(Serialize)]
+pub struct AccessTokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    /// Seconds until the access token expires
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        let two_factor_enabled = user.has_two_factor();
+        UserResponse {
+            id: user.id.unwrap_or(0), // This should only be called for stored users
+            email: user.email,
+            display_name: user.display_name,
+            role: user.role,
+            two_factor_enabled,
+            created_at: user.created_at,
+        }
+    }
+}
+
+impl SessionResponse {
+    pub fn new(session: Session, current: bool) -> Self {
+        SessionResponse {
+            id: session.id.unwrap_or(0), // This should only be called for stored sessions
+            created_at: session.created_at,
+            expires_at: session.expires_at,
+            last_seen_at: session.last_seen_at,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            current,
+        }
+    }
+}
+
+impl From<IssuedTokens> for AccessTokenResponse {
+    fn from(tokens: IssuedTokens) -> Self {
+        AccessTokenResponse {
+            access_token: tokens.access_token,
+            token_type: "Bearer",
+            expires_in: (tokens.access_expires_at - Utc::now()).num_seconds().max(0),
+            refresh_token: tokens.refresh_token,
+            refresh_expires_at: tokens.refresh_expires_at,
+        }
+    }
+}
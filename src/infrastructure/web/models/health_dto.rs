@@ -0,0 +1,44 @@
+use serde::Serialize;
+use crate::infrastructure::database::HealthCheck;
+
+const SERVICE_NAME: &str = "blog-rust-hexagonal";
+
+/// DTO for a passing liveness or readiness probe
+#[derive(Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub service: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<HealthCheckResponse>,
+}
+
+impl HealthResponse {
+    pub fn pass(checks: Vec<HealthCheckResponse>) -> Self {
+        Self {
+            status: "pass",
+            service: SERVICE_NAME,
+            checks,
+        }
+    }
+}
+
+/// DTO for one readiness check and how long it took; `status` is `pass` or `fail`
+#[derive(Serialize)]
+pub struct HealthCheckResponse {
+    pub name: &'static str,
+    pub status: &'static str,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<HealthCheck> for HealthCheckResponse {
+    fn from(check: HealthCheck) -> Self {
+        Self {
+            name: check.name,
+            status: if check.passed() { "pass" } else { "fail" },
+            latency_ms: check.elapsed.as_secs_f64() * 1000.0,
+            error: check.error,
+        }
+    }
+}
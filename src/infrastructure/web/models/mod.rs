@@ -1,3 +1,11 @@
+pub mod auth_dto;
+pub mod health_dto;
 pub mod post_dto;
+pub mod token_dto;
+pub mod two_factor_dto;
 
+pub use auth_dto::*;
+pub use health_dto::*;
 pub use post_dto::*;
+pub use token_dto::*;
+pub use two_factor_dto::*;
@@ -1,12 +1,25 @@
-use serde::{Deserialize, Serialize};
-use crate::domain::Post;
+use actix_web::mime;
+use actix_web::web::JsonConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::domain::{Post, PostOperation, PostPatch};
+use crate::infrastructure::web::errors::ProblemDetails;
 
 /// DTO for creating a new post via HTTP
 #[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CreatePostRequest {
     pub title: String,
     pub body: String,
     pub slug: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Drafts are hidden from feeds until published; posts are published immediately by default
+    #[serde(default)]
+    pub draft: bool,
+    /// Publication date, which takes precedence over `draft`; a future date schedules the post
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
 }
 
 /// DTO for updating a post via HTTP
@@ -16,6 +29,71 @@ pub struct UpdatePostRequest {
     pub body: String,
 }
 
+/// DTO for partially updating a post via JSON Merge Patch (RFC 7396)
+/// Absent members are left untouched; `null` clears the field
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatchPostRequest {
+    #[serde(default, deserialize_with = "present")]
+    pub title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub slug: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub body: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub tags: Option<Option<Vec<String>>>,
+    #[serde(default, deserialize_with = "present")]
+    pub published_at: Option<Option<DateTime<Utc>>>,
+}
+
+/// Body settings for `PatchPostRequest`: `application/merge-patch+json`, the RFC 7396 media type, next to plain JSON
+pub fn merge_patch_config() -> JsonConfig {
+    JsonConfig::default().content_type(|content_type| {
+        content_type.type_() == mime::APPLICATION
+            && content_type.subtype() == "merge-patch"
+            && content_type.suffix() == Some(mime::JSON)
+    })
+}
+
+/// DTO for one operation of a batch, tagged by `op`
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOperationRequest {
+    Create(CreatePostRequest),
+    /// `changes` is a JSON Merge Patch, as accepted by `PATCH /posts/{id}`
+    Update { id: i32, changes: PatchPostRequest },
+    Delete { id: i32 },
+}
+
+impl BatchOperationRequest {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatchOperationRequest::Create(_) => "create",
+            BatchOperationRequest::Update { .. } => "update",
+            BatchOperationRequest::Delete { .. } => "delete",
+        }
+    }
+}
+
+/// DTO for applying several operations in one request
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRequest {
+    pub operations: Vec<BatchOperationRequest>,
+    /// Keep the operations that succeed instead of rolling everything back on the first failure
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+/// Distinguish an explicit `null` (`Some(None)`) from an absent member (`None`)
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 /// DTO for returning post data via HTTP
 #[derive(Serialize)]
 pub struct PostResponse {
@@ -23,6 +101,33 @@ pub struct PostResponse {
     pub title: String,
     pub slug: String,
     pub body: String,
+    pub tags: Vec<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// `null` for posts created before user accounts, or whose author was deleted
+    pub author_id: Option<i32>,
+}
+
+/// DTO for the result of one batch operation; `status` is what the single-post endpoint would have returned
+#[derive(Serialize)]
+pub struct BatchOperationResponse {
+    pub op: &'static str,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<PostResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProblemDetails>,
+}
+
+/// DTO for the results of a committed batch, in request order
+#[derive(Serialize)]
+pub struct BatchResponse {
+    pub applied: usize,
+    pub failed: usize,
+    pub results: Vec<BatchOperationResponse>,
 }
 
 impl From<Post> for PostResponse {
@@ -32,12 +137,46 @@ impl From<Post> for PostResponse {
             title: post.title,
             slug: post.slug,
             body: post.body,
+            tags: post.tags,
+            published_at: post.published_at,
+            author_id: post.author_id,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
         }
     }
 }
 
 impl From<CreatePostRequest> for Post {
     fn from(req: CreatePostRequest) -> Self {
-        Post::new(req.title, req.slug, req.body)
+        let mut post = Post::new(req.title, req.slug, req.body);
+        post.set_tags(req.tags);
+        post.published_at = req.published_at;
+        if !req.draft {
+            post.publish();
+        }
+        post
+    }
+}
+
+impl From<PatchPostRequest> for PostPatch {
+    fn from(req: PatchPostRequest) -> Self {
+        // Title, slug and body are required, so removing one leaves it empty and fails validation
+        PostPatch {
+            title: req.title.map(Option::unwrap_or_default),
+            slug: req.slug.map(Option::unwrap_or_default),
+            body: req.body.map(Option::unwrap_or_default),
+            tags: req.tags.map(Option::unwrap_or_default),
+            published_at: req.published_at,
+        }
+    }
+}
+
+impl From<BatchOperationRequest> for PostOperation {
+    fn from(req: BatchOperationRequest) -> Self {
+        match req {
+            BatchOperationRequest::Create(create) => PostOperation::Create(create.into()),
+            BatchOperationRequest::Update { id, changes } => PostOperation::Update(id, changes.into()),
+            BatchOperationRequest::Delete { id } => PostOperation::Delete(id),
+        }
     }
 }
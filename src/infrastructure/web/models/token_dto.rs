@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::application::MintedToken;
+use crate::domain::ApiToken;
+
+/// DTO for minting an API token via HTTP
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTokenRequest {
+    pub name: String,
+    /// The token never expires when absent
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for returning token metadata via HTTP; the secret itself is never stored
+#[derive(Serialize)]
+pub struct TokenResponse {
+    pub id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub active: bool,
+}
+
+/// DTO for a freshly minted token, the only response that carries the secret
+#[derive(Serialize)]
+pub struct CreatedTokenResponse {
+    pub token: String,
+    #[serde(flatten)]
+    pub metadata: TokenResponse,
+}
+
+impl From<ApiToken> for TokenResponse {
+    fn from(token: ApiToken) -> Self {
+        TokenResponse {
+            id: token.id.unwrap_or(0), // This should only be called for stored tokens
+            active: token.is_active_at(Utc::now()),
+            name: token.name,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+        }
+    }
+}
+
+impl From<MintedToken> for CreatedTokenResponse {
+    fn from(minted: MintedToken) -> Self {
+        CreatedTokenResponse {
+            token: minted.secret,
+            metadata: TokenResponse::from(minted.token),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use crate::application::TwoFactorStatus;
+use crate::domain::Role;
+
+/// DTO for a TOTP or recovery code proving the second factor via HTTP
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+/// DTO for a started enrollment; the secret is shown only this once
+#[derive(Serialize)]
+pub struct TwoFactorEnrollmentResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+    /// The `otpauth_uri` as an SVG QR code, for authenticator apps to scan
+    pub qr_code_svg: String,
+}
+
+/// DTO for newly issued recovery codes; the response is the only time they are shown
+#[derive(Serialize)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// DTO for returning where the signed-in user stands with two-factor authentication
+#[derive(Serialize)]
+pub struct TwoFactorStatusResponse {
+    pub enabled: bool,
+    pub required: bool,
+    pub recovery_codes_remaining: usize,
+}
+
+/// DTO for the roles that must use two-factor authentication
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TwoFactorPolicy {
+    pub required_roles: Vec<Role>,
+}
+
+impl From<TwoFactorStatus> for TwoFactorStatusResponse {
+    fn from(status: TwoFactorStatus) -> Self {
+        TwoFactorStatusResponse {
+            enabled: status.enabled,
+            required: status.required,
+            recovery_codes_remaining: status.recovery_codes_remaining,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::domain::Principal;
+use crate::infrastructure::config::{RateLimitConfig, RouteClass};
+use crate::infrastructure::rate_limit::{RateLimit, RateLimitDecision, RateLimitStore};
+use crate::infrastructure::web::auth::identify;
+use crate::infrastructure::web::errors::ApiError;
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use actix_web::http::{Method, StatusCode};
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+use std::sync::Arc;
+
+/// Token-bucket rate limits per client and route class
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    config: RateLimitConfig,
+}
+
+impl RateLimiter {
+    pub fn new(store: Arc<dyn RateLimitStore>, config: RateLimitConfig) -> Self {
+        Self { store, config }
+    }
+}
+
+/// Middleware refusing clients that ran out of requests with `429 Too Many Requests`
+/// Signed-in clients are counted per user or API token, everyone else per IP address
+pub async fn rate_limit<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let Some(limiter) = req.app_data::<web::Data<RateLimiter>>().cloned() else {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    };
+    let class = route_class(&req);
+    let Some(limit) = limiter.config.limit(class) else {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    };
+
+    let key = format!("{}:{}", class, client_key(&req, &limiter.config).await);
+    let decision = match limiter.store.take(&key, limit).await {
+        Ok(decision) => decision,
+        // A broken store should not take the site down with it
+        Err(error) => {
+            tracing::warn!(error = %error, "rate limit store failed; letting the request through");
+            return next.call(req).await.map(ServiceResponse::map_into_left_body);
+        }
+    };
+
+    if !decision.allowed {
+        let detail = format!("Too many {} requests; retry in {} seconds", class, decision.retry_after);
+        let mut response = req.error_response(ApiError::from_status(StatusCode::TOO_MANY_REQUESTS, detail));
+        insert_headers(response.headers_mut(), limit, &decision);
+        response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(decision.retry_after));
+        return Ok(response.map_into_right_body());
+    }
+
+    let mut response = next.call(req).await?;
+    insert_headers(response.headers_mut(), limit, &decision);
+    Ok(response.map_into_left_body())
+}
+
+/// Writes under `/auth/` check passwords and codes, so they get the tightest limit
+fn route_class(req: &ServiceRequest) -> RouteClass {
+    let safe = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    match (safe, req.path().starts_with("/auth/")) {
+        (true, _) => RouteClass::Read,
+        (false, true) => RouteClass::Auth,
+        (false, false) => RouteClass::Write,
+    }
+}
+
+/// A user shares one bucket across sessions and access tokens; invalid credentials count against the IP address
+async fn client_key(req: &ServiceRequest, config: &RateLimitConfig) -> String {
+    match identify(req).await {
+        Some(Principal::User { user, .. }) => format!("user:{}", user.id.unwrap_or(0)),
+        Some(Principal::Jwt(claims)) => format!("user:{}", claims.user_id),
+        Some(Principal::Token(token)) => format!("token:{}", token.id.unwrap_or(0)),
+        None if config.trust_proxy => format!("ip:{}", req.connection_info().realip_remote_addr().unwrap_or("unknown")),
+        None => format!("ip:{}", req.peer_addr().map_or("unknown".to_string(), |addr| addr.ip().to_string())),
+    }
+}
+
+/// `RateLimit-*` fields of the IETF rate limit headers draft
+fn insert_headers(headers: &mut HeaderMap, limit: &RateLimit, decision: &RateLimitDecision) {
+    let fields = [
+        ("ratelimit-limit", decision.limit.to_string()),
+        ("ratelimit-remaining", decision.remaining.to_string()),
+        ("ratelimit-reset", decision.reset_after.to_string()),
+        ("ratelimit-policy", format!("{};w={}", limit.capacity, limit.period.as_secs())),
+    ];
+    for (name, value) in fields {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::infrastructure::config::SecurityHeadersConfig;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+
+/// Middleware adding security headers to every response, errors included; headers a handler set are kept
+/// HTML pages and everything else (JSON, feeds, sitemaps) get their own Content Security Policy
+pub async fn security_headers<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error> {
+    let config = req.app_data::<web::Data<SecurityHeadersConfig>>().cloned();
+    let mut response = next.call(req).await?;
+    let Some(config) = config else {
+        return Ok(response);
+    };
+
+    let html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    let (content_security_policy, referrer_policy) = if html {
+        (&config.html_content_security_policy, &config.html_referrer_policy)
+    } else {
+        (&config.api_content_security_policy, &config.api_referrer_policy)
+    };
+
+    let headers = response.headers_mut();
+    insert_default(headers, header::CONTENT_SECURITY_POLICY, content_security_policy);
+    insert_default(headers, header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    insert_default(headers, header::REFERRER_POLICY, referrer_policy);
+    insert_default(headers, header::X_FRAME_OPTIONS, &config.frame_options);
+    if let Some(value) = &config.strict_transport_security {
+        insert_default(headers, header::STRICT_TRANSPORT_SECURITY, value);
+    }
+    Ok(response)
+}
+
+fn insert_default(headers: &mut HeaderMap, name: HeaderName, value: &str) {
+    if headers.contains_key(&name) {
+        return;
+    }
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod pages;
+
+pub use pages::{
+    IndexTemplate, NotFoundTemplate, PostTemplate, SignedInTemplate, TagTemplate, TwoFactorTemplate, index_path, post_path, tag_path,
+};
@@ -0,0 +1,277 @@
+use crate::application::PostPage;
+use crate::domain::Post;
+use crate::infrastructure::config::SiteConfig;
+use crate::infrastructure::feeds::{FeedChannel, FeedFormat};
+use crate::infrastructure::rendering::{excerpt, render_markdown};
+use askama::Template;
+
+/// Length of `<meta name="description">` summaries
+const META_DESCRIPTION_LENGTH: usize = 160;
+const LIST_EXCERPT_LENGTH: usize = 280;
+
+/// Metadata rendered in the `<head>` of every page
+pub struct PageMeta {
+    pub title: String,
+    pub description: String,
+    /// `None` marks the page as not indexable
+    pub canonical: Option<String>,
+    pub site_title: String,
+    pub language: String,
+    pub feeds: Vec<FeedLinkView>,
+}
+
+impl PageMeta {
+    fn new(
+        site: &SiteConfig,
+        title: String,
+        description: String,
+        canonical: Option<String>,
+        tag: Option<&str>,
+    ) -> Self {
+        Self {
+            title,
+            description,
+            canonical,
+            site_title: site.title.clone(),
+            language: site.language.clone(),
+            feeds: FeedLinkView::all(site, tag),
+        }
+    }
+}
+
+/// Feed autodiscovery link
+pub struct FeedLinkView {
+    pub href: String,
+    pub media_type: &'static str,
+    pub title: String,
+    pub label: &'static str,
+}
+
+impl FeedLinkView {
+    fn all(site: &SiteConfig, tag: Option<&str>) -> Vec<Self> {
+        FeedFormat::ALL
+            .iter()
+            .map(|format| {
+                let channel = match tag {
+                    Some(tag) => FeedChannel::tag(site, tag, *format),
+                    None => FeedChannel::site(site, *format),
+                };
+                FeedLinkView {
+                    href: channel.self_url,
+                    media_type: format.media_type(),
+                    title: format!("{} {}", channel.title, format.label()),
+                    label: format.label(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Post fields prepared for display
+pub struct PostView {
+    pub title: String,
+    pub url: String,
+    pub excerpt: String,
+    pub published_iso: String,
+    pub published_display: String,
+    pub tags: Vec<String>,
+}
+
+impl PostView {
+    fn from_post(post: &Post) -> Self {
+        let published = post.published_at.unwrap_or(post.created_at);
+        Self {
+            title: post.title.clone(),
+            url: post_path(&post.slug),
+            excerpt: excerpt(&post.body, LIST_EXCERPT_LENGTH),
+            published_iso: published.to_rfc3339(),
+            published_display: published.format("%B %-d, %Y").to_string(),
+            tags: post.tags.clone(),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "index.html")]
+pub struct IndexTemplate {
+    pub meta: PageMeta,
+    pub posts: Vec<PostView>,
+    pub newer_url: Option<String>,
+    pub older_url: Option<String>,
+}
+
+impl IndexTemplate {
+    pub fn new(site: &SiteConfig, page: &PostPage) -> Self {
+        let path = index_path(page.page);
+        let title = if page.page == 1 {
+            site.title.clone()
+        } else {
+            format!("Page {} – {}", page.page, site.title)
+        };
+
+        Self {
+            meta: PageMeta::new(
+                site,
+                title,
+                site.description.clone(),
+                Some(site.url(&path)),
+                None,
+            ),
+            posts: page.posts.iter().map(PostView::from_post).collect(),
+            newer_url: (page.page > 1).then(|| index_path(page.page - 1)),
+            older_url: page.has_next.then(|| index_path(page.page + 1)),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "tag.html")]
+pub struct TagTemplate {
+    pub meta: PageMeta,
+    pub tag: String,
+    pub posts: Vec<PostView>,
+    pub newer_url: Option<String>,
+    pub older_url: Option<String>,
+}
+
+impl TagTemplate {
+    pub fn new(site: &SiteConfig, tag: &str, page: &PostPage) -> Self {
+        let path = tag_path(tag, page.page);
+        let title = if page.page == 1 {
+            format!("#{} – {}", tag, site.title)
+        } else {
+            format!("#{} (page {}) – {}", tag, page.page, site.title)
+        };
+
+        Self {
+            meta: PageMeta::new(
+                site,
+                title,
+                format!("Posts tagged #{} on {}", tag, site.title),
+                Some(site.url(&path)),
+                Some(tag),
+            ),
+            tag: tag.to_string(),
+            posts: page.posts.iter().map(PostView::from_post).collect(),
+            newer_url: (page.page > 1).then(|| tag_path(tag, page.page - 1)),
+            older_url: page.has_next.then(|| tag_path(tag, page.page + 1)),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "post.html")]
+pub struct PostTemplate {
+    pub meta: PageMeta,
+    pub post: PostView,
+    pub content_html: String,
+}
+
+impl PostTemplate {
+    pub fn new(site: &SiteConfig, post: &Post) -> Self {
+        Self {
+            meta: PageMeta::new(
+                site,
+                format!("{} – {}", post.title, site.title),
+                excerpt(&post.body, META_DESCRIPTION_LENGTH),
+                Some(site.post_url(&post.slug)),
+                None,
+            ),
+            post: PostView::from_post(post),
+            content_html: render_markdown(&post.body),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "not_found.html")]
+pub struct NotFoundTemplate {
+    pub meta: PageMeta,
+}
+
+impl NotFoundTemplate {
+    pub fn new(site: &SiteConfig) -> Self {
+        Self {
+            meta: PageMeta::new(
+                site,
+                format!("Page not found – {}", site.title),
+                "The requested page does not exist.".to_string(),
+                None,
+                None,
+            ),
+        }
+    }
+}
+
+/// Landing page after single sign-on; it moves on to `return_to` from this site,
+/// so the browser sends the new `SameSite=Strict` session cookie along
+#[derive(Template)]
+#[template(path = "signed_in.html")]
+pub struct SignedInTemplate {
+    pub meta: PageMeta,
+    pub display_name: String,
+    pub return_to: String,
+}
+
+impl SignedInTemplate {
+    pub fn new(site: &SiteConfig, display_name: String, return_to: String) -> Self {
+        Self {
+            meta: PageMeta::new(
+                site,
+                format!("Signed in – {}", site.title),
+                "You are signed in.".to_string(),
+                None,
+                None,
+            ),
+            display_name,
+            return_to,
+        }
+    }
+}
+
+/// Prompt for the second factor of a single sign-on login, for accounts with two-factor authentication
+#[derive(Template)]
+#[template(path = "two_factor.html")]
+pub struct TwoFactorTemplate {
+    pub meta: PageMeta,
+    /// Why the previous code was refused
+    pub error: Option<String>,
+}
+
+impl TwoFactorTemplate {
+    pub fn new(site: &SiteConfig, error: Option<String>) -> Self {
+        Self {
+            meta: PageMeta::new(
+                site,
+                format!("Two-factor authentication – {}", site.title),
+                "Enter your two-factor authentication code.".to_string(),
+                None,
+                None,
+            ),
+            error,
+        }
+    }
+}
+
+/// Site-relative URL of a post page
+pub fn post_path(slug: &str) -> String {
+    format!("/blog/{}", slug)
+}
+
+/// Site-relative URL of an index page (1-based)
+pub fn index_path(page: i64) -> String {
+    if page == 1 {
+        "/".to_string()
+    } else {
+        format!("/page/{}", page)
+    }
+}
+
+/// Site-relative URL of a tag page (1-based)
+pub fn tag_path(tag: &str, page: i64) -> String {
+    if page == 1 {
+        format!("/tags/{}", tag)
+    } else {
+        format!("/tags/{}/page/{}", tag, page)
+    }
+}
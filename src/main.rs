@@ -1,61 +1,405 @@
-use actix_web::{web, App, HttpResponse, HttpServer, Responder, get, Result};
-use blog_rust::application::PostService;
-use blog_rust::infrastructure::{establish_connection_pool, SqlitePostRepository, PostHandler};
-use serde_json::json;
-use std::sync::Arc;
-
-#[get("/")]
-async fn hello() -> impl Responder {
-    HttpResponse::Ok().body("Hello world!")
-}
+mod cli;
 
-#[get("/health")]
-async fn health_check() -> impl Responder {
-    HttpResponse::Ok().json(json!({
-        "status": "healthy",
-        "service": "blog-rust-hexagonal"
-    }))
-}
+use actix_files::Files;
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Result};
+use blog_rust::application::{AuthService, JwtService, OidcService, PostService, TokenService, TwoFactorService};
+use blog_rust::infrastructure::{establish_connection_pool, DbPool, problem_details, SqlitePostRepository, SqliteApiTokenRepository, SqliteUserRepository, SqliteSessionRepository, SqliteRefreshTokenRepository, SqliteUserIdentityRepository, SqliteRecoveryCodeRepository, SqliteTwoFactorPolicyRepository, Argon2PasswordHasher, JwtCodec, OidcClient, PostHandler, FeedHandler, SitemapHandler, PageHandler, ImportHandler, ExportHandler, TokenHandler, AuthHandler, JwtHandler, HealthHandler, MetricsHandler, OidcHandler, TwoFactorHandler, SiteConfig, SessionConfig, JwtConfig, OidcConfig, RateLimitConfig, CorsConfig, SecurityHeadersConfig, InMemoryRateLimitStore, StaticSiteExporter, MarkdownImporter, MarkdownExporter, NdjsonExporter, NdjsonImporter, WxrImporter};
+use blog_rust::infrastructure::web::{cors, http_metrics, merge_patch_config, rate_limit, request_id, require_auth, security_headers, require_auth_for_writes, RateLimiter, MAX_ARCHIVE_BYTES};
+use blog_rust::infrastructure::feeds::FeedFormat;
+use blog_rust::infrastructure::observability::{init_logging, PostMetrics};
+use clap::Parser;
+use cli::{Cli, Command};
+use actix_web::middleware::{from_fn, Condition};
+use std::sync::Arc;
 
 // Wrapper functions to handle the handler method calls
 async fn get_all_posts_handler(
+    req: HttpRequest,
     handler: web::Data<PostHandler>
 ) -> Result<HttpResponse> {
-    handler.get_all_posts().await
+    handler.get_all_posts(req).await
 }
 
 async fn get_post_by_id_handler(
+    req: HttpRequest,
     path: web::Path<i32>,
     handler: web::Data<PostHandler>
 ) -> Result<HttpResponse> {
-    handler.get_post_by_id(path).await
+    handler.get_post_by_id(req, path).await
+}
+
+async fn get_post_by_slug_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    handler: web::Data<PostHandler>
+) -> Result<HttpResponse> {
+    handler.get_post_by_slug(req, path).await
 }
 
 async fn create_post_handler(
+    req: HttpRequest,
     post_data: web::Json<blog_rust::infrastructure::CreatePostRequest>,
     handler: web::Data<PostHandler>
 ) -> Result<HttpResponse> {
-    handler.create_post(post_data).await
+    handler.create_post(req, post_data).await
+}
+
+async fn batch_posts_handler(
+    req: HttpRequest,
+    batch_data: web::Json<blog_rust::infrastructure::BatchRequest>,
+    handler: web::Data<PostHandler>
+) -> Result<HttpResponse> {
+    handler.batch(req, batch_data).await
 }
 
 async fn update_post_handler(
+    req: HttpRequest,
     path: web::Path<i32>,
     post_data: web::Json<blog_rust::infrastructure::UpdatePostRequest>,
     handler: web::Data<PostHandler>
 ) -> Result<HttpResponse> {
-    handler.update_post(path, post_data).await
+    handler.update_post(req, path, post_data).await
+}
+
+async fn patch_post_handler(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    patch_data: web::Json<blog_rust::infrastructure::PatchPostRequest>,
+    handler: web::Data<PostHandler>
+) -> Result<HttpResponse> {
+    handler.patch_post(req, path, patch_data).await
 }
 
 async fn delete_post_handler(
+    req: HttpRequest,
     path: web::Path<i32>,
     handler: web::Data<PostHandler>
 ) -> Result<HttpResponse> {
-    handler.delete_post(path).await
+    handler.delete_post(req, path).await
+}
+
+async fn release_slug_redirect_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    handler: web::Data<PostHandler>
+) -> Result<HttpResponse> {
+    handler.release_slug_redirect(req, path).await
+}
+
+async fn rss_feed_handler(
+    req: HttpRequest,
+    handler: web::Data<FeedHandler>
+) -> Result<HttpResponse> {
+    handler.site_feed(req, FeedFormat::Rss).await
+}
+
+async fn atom_feed_handler(
+    req: HttpRequest,
+    handler: web::Data<FeedHandler>
+) -> Result<HttpResponse> {
+    handler.site_feed(req, FeedFormat::Atom).await
+}
+
+async fn json_feed_handler(
+    req: HttpRequest,
+    handler: web::Data<FeedHandler>
+) -> Result<HttpResponse> {
+    handler.site_feed(req, FeedFormat::Json).await
+}
+
+async fn tag_rss_feed_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    handler: web::Data<FeedHandler>
+) -> Result<HttpResponse> {
+    handler.tag_feed(req, path, FeedFormat::Rss).await
+}
+
+async fn tag_atom_feed_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    handler: web::Data<FeedHandler>
+) -> Result<HttpResponse> {
+    handler.tag_feed(req, path, FeedFormat::Atom).await
+}
+
+async fn tag_json_feed_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    handler: web::Data<FeedHandler>
+) -> Result<HttpResponse> {
+    handler.tag_feed(req, path, FeedFormat::Json).await
+}
+
+async fn sitemap_xml_handler(
+    req: HttpRequest,
+    handler: web::Data<SitemapHandler>
+) -> Result<HttpResponse> {
+    handler.sitemap(req).await
+}
+
+async fn sitemap_page_handler(
+    req: HttpRequest,
+    path: web::Path<i64>,
+    handler: web::Data<SitemapHandler>
+) -> Result<HttpResponse> {
+    handler.sitemap_page(req, path).await
+}
+
+async fn robots_handler(
+    handler: web::Data<SitemapHandler>
+) -> Result<HttpResponse> {
+    handler.robots().await
+}
+
+async fn index_page_handler(
+    handler: web::Data<PageHandler>
+) -> Result<HttpResponse> {
+    handler.index(1).await
+}
+
+async fn index_page_n_handler(
+    path: web::Path<i64>,
+    handler: web::Data<PageHandler>
+) -> Result<HttpResponse> {
+    handler.index(path.into_inner()).await
+}
+
+async fn post_page_handler(
+    path: web::Path<String>,
+    handler: web::Data<PageHandler>
+) -> Result<HttpResponse> {
+    handler.post(path).await
+}
+
+async fn tag_page_handler(
+    path: web::Path<String>,
+    handler: web::Data<PageHandler>
+) -> Result<HttpResponse> {
+    handler.tag(&path.into_inner(), 1).await
+}
+
+async fn tag_page_n_handler(
+    path: web::Path<(String, i64)>,
+    handler: web::Data<PageHandler>
+) -> Result<HttpResponse> {
+    let (tag, page) = path.into_inner();
+    handler.tag(&tag, page).await
+}
+
+async fn not_found_page_handler(
+    req: HttpRequest,
+    handler: web::Data<PageHandler>
+) -> Result<HttpResponse> {
+    handler.not_found(req).await
+}
+
+async fn import_markdown_handler(
+    req: HttpRequest,
+    archive: web::Bytes,
+    handler: web::Data<ImportHandler>
+) -> Result<HttpResponse> {
+    handler.import_markdown(req, archive).await
+}
+
+async fn import_ndjson_handler(
+    req: HttpRequest,
+    body: web::Payload,
+    handler: web::Data<ImportHandler>
+) -> Result<HttpResponse> {
+    handler.import_ndjson(req, body).await
+}
+
+async fn export_markdown_handler(
+    req: HttpRequest,
+    handler: web::Data<ExportHandler>
+) -> Result<HttpResponse> {
+    handler.export_markdown(req).await
+}
+
+async fn export_ndjson_handler(
+    req: HttpRequest,
+    handler: web::Data<ExportHandler>
+) -> Result<HttpResponse> {
+    handler.export_ndjson(req).await
+}
+
+async fn list_tokens_handler(
+    req: HttpRequest,
+    handler: web::Data<TokenHandler>
+) -> Result<HttpResponse> {
+    handler.list_tokens(req).await
+}
+
+async fn create_token_handler(
+    req: HttpRequest,
+    token_data: web::Json<blog_rust::infrastructure::CreateTokenRequest>,
+    handler: web::Data<TokenHandler>
+) -> Result<HttpResponse> {
+    handler.create_token(req, token_data).await
+}
+
+async fn revoke_token_handler(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    handler: web::Data<TokenHandler>
+) -> Result<HttpResponse> {
+    handler.revoke_token(req, path).await
+}
+
+async fn login_handler(
+    req: HttpRequest,
+    login_data: web::Json<blog_rust::infrastructure::LoginRequest>,
+    handler: web::Data<AuthHandler>
+) -> Result<HttpResponse> {
+    handler.login(req, login_data).await
+}
+
+async fn logout_handler(
+    req: HttpRequest,
+    handler: web::Data<AuthHandler>
+) -> Result<HttpResponse> {
+    handler.logout(req).await
+}
+
+async fn list_sessions_handler(
+    req: HttpRequest,
+    handler: web::Data<AuthHandler>
+) -> Result<HttpResponse> {
+    handler.list_sessions(req).await
+}
+
+async fn revoke_session_handler(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    handler: web::Data<AuthHandler>
+) -> Result<HttpResponse> {
+    handler.revoke_session(req, path).await
+}
+
+async fn issue_jwt_handler(
+    login_data: web::Json<blog_rust::infrastructure::LoginRequest>,
+    handler: web::Data<JwtHandler>
+) -> Result<HttpResponse> {
+    handler.issue_token(login_data).await
+}
+
+async fn refresh_jwt_handler(
+    refresh_data: web::Json<blog_rust::infrastructure::RefreshTokenRequest>,
+    handler: web::Data<JwtHandler>
+) -> Result<HttpResponse> {
+    handler.refresh_token(refresh_data).await
+}
+
+async fn revoke_refresh_token_handler(
+    refresh_data: web::Json<blog_rust::infrastructure::RefreshTokenRequest>,
+    handler: web::Data<JwtHandler>
+) -> Result<HttpResponse> {
+    handler.revoke_token(refresh_data).await
+}
+
+async fn oidc_login_handler(
+    query: web::Query<blog_rust::infrastructure::OidcLoginQuery>,
+    handler: web::Data<OidcHandler>
+) -> Result<HttpResponse> {
+    handler.login(query).await
+}
+
+async fn oidc_two_factor_handler(
+    req: HttpRequest,
+    form: web::Form<blog_rust::infrastructure::TwoFactorCodeRequest>,
+    handler: web::Data<OidcHandler>
+) -> Result<HttpResponse> {
+    handler.second_factor(req, form).await
+}
+
+async fn oidc_callback_handler(
+    req: HttpRequest,
+    query: web::Query<blog_rust::infrastructure::OidcCallbackQuery>,
+    handler: web::Data<OidcHandler>
+) -> Result<HttpResponse> {
+    handler.callback(req, query).await
+}
+
+async fn two_factor_status_handler(
+    req: HttpRequest,
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.status(req).await
+}
+
+async fn begin_two_factor_enrollment_handler(
+    req: HttpRequest,
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.begin_enrollment(req).await
+}
+
+async fn confirm_two_factor_enrollment_handler(
+    req: HttpRequest,
+    code_data: web::Json<blog_rust::infrastructure::TwoFactorCodeRequest>,
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.confirm_enrollment(req, code_data).await
+}
+
+async fn regenerate_recovery_codes_handler(
+    req: HttpRequest,
+    code_data: web::Json<blog_rust::infrastructure::TwoFactorCodeRequest>,
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.regenerate_recovery_codes(req, code_data).await
+}
+
+async fn disable_two_factor_handler(
+    req: HttpRequest,
+    code_data: web::Json<blog_rust::infrastructure::TwoFactorCodeRequest>,
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.disable(req, code_data).await
+}
+
+async fn two_factor_policy_handler(
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.policy().await
+}
+
+async fn set_two_factor_policy_handler(
+    req: HttpRequest,
+    policy_data: web::Json<blog_rust::infrastructure::TwoFactorPolicy>,
+    handler: web::Data<TwoFactorHandler>
+) -> Result<HttpResponse> {
+    handler.set_policy(req, policy_data).await
+}
+
+async fn live_handler(
+    handler: web::Data<HealthHandler>
+) -> Result<HttpResponse> {
+    handler.live().await
+}
+
+async fn ready_handler(
+    handler: web::Data<HealthHandler>
+) -> Result<HttpResponse> {
+    handler.ready().await
+}
+
+async fn get_metrics_handler(
+    handler: web::Data<MetricsHandler>
+) -> Result<HttpResponse> {
+    handler.metrics().await
+}
+
+async fn jwks_handler(
+    handler: web::Data<JwtHandler>
+) -> Result<HttpResponse> {
+    handler.jwks().await
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    println!("🚀 Starting Blog Rust Server with Hexagonal Architecture...");
+    let cli = Cli::parse();
 
     // Infrastructure Layer: Database connection
     let pool = establish_connection_pool();
@@ -66,24 +410,286 @@ async fn main() -> std::io::Result<()> {
     let post_repository_arc: Arc<dyn blog_rust::domain::PostRepository> = Arc::new(post_repository);
 
     // Application Layer: Service/Use Cases
-    let post_service = Arc::new(PostService::new(post_repository_arc));
+    let post_service = Arc::new(PostService::new(post_repository_arc, Arc::new(PostMetrics)));
+    let token_service = Arc::new(TokenService::new(Arc::new(SqliteApiTokenRepository::new(Arc::clone(&pool_arc)))));
+
+    // Infrastructure Layer: Site settings for absolute links and feed metadata
+    let site_config = Arc::new(SiteConfig::from_env());
+    let session_config = Arc::new(SessionConfig::from_env(&site_config));
+
+    let user_repository: Arc<dyn blog_rust::domain::UserRepository> = Arc::new(SqliteUserRepository::new(Arc::clone(&pool_arc)));
+    let two_factor_service = Arc::new(TwoFactorService::new(
+        Arc::clone(&user_repository),
+        Arc::new(SqliteRecoveryCodeRepository::new(Arc::clone(&pool_arc))),
+        Arc::new(SqliteTwoFactorPolicyRepository::new(Arc::clone(&pool_arc))),
+        site_config.title.clone(),
+    ));
+    let auth_service = Arc::new(AuthService::new(
+        Arc::clone(&user_repository),
+        Arc::new(SqliteSessionRepository::new(Arc::clone(&pool_arc))),
+        Arc::new(Argon2PasswordHasher::new()),
+        Arc::clone(&two_factor_service),
+        session_config.ttl,
+    ));
+
+    // Infrastructure Layer: JWT signing keys, loaded from JWT_SECRETS or JWT_KEY_FILES
+    let jwt_config = Arc::new(JwtConfig::from_env(&site_config));
+    let jwt_codec = Arc::new(JwtCodec::new(&jwt_config).map_err(std::io::Error::other)?);
+    let jwt_service = Arc::new(JwtService::new(
+        Arc::clone(&user_repository),
+        Arc::new(SqliteRefreshTokenRepository::new(Arc::clone(&pool_arc))),
+        Arc::clone(&jwt_codec) as Arc<dyn blog_rust::domain::AccessTokenCodec>,
+        Arc::clone(&two_factor_service),
+        jwt_config.access_ttl,
+        jwt_config.refresh_ttl,
+    ));
+
+    // Infrastructure Layer: Single sign-on, when OIDC_ISSUER names a provider
+    let oidc_service = match OidcConfig::from_env(&site_config) {
+        Some(oidc_config) => {
+            let role_mapping = oidc_config.role_mapping.clone();
+            let provider = OidcClient::new(oidc_config).map_err(std::io::Error::other)?;
+            Some(Arc::new(OidcService::new(
+                Arc::new(provider),
+                user_repository,
+                Arc::new(SqliteUserIdentityRepository::new(Arc::clone(&pool_arc))),
+                role_mapping,
+            )))
+        }
+        None => None,
+    };
+
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => {
+            let services = Services {
+                post_service,
+                token_service,
+                auth_service,
+                jwt_service,
+                two_factor_service,
+                oidc_service,
+            };
+            serve(services, pool_arc, site_config, session_config, jwt_config, jwt_codec).await
+        }
+        Command::ExportSite { out_dir } => {
+            cli::export_site(StaticSiteExporter::new(post_service, site_config), &out_dir).await
+        }
+        Command::ImportMarkdown { paths } => {
+            cli::import_markdown(MarkdownImporter::new(post_service), &paths).await
+        }
+        Command::ImportWxr { path, dry_run } => {
+            cli::import_wxr(WxrImporter::new(post_service), &path, dry_run).await
+        }
+        Command::ExportMarkdown { output } => {
+            let exporter = MarkdownExporter::new(post_service, site_config.media_dir.clone());
+            cli::export_markdown(exporter, &output).await
+        }
+        Command::Token { command } => cli::token(&token_service, command).await,
+        Command::User { command } => cli::user(&auth_service, &jwt_service, &two_factor_service, command).await,
+    }
+}
+
+/// Application services shared by the HTTP handlers and middleware
+struct Services {
+    post_service: Arc<PostService>,
+    token_service: Arc<TokenService>,
+    auth_service: Arc<AuthService>,
+    jwt_service: Arc<JwtService>,
+    two_factor_service: Arc<TwoFactorService>,
+    /// `None` when single sign-on is not configured
+    oidc_service: Option<Arc<OidcService>>,
+}
+
+async fn serve(
+    services: Services,
+    pool: Arc<DbPool>,
+    site_config: Arc<SiteConfig>,
+    session_config: Arc<SessionConfig>,
+    jwt_config: Arc<JwtConfig>,
+    jwt_codec: Arc<JwtCodec>,
+) -> std::io::Result<()> {
+    let Services { post_service, token_service, auth_service, jwt_service, two_factor_service, oidc_service } = services;
+
+    init_logging();
+    tracing::info!("starting Blog Rust server");
+    if session_config.ephemeral {
+        tracing::warn!("SESSION_SECRET is not set; sessions will not survive a restart");
+    }
+    if jwt_config.ephemeral() {
+        tracing::warn!("no JWT signing key is configured; access tokens will not survive a restart");
+    }
+    if let Some(oidc_service) = &oidc_service {
+        tracing::info!(issuer = oidc_service.issuer(), "single sign-on enabled");
+    }
 
     // Infrastructure Layer: Web handlers
-    let post_handler = PostHandler::new(post_service);
+    let post_handler = PostHandler::new(Arc::clone(&post_service));
+    let feed_handler = FeedHandler::new(Arc::clone(&post_service), Arc::clone(&site_config));
+    let sitemap_handler = SitemapHandler::new(Arc::clone(&post_service), Arc::clone(&site_config));
+    let page_handler = PageHandler::new(Arc::clone(&post_service), Arc::clone(&site_config));
+    let token_handler = TokenHandler::new(Arc::clone(&token_service));
+    let auth_handler = AuthHandler::new(Arc::clone(&auth_service), Arc::clone(&session_config));
+    let jwt_handler = JwtHandler::new(Arc::clone(&auth_service), Arc::clone(&jwt_service), jwt_codec);
+    let two_factor_handler = TwoFactorHandler::new(Arc::clone(&two_factor_service));
+    let oidc_handler = OidcHandler::new(
+        oidc_service,
+        Arc::clone(&auth_service),
+        Arc::clone(&session_config),
+        Arc::clone(&site_config),
+    );
+    let import_handler = ImportHandler::new(
+        Arc::new(MarkdownImporter::new(Arc::clone(&post_service))),
+        Arc::new(NdjsonImporter::new(Arc::clone(&post_service))),
+    );
+    let health_handler = HealthHandler::new(Arc::clone(&pool));
+    let metrics_handler = MetricsHandler::new(pool);
+    let export_handler = ExportHandler::new(
+        Arc::new(MarkdownExporter::new(Arc::clone(&post_service), site_config.media_dir.clone())),
+        Arc::new(NdjsonExporter::new(post_service)),
+    );
+
+    // Infrastructure Layer: Rate limits, shared by every worker
+    let rate_limiter = web::Data::new(RateLimiter::new(
+        Arc::new(InMemoryRateLimitStore::new()),
+        RateLimitConfig::from_env(),
+    ));
+
+    // Infrastructure Layer: Cross-origin access and security headers
+    let cors_config = Arc::new(CorsConfig::from_env());
+    let security_headers_config = Arc::new(SecurityHeadersConfig::from_env(&site_config));
+    if cors_config.enabled() {
+        tracing::info!(origins = %cors_config.allowed_origins.join(", "), "CORS enabled");
+    }
 
-    println!("✅ Dependencies injected successfully");
-    println!("🌐 Server starting on http://127.0.0.1:8080");
+    tracing::info!(address = "http://127.0.0.1:8080", "listening");
 
     HttpServer::new(move || {
         App::new()
+            // Registered first so it runs inside `problem_details`, which renders its 401s
+            .wrap(from_fn(require_auth_for_writes))
+            // Outside the auth middleware, so requests with bad credentials are counted too
+            .wrap(from_fn(rate_limit))
+            .wrap(problem_details())
+            // Outside `problem_details`, so error responses get the headers too
+            .wrap(from_fn(security_headers))
+            .wrap(Condition::new(cors_config.enabled(), cors(&cors_config)))
+            // Outside everything that can change the status, so the recorded one is the one sent
+            .wrap(from_fn(http_metrics))
+            // Outermost, so every log line of a request carries its ID
+            .wrap(from_fn(request_id))
+            .app_data(rate_limiter.clone())
+            .app_data(web::Data::from(Arc::clone(&security_headers_config)))
+            .app_data(web::Data::from(Arc::clone(&token_service)))
+            .app_data(web::Data::from(Arc::clone(&auth_service)))
+            .app_data(web::Data::from(Arc::clone(&jwt_service)))
+            .app_data(web::Data::from(Arc::clone(&two_factor_service)))
+            .app_data(web::Data::from(Arc::clone(&session_config)))
             .app_data(web::Data::new(post_handler.clone()))
-            .service(hello)
-            .service(health_check)
-            .route("/posts", web::get().to(get_all_posts_handler))
-            .route("/posts/{id}", web::get().to(get_post_by_id_handler))
-            .route("/posts", web::post().to(create_post_handler))
-            .route("/posts/{id}", web::put().to(update_post_handler))
-            .route("/posts/{id}", web::delete().to(delete_post_handler))
+            .app_data(web::Data::new(feed_handler.clone()))
+            .app_data(web::Data::new(sitemap_handler.clone()))
+            .app_data(web::Data::new(page_handler.clone()))
+            .app_data(web::Data::new(import_handler.clone()))
+            .app_data(web::Data::new(export_handler.clone()))
+            .app_data(web::Data::new(token_handler.clone()))
+            .app_data(web::Data::new(auth_handler.clone()))
+            .app_data(web::Data::new(jwt_handler.clone()))
+            .app_data(web::Data::new(two_factor_handler.clone()))
+            .app_data(web::Data::new(oidc_handler.clone()))
+            .app_data(web::Data::new(health_handler.clone()))
+            .app_data(web::Data::new(metrics_handler.clone()))
+            .route("/health/live", web::get().to(live_handler))
+            .route("/health/ready", web::get().to(ready_handler))
+            .route("/metrics", web::get().to(get_metrics_handler))
+            .route("/", web::get().to(index_page_handler))
+            .route("/page/{page}", web::get().to(index_page_n_handler))
+            .route("/blog/{slug}", web::get().to(post_page_handler))
+            .route("/tags/{slug}", web::get().to(tag_page_handler))
+            .route("/tags/{slug}/page/{page}", web::get().to(tag_page_n_handler))
+            .service(
+                web::resource("/posts")
+                    .route(web::get().to(get_all_posts_handler))
+                    .route(web::post().to(create_post_handler)),
+            )
+            // Registered before `/posts/{id}`, which would otherwise match them
+            .service(
+                web::resource("/posts/export.ndjson")
+                    .wrap(from_fn(require_auth))
+                    .route(web::get().to(export_ndjson_handler)),
+            )
+            .route("/posts/import.ndjson", web::post().to(import_ndjson_handler))
+            .route("/posts/batch", web::post().to(batch_posts_handler))
+            .service(
+                web::resource("/posts/{id}")
+                    .app_data(merge_patch_config())
+                    .route(web::get().to(get_post_by_id_handler))
+                    .route(web::put().to(update_post_handler))
+                    .route(web::patch().to(patch_post_handler))
+                    .route(web::delete().to(delete_post_handler)),
+            )
+            .route("/posts/slug/{slug}", web::get().to(get_post_by_slug_handler))
+            .route("/slug-redirects/{slug}", web::delete().to(release_slug_redirect_handler))
+            .route("/feed.rss", web::get().to(rss_feed_handler))
+            .route("/feed.atom", web::get().to(atom_feed_handler))
+            .route("/feed.json", web::get().to(json_feed_handler))
+            .route("/tags/{slug}/feed.rss", web::get().to(tag_rss_feed_handler))
+            .route("/tags/{slug}/feed.atom", web::get().to(tag_atom_feed_handler))
+            .route("/tags/{slug}/feed.json", web::get().to(tag_json_feed_handler))
+            .route("/sitemap.xml", web::get().to(sitemap_xml_handler))
+            .route("/sitemaps/{page}.xml", web::get().to(sitemap_page_handler))
+            .route("/robots.txt", web::get().to(robots_handler))
+            .service(
+                Files::new("/media", &site_config.media_dir)
+                    .default_handler(web::to(not_found_page_handler)),
+            )
+            .service(
+                web::resource("/import/markdown")
+                    .app_data(web::PayloadConfig::new(MAX_ARCHIVE_BYTES))
+                    .route(web::post().to(import_markdown_handler)),
+            )
+            // Exports include drafts, so reading them needs credentials and the export policy too
+            .service(
+                web::resource("/export")
+                    .wrap(from_fn(require_auth))
+                    .route(web::get().to(export_markdown_handler)),
+            )
+            .service(
+                web::resource("/tokens")
+                    .wrap(from_fn(require_auth))
+                    .route(web::get().to(list_tokens_handler))
+                    .route(web::post().to(create_token_handler)),
+            )
+            .route("/tokens/{id}", web::delete().to(revoke_token_handler))
+            .route("/auth/login", web::post().to(login_handler))
+            .route("/auth/logout", web::post().to(logout_handler))
+            .service(
+                web::resource("/auth/sessions")
+                    .wrap(from_fn(require_auth))
+                    .route(web::get().to(list_sessions_handler)),
+            )
+            .route("/auth/sessions/{id}", web::delete().to(revoke_session_handler))
+            .route("/auth/token", web::post().to(issue_jwt_handler))
+            .route("/auth/token/refresh", web::post().to(refresh_jwt_handler))
+            .route("/auth/token/revoke", web::post().to(revoke_refresh_token_handler))
+            .service(
+                web::resource("/auth/two-factor")
+                    .wrap(from_fn(require_auth))
+                    .route(web::get().to(two_factor_status_handler))
+                    .route(web::delete().to(disable_two_factor_handler)),
+            )
+            .route("/auth/two-factor/enrollment", web::post().to(begin_two_factor_enrollment_handler))
+            .route("/auth/two-factor/enrollment/confirm", web::post().to(confirm_two_factor_enrollment_handler))
+            .route("/auth/two-factor/recovery-codes", web::post().to(regenerate_recovery_codes_handler))
+            .service(
+                web::resource("/auth/two-factor/policy")
+                    .wrap(from_fn(require_auth))
+                    .route(web::get().to(two_factor_policy_handler))
+                    .route(web::put().to(set_two_factor_policy_handler)),
+            )
+            .route("/.well-known/jwks.json", web::get().to(jwks_handler))
+            .route("/auth/oidc/login", web::get().to(oidc_login_handler))
+            .route("/auth/oidc/callback", web::get().to(oidc_callback_handler))
+            .route("/auth/oidc/two-factor", web::post().to(oidc_two_factor_handler))
+            .default_service(web::to(not_found_page_handler))
     })
     .bind(("127.0.0.1", 8080))?
     .run()